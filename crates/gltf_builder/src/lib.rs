@@ -501,7 +501,7 @@ impl glTFBuilder {
 
     /// Inserts [`Vec<u32>`] data, like Indices.
     fn insert_vec_u32(&mut self, name: &str, data: &[u32]) -> Result<Index<Accessor>> {
-        let buffer = self.auto_buffer(data.len() * size_of::<u32>());
+        let buffer = self.auto_buffer(std::mem::size_of_val(data));
         self.pad_to_alignment(buffer, size_of::<u32>());
         let buffer_view = self.insert_write_gltf(Some(name), buffer, data);
         self.create_accessor_vec_u32(Some(name), buffer_view, data)
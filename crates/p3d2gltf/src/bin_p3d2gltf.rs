@@ -2,12 +2,8 @@ use clap::{arg, command, value_parser};
 use indicatif::ProgressBar;
 use p3d2gltf::export_all_to_gltf;
 // use p3d2gltf::export_all_to_gltf;
-use p3dparse::Bytes;
-use std::{
-    fs::File,
-    io::Read,
-    path::{Path, PathBuf},
-};
+use p3dparse::{chunk::type_identifiers::ChunkType, map_file, scan::scan_file};
+use std::path::{Path, PathBuf};
 use walkdir::WalkDir;
 
 fn main() {
@@ -23,7 +19,10 @@ fn main() {
                 .value_parser(value_parser!(PathBuf)),
         )
         .arg(arg!(-r --recurse "Recurse into directories").value_parser(value_parser!(bool)))
-        .arg(arg!(--list "List valid meshes to export").value_parser(value_parser!(bool)))
+        .arg(
+            arg!(--list "List mesh and skin chunks, without checking that they can be exported")
+                .value_parser(value_parser!(bool)),
+        )
         .get_matches();
 
     match (
@@ -47,16 +46,9 @@ fn main() {
                 let bar = ProgressBar::new(files.len() as u64);
 
                 for entry in files {
-                    match File::open(entry.path()) {
-                        Ok(input) => {
-                            match export_file(input, entry.path(), dest, matches.get_flag("list")) {
-                                Ok(_) => {}
-                                Err(e) => println!("{:?}", e),
-                            }
-                        }
-                        Err(e) => {
-                            println!("Unable to read file {:?}: {:?}", entry, e)
-                        }
+                    match export_file(entry.path(), dest, matches.get_flag("list")) {
+                        Ok(_) => {}
+                        Err(e) => println!("{:?}", e),
                     }
 
                     bar.inc(1);
@@ -64,9 +56,7 @@ fn main() {
 
                 bar.finish()
             } else if src.is_file() {
-                let input =
-                    File::open(src).unwrap_or_else(|_| panic!("Failed to open file {:?}", src));
-                export_file(input, src, dest, matches.get_flag("list")).unwrap();
+                export_file(src, dest, matches.get_flag("list")).unwrap();
             } else {
                 eprintln!("{:?} is not a file or directory", src);
             }
@@ -75,49 +65,30 @@ fn main() {
     }
 }
 
-fn export_file(
-    mut input: File,
-    src: &Path,
-    dest: &Path,
-    list: bool,
-) -> Result<(), eyre::ErrReport> {
-    let mut input_bytes = Vec::new();
-    input.read_to_end(&mut input_bytes).unwrap();
+fn export_file(src: &Path, dest: &Path, list: bool) -> Result<(), eyre::ErrReport> {
+    if list {
+        return list_file(src);
+    }
 
-    match p3dparse::parse_file(Bytes::from(input_bytes)) {
+    match p3dparse::parse_path(src) {
         Ok(p3d_file) => {
             std::fs::create_dir_all(dest)
                 .unwrap_or_else(|_| panic!("Failed to create directory {:?}", dest));
 
-            if list {
-                match p3dhl::parse_high_level_types(&p3d_file) {
-                    Ok(hlt) => {
-                        for x in hlt {
-                            match x {
-                                p3dhl::HighLevelType::Mesh(m) => println!("Mesh: {}", m.name),
-                                p3dhl::HighLevelType::Skin(s) => println!("Skin: {}", s.name),
-                                _ => {}
-                            }
-                        }
-                    }
-                    Err(e) => return Err(eyre::eyre!("Failed to parse file: {:#?}", e)),
+            match export_all_to_gltf(src, &p3d_file, dest) {
+                Ok(_) => {
+                    // if let Some(name) = src.file_name() {
+                    //     println!("Exported file {}", name.to_string_lossy());
+                    // } else {
+                    //     println!("Exported file {:?}", src);
+                    // }
                 }
-            } else {
-                match export_all_to_gltf(src, &p3d_file, dest) {
-                    Ok(_) => {
-                        // if let Some(name) = src.file_name() {
-                        //     println!("Exported file {}", name.to_string_lossy());
-                        // } else {
-                        //     println!("Exported file {:?}", src);
-                        // }
-                    }
-                    Err(e) => {
-                        return Err(eyre::eyre!(
-                            "Failed to export file {:?} due to error: {}",
-                            src,
-                            e
-                        ));
-                    }
+                Err(e) => {
+                    return Err(eyre::eyre!(
+                        "Failed to export file {:?} due to error: {}",
+                        src,
+                        e
+                    ));
                 }
             }
         }
@@ -132,3 +103,30 @@ fn export_file(
 
     Ok(())
 }
+
+/// Only the mesh and skin chunks themselves get decoded, not their vertex data. That's much faster on
+/// big files, but every mesh and skin chunk gets listed, even skins whose skeleton is missing and
+/// meshes whose data is broken, which would make a real export fail.
+fn list_file(src: &Path) -> Result<(), eyre::ErrReport> {
+    let scanned = map_file(src)
+        .and_then(scan_file)
+        .map_err(|e| eyre::eyre!("Failed to list file {:?}, could not scan file: {}", src, e))?;
+
+    for (index, chunk) in scanned.chunks().iter().enumerate() {
+        let kind = match chunk.typ {
+            ChunkType::Mesh => "Mesh",
+            ChunkType::Skin => "Skin",
+            _ => continue,
+        };
+        match scanned.decode(index) {
+            Ok(data) => {
+                if let Some(name) = data.get_name() {
+                    println!("{}: {}", kind, name.0)
+                }
+            }
+            Err(e) => println!("Skipping {} in {:?}: {}", kind, src, e),
+        }
+    }
+
+    Ok(())
+}
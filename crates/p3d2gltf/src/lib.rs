@@ -8,7 +8,10 @@ use gltf_json::{
 use itertools::Itertools;
 use nalgebra::Transform3;
 use p3dhl::{Mesh, PrimGroup, Shader, Skeleton, SkeletonJoint, Skin};
-use p3dparse::chunk::{data::kinds::image::ImageFormat, Chunk};
+use p3dparse::{
+    chunk::{data::kinds::image::ImageFormat, index::ChunkIndex, Chunk},
+    ParseOptions,
+};
 use std::{
     collections::{HashMap, HashSet},
     path::Path,
@@ -83,8 +86,7 @@ fn export_primgroup_to_gltf(
             let (joints, weights): (Vec<[u16; 4]>, Vec<[f32; 4]>) = matrices
                 .iter()
                 .zip(weights.iter())
-                .enumerate()
-                .map(|(_idx, (affecting_joints, joint_weights))| {
+                .map(|(affecting_joints, joint_weights)| {
                     let real_joints = affecting_joints.map(|f| palette[f as usize] as u16);
 
                     let x_weight = joint_weights.x;
@@ -143,8 +145,7 @@ fn export_primgroup_to_gltf(
         (Some(matrices), Some(palette), None) => {
             let (joints, weights): (Vec<[u16; 4]>, Vec<[f32; 4]>) = matrices
                 .iter()
-                .enumerate()
-                .map(|(_idx, affecting_joints)| {
+                .map(|affecting_joints| {
                     // In the case where there is no weight chunk, we want a weight of 1 for the first joint entry.
                     let real_joint = [palette[affecting_joints[0] as usize] as u16, 0, 0, 0];
                     (real_joint, [1., 0., 0., 0.])
@@ -217,11 +218,7 @@ fn export_shader_to_gltf(
         shader.name,
         None,
         gltf_json::material::AlphaMode::Opaque,
-        if let Some(two_sided) = shader.two_sided {
-            two_sided
-        } else {
-            false
-        },
+        shader.two_sided.unwrap_or_default(),
         gltf_json::material::PbrMetallicRoughness {
             base_color_factor: gltf_json::material::PbrBaseColorFactor([1., 1., 1., 1.]),
             base_color_texture: texture_idx.map(|texture| gltf_json::texture::Info {
@@ -438,7 +435,14 @@ pub fn export_all_to_gltf(filename: &Path, tree: &[Chunk], dest: &Path) -> Resul
     builder.set_generator(&format!("Khronos glTF p3d2gltf v{}", VERSION));
 
     let mut nodes = vec![];
-    let hltypes = p3dhl::parse_high_level_types(tree)?;
+    let (hltypes, diagnostics) = p3dhl::parse_high_level_types_with_options(
+        tree,
+        &ChunkIndex::new(tree),
+        &ParseOptions::lenient(),
+    )?;
+    for diagnostic in diagnostics {
+        eprintln!("Warning: {}", diagnostic);
+    }
 
     for hlt in hltypes {
         match hlt {
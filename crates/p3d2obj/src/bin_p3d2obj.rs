@@ -1,7 +1,6 @@
 use clap::{arg, command, value_parser};
 use p3d2obj::export_all_to_obj;
-use p3dparse::Bytes;
-use std::{fs::File, io::Read, path::PathBuf};
+use std::path::PathBuf;
 
 fn main() {
     let matches = command!()
@@ -22,12 +21,8 @@ fn main() {
         matches.get_one::<PathBuf>("out"),
     ) {
         (Some(src), Some(dest)) => {
-            let mut input =
-                File::open(src).unwrap_or_else(|_| panic!("Failed to open file {:?}", src));
-
-            let mut input_bytes = Vec::new();
-            input.read_to_end(&mut input_bytes).unwrap();
-            let p3d_file = p3dparse::parse_file(Bytes::from(input_bytes)).unwrap();
+            let p3d_file = p3dparse::parse_path(src)
+                .unwrap_or_else(|e| panic!("Failed to parse file {:?}: {}", src, e));
 
             std::fs::create_dir_all(dest)
                 .unwrap_or_else(|_| panic!("Failed to create directory {:?}", dest));
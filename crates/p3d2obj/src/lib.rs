@@ -1,8 +1,12 @@
 use itertools::Itertools;
 use p3dhl::{HighLevelType, Mesh, Skin};
-use p3dparse::chunk::{
-    data::kinds::{image::ImageFormat, mesh::PrimitiveType, shader_param::ShaderParamValue},
-    Chunk,
+use p3dparse::{
+    chunk::{
+        data::kinds::{image::ImageFormat, mesh::PrimitiveType, shader_param::ShaderParamValue},
+        index::ChunkIndex,
+        Chunk,
+    },
+    ParseOptions,
 };
 use std::{
     fs::File,
@@ -366,7 +370,14 @@ impl<'a> WriteObj for Skin<'a> {
 }
 
 pub fn export_all_to_obj(tree: &[Chunk], dest: &Path) -> Result<()> {
-    let high_level_types = p3dhl::parse_high_level_types(tree)?;
+    let (high_level_types, diagnostics) = p3dhl::parse_high_level_types_with_options(
+        tree,
+        &ChunkIndex::new(tree),
+        &ParseOptions::lenient(),
+    )?;
+    for diagnostic in diagnostics {
+        eprintln!("Warning: {}", diagnostic);
+    }
 
     for typ in high_level_types {
         match typ {
@@ -1,17 +1,29 @@
+use std::collections::BTreeMap;
+
 use eyre::eyre;
-use p3dparse::chunk::{
-    data::{
-        kinds::{
-            self,
-            image::ImageFormat,
-            mesh::{OldPrimGroup, PrimitiveType},
-            shader_param::{ShaderParam, ShaderParamValue},
-            shared::{Colour, Matrix, Vector2, Vector3},
-        }, data_enum::ChunkData,
+use p3dparse::{
+    chunk::{
+        data::{
+            data_enum::ChunkData,
+            kinds::{
+                self,
+                frontend::{FrontendMultiText, FrontendPolygon},
+                gameattr::GameAttrParamValue,
+                image::ImageFormat,
+                light::{LightConeParam, LightDecayRange, LightType},
+                mesh::{OldPrimGroup, PrimitiveType},
+                shader_param::{ShaderParam, ShaderParamValue},
+                shared::{Colour, Matrix, Vector2, Vector3},
+            },
+        },
+        index::ChunkIndex,
+        type_identifiers::ChunkType,
+        Chunk,
     },
-    type_identifiers::ChunkType,
-    Chunk,
+    error::{Diagnostic, DiagnosticKind},
+    ParseOptions,
 };
+use serde::Serialize;
 
 pub type Result<T> = std::result::Result<T, eyre::Error>;
 
@@ -19,6 +31,12 @@ pub trait FromChunk<'a> {
     type Output;
 
     fn from_chunk(chunk: &'a Chunk, tree: &'a [Chunk]) -> Result<Self::Output>;
+
+    /// Like [`FromChunk::from_chunk`], but looks up chunks referenced by name in an index that's
+    /// already been built, instead of building one for every call.
+    fn from_chunk_with_index(chunk: &'a Chunk, tree: &'a [Chunk], _index: &ChunkIndex) -> Result<Self::Output> {
+        Self::from_chunk(chunk, tree)
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, PartialOrd)]
@@ -175,38 +193,56 @@ pub struct AllTextures<'a> {
 }
 
 impl<'a> AllTextures<'a> {
-    fn from_data(tree: &'a [Chunk]) -> Self {
-        let mut textures = Self {
-            textures: vec![],
-        };
+    fn from_data(tree: &'a [Chunk], index: &ChunkIndex) -> Self {
+        AllTextures {
+            textures: index
+                .all(tree, ChunkType::Texture)
+                .filter_map(|f| texture_data(f, tree))
+                .collect(),
+        }
+    }
+}
 
-        textures.textures = tree
-            .iter()
-            .filter(|f| f.typ == ChunkType::Texture)
-            // Filter for valid data & in active shader list
-            .filter_map(|f| {
-                if let ChunkData::Texture(name, _, _) = &f.data {
-                    Some((&name.0 as &str, f))
-                } else {
-                    None
-                }
-            })
-            .filter_map(|(name, f)| {
-                if let Ok(image_chunk) = f.get_child(tree, 0) {
-                    if let ChunkData::Image(_, _, data) = &image_chunk.data {
-                        if let Ok(image_raw) = image_chunk.get_child(tree, 0) {
-                            if let ChunkData::ImageRaw(raw) = &image_raw.data {
-                                return Some((name, data.image_format, &raw.data as &[u8]));
-                            }
-                        }
+/// Name, format and raw image data of a Texture chunk, if it has an Image with data in it.
+fn texture_data<'a>(chunk: &'a Chunk, tree: &'a [Chunk]) -> Option<(&'a str, ImageFormat, &'a [u8])> {
+    if let ChunkData::Texture(name, _, _) = &chunk.data {
+        if let Ok(image_chunk) = chunk.get_child(tree, 0) {
+            if let ChunkData::Image(_, _, data) = &image_chunk.data {
+                if let Ok(image_raw) = image_chunk.get_child(tree, 0) {
+                    if let ChunkData::ImageRaw(raw) = &image_raw.data {
+                        return Some((&name.0, data.image_format, &raw.data as &[u8]));
                     }
                 }
-                None
-            })
-            .collect();
-    
-        textures
+            }
+        }
     }
+    None
+}
+
+/// Every texture used by a TEX param of one of these shaders, once each, in the order they're used.
+fn shader_textures<'a>(
+    shaders: &[Shader<'a>],
+    tree: &'a [Chunk],
+    index: &ChunkIndex,
+) -> Vec<(&'a str, ImageFormat, &'a [u8])> {
+    let mut textures: Vec<(&'a str, ImageFormat, &'a [u8])> = Vec::new();
+
+    for param in shaders.iter().flat_map(|shader| &shader.params) {
+        let ShaderParamValue::Texture(texture) = &param.value else {
+            continue;
+        };
+        if param.param != "TEX" || textures.iter().any(|(name, _, _)| name == texture) {
+            continue;
+        }
+        if let Some(data) = index
+            .get(tree, ChunkType::Texture, texture)
+            .and_then(|chunk| texture_data(chunk, tree))
+        {
+            textures.push(data);
+        }
+    }
+
+    textures
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -223,6 +259,7 @@ impl<'a> Mesh<'a> {
         name: &'a str,
         num_prim_groups: u32,
         tree: &'a [Chunk],
+        index: &ChunkIndex,
     ) -> Result<Self> {
         let mut mesh = Mesh {
             name,
@@ -234,49 +271,14 @@ impl<'a> Mesh<'a> {
         for child in chunk.get_children_of_type(tree, ChunkType::OldPrimGroup) {
             let group = PrimGroup::from_chunk(child, tree)?;
 
-            if let Some(shader) = tree.iter().find(|c| match (&c.typ, &c.data) {
-                (ChunkType::Shader, ChunkData::Shader(name, _, _)) => name.0 == group.shader,
-                _ => false,
-            }) {
+            if let Some(shader) = index.get(tree, ChunkType::Shader, group.shader) {
                 mesh.shaders.push(Shader::from_chunk(shader, tree)?);
             }
 
             mesh.prim_groups.push(group);
         }
 
-        mesh.textures = tree
-            .iter()
-            .filter(|f| f.typ == ChunkType::Texture)
-            // Filter for valid data & in active shader list
-            .filter_map(|f| {
-                if let ChunkData::Texture(name, _, _) = &f.data {
-                    // Make sure
-                    if mesh.shaders.iter().any(|f| {
-                        f.params.iter().any(|p| {
-                            p.param == "TEX" && p.value == ShaderParamValue::Texture(name.0.clone())
-                        })
-                    }) {
-                        Some((&name.0 as &str, f))
-                    } else {
-                        None
-                    }
-                } else {
-                    None
-                }
-            })
-            .filter_map(|(name, f)| {
-                if let Ok(image_chunk) = f.get_child(tree, 0) {
-                    if let ChunkData::Image(_, _, data) = &image_chunk.data {
-                        if let Ok(image_raw) = image_chunk.get_child(tree, 0) {
-                            if let ChunkData::ImageRaw(raw) = &image_raw.data {
-                                return Some((name, data.image_format, &raw.data as &[u8]));
-                            }
-                        }
-                    }
-                }
-                None
-            })
-            .collect();
+        mesh.textures = shader_textures(&mesh.shaders, tree, index);
 
         Ok(mesh)
     }
@@ -286,9 +288,13 @@ impl<'a> FromChunk<'a> for Mesh<'a> {
     type Output = Mesh<'a>;
 
     fn from_chunk(chunk: &'a Chunk, tree: &'a [Chunk]) -> Result<Self::Output> {
+        Self::from_chunk_with_index(chunk, tree, &ChunkIndex::new(tree))
+    }
+
+    fn from_chunk_with_index(chunk: &'a Chunk, tree: &'a [Chunk], index: &ChunkIndex) -> Result<Self::Output> {
         match (&chunk.typ, &chunk.data) {
             (ChunkType::Mesh, ChunkData::Mesh(name, _version, mesh)) => {
-                Mesh::from_data(chunk, &name.0, mesh.num_prim_groups, tree)
+                Mesh::from_data(chunk, &name.0, mesh.num_prim_groups, tree, index)
             }
             (typ, data) => Err(eyre!(
                 "Mesh expected ChunkType::Mesh with ChunkData::Mesh but got a {:?} chunk with {:?}",
@@ -418,6 +424,10 @@ impl<'a> FromChunk<'a> for Skin<'a> {
     type Output = Skin<'a>;
 
     fn from_chunk(chunk: &'a Chunk, tree: &'a [Chunk]) -> Result<Self::Output> {
+        Self::from_chunk_with_index(chunk, tree, &ChunkIndex::new(tree))
+    }
+
+    fn from_chunk_with_index(chunk: &'a Chunk, tree: &'a [Chunk], index: &ChunkIndex) -> Result<Self::Output> {
         match (&chunk.typ, &chunk.data) {
             (ChunkType::Skin, ChunkData::Skin(name, _version, data)) => {
                 let mut skin = Skin {
@@ -428,70 +438,504 @@ impl<'a> FromChunk<'a> for Skin<'a> {
                     textures: Vec::new()
                 };
 
-                if let Some(skeleton) = tree.iter().find(|c| {
-                    match (&c.typ, &c.data) {
-                        (ChunkType::P3DSkeleton, ChunkData::Skeleton(name, _, _)) => {
-                            name.0 == data.skeleton_name
-                        }
-                        _ => false
-                    }
-                }) {
+                if let Some(skeleton) = index.get(tree, ChunkType::P3DSkeleton, &data.skeleton_name) {
                     skin.skeleton = Some(Skeleton::from_chunk(skeleton, tree)?);
                 }
 
                 for child in chunk.get_children_of_type(tree, ChunkType::OldPrimGroup) {
                     let group = PrimGroup::from_chunk(child, tree)?;
 
-                    if let Some(shader) = tree.iter().find(|c| match (&c.typ, &c.data) {
-                        (ChunkType::Shader, ChunkData::Shader(name, _, _)) => {
-                            name.0 == group.shader
-                        }
-                        _ => false,
-                    }) {
+                    if let Some(shader) = index.get(tree, ChunkType::Shader, group.shader) {
                         skin.shaders.push(Shader::from_chunk(shader, tree)?);
                     }
 
                     skin.prim_groups.push(group);
                 }
 
-                skin.textures = tree
-                    .iter()
-                    .filter(|f| f.typ == ChunkType::Texture)
-                    // Filter for valid data & in active shader list
-                    .filter_map(|f| {
-                        if let ChunkData::Texture(name, _, _) = &f.data {
-                            // Make sure
-                            if skin.shaders.iter().any(|f| {
-                                f.params.iter().any(|p| {
-                                    p.param == "TEX" && p.value == ShaderParamValue::Texture(name.0.clone())
+                skin.textures = shader_textures(&skin.shaders, tree, index);
+
+                Ok(skin)
+            }
+            (typ, data) => Err(eyre!(
+                "Skeleton expected ChunkType::P3DSkeleton with ChunkData::Skeleton but got a {:?} chunk with {:?}",
+                typ,
+                data
+            )),
+        }
+    }
+}
+
+/// The `1 / (constant + linear * d + squared * d^2)` falloff of a [`Light`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Attenuation {
+    pub constant: f32,
+    pub linear: f32,
+    pub squared: f32,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Light<'a> {
+    pub name: &'a str,
+    pub kind: LightType,
+    pub colour: &'a Colour,
+    pub enabled: bool,
+    pub attenuation: Attenuation,
+    /// Point and spot lights
+    pub position: Option<&'a Vector3>,
+    /// Directional and spot lights
+    pub direction: Option<&'a Vector3>,
+    /// Spot lights
+    pub cone: Option<&'a LightConeParam>,
+    pub decay_range: Option<&'a LightDecayRange>,
+    pub shadow: Option<bool>,
+}
+
+impl<'a> FromChunk<'a> for Light<'a> {
+    type Output = Light<'a>;
+
+    fn from_chunk(chunk: &'a Chunk, tree: &'a [Chunk]) -> Result<Self::Output> {
+        match (&chunk.typ, &chunk.data) {
+            (ChunkType::Light, ChunkData::Light(name, _version, data)) => {
+                let mut light = Light {
+                    name: &name.0,
+                    kind: data.light_type,
+                    colour: &data.colour,
+                    enabled: data.enabled > 0,
+                    attenuation: Attenuation {
+                        constant: data.constant,
+                        linear: data.linear,
+                        squared: data.squared,
+                    },
+                    position: None,
+                    direction: None,
+                    cone: None,
+                    decay_range: None,
+                    shadow: None,
+                };
+
+                for child in chunk.get_children(tree) {
+                    match &child.data {
+                        ChunkData::LightPosition(position) => light.position = Some(&position.position),
+                        ChunkData::LightDirection(direction) => light.direction = Some(&direction.direction),
+                        ChunkData::LightConeParam(cone) => light.cone = Some(cone),
+                        ChunkData::LightDecayRange(range) => light.decay_range = Some(range),
+                        ChunkData::LightShadow(shadow) => light.shadow = Some(shadow.shadow > 0),
+                        _ => {}
+                    }
+                }
+
+                Ok(light)
+            }
+            (typ, data) => Err(eyre!(
+                "Light expected ChunkType::Light with ChunkData::Light but got a {:?} chunk with {:?}",
+                typ,
+                data
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct LightGroup<'a> {
+    pub name: &'a str,
+    pub lights: Vec<Light<'a>>,
+}
+
+impl<'a> FromChunk<'a> for LightGroup<'a> {
+    type Output = LightGroup<'a>;
+
+    /// Takes either the light group itself, or a scenegraph node that refers to one by name.
+    fn from_chunk(chunk: &'a Chunk, tree: &'a [Chunk]) -> Result<Self::Output> {
+        Self::from_chunk_with_index(chunk, tree, &ChunkIndex::new(tree))
+    }
+
+    fn from_chunk_with_index(chunk: &'a Chunk, tree: &'a [Chunk], index: &ChunkIndex) -> Result<Self::Output> {
+        match (&chunk.typ, &chunk.data) {
+            (ChunkType::P3DLightGroup, ChunkData::LightGroup(name, data)) => {
+                let mut group = LightGroup {
+                    name: &name.0,
+                    lights: Vec::with_capacity(data.lights.len()),
+                };
+
+                for light in &data.lights {
+                    if let Some(light) = index.get(tree, ChunkType::Light, light) {
+                        group.lights.push(Light::from_chunk(light, tree)?);
+                    }
+                }
+
+                Ok(group)
+            }
+            (ChunkType::OldScenegraphLightGroup, ChunkData::ScenegraphLightGroup(_name, data)) => {
+                match index.get(tree, ChunkType::P3DLightGroup, &data.light_group_name) {
+                    Some(group) => LightGroup::from_chunk_with_index(group, tree, index),
+                    None => Err(eyre!("Light group {} not found", data.light_group_name)),
+                }
+            }
+            (typ, data) => Err(eyre!(
+                "LightGroup expected ChunkType::P3DLightGroup or ChunkType::OldScenegraphLightGroup but got a {:?} chunk with {:?}",
+                typ,
+                data
+            )),
+        }
+    }
+}
+
+/// The params of a GameAttr chunk, by name. Serializes to JSON as
+/// `{"name": ..., "attributes": {"param": {"Float": 1.0}, ...}}`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct GameAttributes<'a> {
+    pub name: &'a str,
+    pub attributes: BTreeMap<&'a str, &'a GameAttrParamValue>,
+}
+
+impl<'a> FromChunk<'a> for GameAttributes<'a> {
+    type Output = GameAttributes<'a>;
+
+    fn from_chunk(chunk: &'a Chunk, tree: &'a [Chunk]) -> Result<Self::Output> {
+        match (&chunk.typ, &chunk.data) {
+            (ChunkType::GameAttr, ChunkData::GameAttr(name, _version, _data)) => {
+                let attributes = chunk
+                    .get_children(tree)
+                    .filter_map(|child| match &child.data {
+                        ChunkData::GameAttrParam(param) => Some((param.param.as_str(), &param.value)),
+                        _ => None,
+                    })
+                    .collect();
+
+                Ok(GameAttributes {
+                    name: &name.0,
+                    attributes,
+                })
+            }
+            (typ, data) => Err(eyre!(
+                "GameAttributes expected ChunkType::GameAttr with ChunkData::GameAttr but got a {:?} chunk with {:?}",
+                typ,
+                data
+            )),
+        }
+    }
+}
+
+/// Where a resource a frontend page uses comes from
+#[derive(Debug, Clone, PartialEq)]
+pub struct FrontendResource<'a> {
+    pub name: &'a str,
+    pub typ: ChunkType,
+    pub filename: &'a str,
+    /// Set for everything but images
+    pub inventory_name: Option<&'a str>,
+}
+
+/// One of the strings a multi text can show
+#[derive(Debug, Clone, PartialEq)]
+pub enum FrontendText<'a> {
+    TextBible { bible: &'a str, id: &'a str },
+    HardCoded(&'a str),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum FrontendElementKind<'a> {
+    Group {
+        alpha: u32,
+        children: Vec<FrontendElement<'a>>,
+    },
+    /// Sprites and multi sprites, with the filename of each image if its resource could be found
+    Sprite {
+        images: Vec<(&'a str, Option<&'a str>)>,
+    },
+    Text {
+        data: &'a FrontendMultiText,
+        strings: Vec<FrontendText<'a>>,
+    },
+    Polygon(&'a FrontendPolygon),
+    Pure3DObject {
+        filename: &'a str,
+    },
+    Movie,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FrontendElement<'a> {
+    pub name: &'a str,
+    /// Position, size, colour and rotation, everything but groups and polygons have one
+    pub placement: Option<&'a kinds::frontend::FrontendElement>,
+    pub kind: FrontendElementKind<'a>,
+}
+
+impl<'a> FrontendElement<'a> {
+    /// Elements of a layer or group, skipping any children that aren't elements.
+    fn children_of(chunk: &'a Chunk, tree: &'a [Chunk], index: &ChunkIndex) -> Result<Vec<Self>> {
+        let mut elements = Vec::new();
+        for child in chunk.get_children(tree) {
+            if let Some(element) = Self::from_data(child, tree, index)? {
+                elements.push(element);
+            }
+        }
+        Ok(elements)
+    }
+
+    /// Pages reuse image names, so the enclosing page's own resources are checked before the rest
+    /// of the file.
+    fn image_filename(
+        chunk: &'a Chunk,
+        image: &str,
+        tree: &'a [Chunk],
+        index: &ChunkIndex,
+    ) -> Option<&'a str> {
+        let mut page = chunk.get_parent(tree);
+        while let Some(parent) = page.filter(|parent| parent.typ != ChunkType::FrontendPage) {
+            page = parent.get_parent(tree);
+        }
+
+        let resource = page
+            .into_iter()
+            .flat_map(|page| page.get_children_of_type(tree, ChunkType::FrontendImageResource))
+            .find(|resource| resource.data.get_name().is_some_and(|name| name.0 == image))
+            .or_else(|| index.get(tree, ChunkType::FrontendImageResource, image));
+
+        match resource.map(|resource| &resource.data) {
+            Some(ChunkData::FrontendImageResource(_, _, resource)) => Some(&resource.filename),
+            _ => None,
+        }
+    }
+
+    fn from_data(chunk: &'a Chunk, tree: &'a [Chunk], index: &ChunkIndex) -> Result<Option<Self>> {
+        let (name, placement, kind) = match &chunk.data {
+            ChunkData::FrontendGroup(name, _version, data) => (
+                name,
+                None,
+                FrontendElementKind::Group {
+                    alpha: data.alpha,
+                    children: Self::children_of(chunk, tree, index)?,
+                },
+            ),
+            ChunkData::FrontendMultiSprite(name, _version, data) => (
+                name,
+                Some(&data.element),
+                FrontendElementKind::Sprite {
+                    images: data
+                        .image_names
+                        .iter()
+                        .map(|image| {
+                            (
+                                image.as_str(),
+                                Self::image_filename(chunk, image, tree, index),
+                            )
+                        })
+                        .collect(),
+                },
+            ),
+            ChunkData::FrontendMultiText(name, _version, data) => (
+                name,
+                Some(&data.element),
+                FrontendElementKind::Text {
+                    data,
+                    strings: chunk
+                        .get_children(tree)
+                        .filter_map(|child| match &child.data {
+                            ChunkData::FrontendStringTextBible(text) => {
+                                Some(FrontendText::TextBible {
+                                    bible: &text.bible_name,
+                                    id: &text.string_id,
                                 })
-                            }) {
-                                Some((&name.0 as &str, f))
-                            } else {
-                                None
                             }
-                        } else {
-                            None
-                        }
-                    })
-                    .filter_map(|(name, f)| {
-                        if let Ok(image_chunk) = f.get_child(tree, 0) {
-                            if let ChunkData::Image(_, _, data) = &image_chunk.data {
-                                if let Ok(image_raw) = image_chunk.get_child(tree, 0) {
-                                    if let ChunkData::ImageRaw(raw) = &image_raw.data {
-                                        return Some((name, data.image_format, &raw.data as &[u8]));
-                                    }
-                                }
+                            ChunkData::FrontendStringHardCoded(text) => {
+                                Some(FrontendText::HardCoded(&text.string))
                             }
+                            _ => None,
+                        })
+                        .collect(),
+                },
+            ),
+            ChunkData::FrontendPolygon(name, _version, data) => {
+                (name, None, FrontendElementKind::Polygon(data))
+            }
+            ChunkData::FrontendPure3DObject(name, _version, data) => (
+                name,
+                Some(&data.element),
+                FrontendElementKind::Pure3DObject {
+                    filename: &data.pure3d_filename,
+                },
+            ),
+            ChunkData::FrontendMovie(name, _version, data) => {
+                (name, Some(&data.element), FrontendElementKind::Movie)
+            }
+            _ => return Ok(None),
+        };
+
+        Ok(Some(FrontendElement {
+            name: &name.0,
+            placement,
+            kind,
+        }))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FrontendLayer<'a> {
+    pub name: &'a str,
+    pub visible: bool,
+    pub editable: bool,
+    pub alpha: u32,
+    pub elements: Vec<FrontendElement<'a>>,
+}
+
+impl<'a> FromChunk<'a> for FrontendLayer<'a> {
+    type Output = FrontendLayer<'a>;
+
+    fn from_chunk(chunk: &'a Chunk, tree: &'a [Chunk]) -> Result<Self::Output> {
+        Self::from_chunk_with_index(chunk, tree, &ChunkIndex::new(tree))
+    }
+
+    fn from_chunk_with_index(chunk: &'a Chunk, tree: &'a [Chunk], index: &ChunkIndex) -> Result<Self::Output> {
+        match (&chunk.typ, &chunk.data) {
+            (ChunkType::FrontendLayer, ChunkData::FrontendLayer(name, _version, data)) => Ok(FrontendLayer {
+                name: &name.0,
+                visible: data.visible > 0,
+                editable: data.editable > 0,
+                alpha: data.alpha,
+                elements: FrontendElement::children_of(chunk, tree, index)?,
+            }),
+            (typ, data) => Err(eyre!(
+                "FrontendLayer expected ChunkType::FrontendLayer with ChunkData::FrontendLayer but got a {:?} chunk with {:?}",
+                typ,
+                data
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FrontendPage<'a> {
+    pub name: &'a str,
+    pub resolution: (u32, u32),
+    pub resources: Vec<FrontendResource<'a>>,
+    pub layers: Vec<FrontendLayer<'a>>,
+}
+
+impl<'a> FromChunk<'a> for FrontendPage<'a> {
+    type Output = FrontendPage<'a>;
+
+    fn from_chunk(chunk: &'a Chunk, tree: &'a [Chunk]) -> Result<Self::Output> {
+        Self::from_chunk_with_index(chunk, tree, &ChunkIndex::new(tree))
+    }
+
+    fn from_chunk_with_index(chunk: &'a Chunk, tree: &'a [Chunk], index: &ChunkIndex) -> Result<Self::Output> {
+        match (&chunk.typ, &chunk.data) {
+            (ChunkType::FrontendPage, ChunkData::FrontendPage(name, _version, data)) => {
+                let mut page = FrontendPage {
+                    name: &name.0,
+                    resolution: (data.resolution_x, data.resolution_y),
+                    resources: Vec::new(),
+                    layers: Vec::new(),
+                };
+
+                for child in chunk.get_children(tree) {
+                    match &child.data {
+                        ChunkData::FrontendImageResource(name, _version, resource) => {
+                            page.resources.push(FrontendResource {
+                                name: &name.0,
+                                typ: child.typ,
+                                filename: &resource.filename,
+                                inventory_name: None,
+                            })
                         }
-                        None
-                    })
-                    .collect();
+                        ChunkData::FrontendResource(name, _version, resource) => {
+                            page.resources.push(FrontendResource {
+                                name: &name.0,
+                                typ: child.typ,
+                                filename: &resource.filename,
+                                inventory_name: Some(&resource.inventory_name),
+                            })
+                        }
+                        ChunkData::FrontendLayer(..) => page.layers.push(FrontendLayer::from_chunk_with_index(child, tree, index)?),
+                        _ => {}
+                    }
+                }
 
-                Ok(skin)
+                Ok(page)
             }
             (typ, data) => Err(eyre!(
-                "Skeleton expected ChunkType::P3DSkeleton with ChunkData::Skeleton but got a {:?} chunk with {:?}",
+                "FrontendPage expected ChunkType::FrontendPage with ChunkData::FrontendPage but got a {:?} chunk with {:?}",
+                typ,
+                data
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FrontendScreen<'a> {
+    pub name: &'a str,
+    /// Pages that couldn't be found in this tree are left out
+    pub pages: Vec<FrontendPage<'a>>,
+}
+
+impl<'a> FromChunk<'a> for FrontendScreen<'a> {
+    type Output = FrontendScreen<'a>;
+
+    fn from_chunk(chunk: &'a Chunk, tree: &'a [Chunk]) -> Result<Self::Output> {
+        Self::from_chunk_with_index(chunk, tree, &ChunkIndex::new(tree))
+    }
+
+    fn from_chunk_with_index(chunk: &'a Chunk, tree: &'a [Chunk], index: &ChunkIndex) -> Result<Self::Output> {
+        match (&chunk.typ, &chunk.data) {
+            (ChunkType::FrontendScreen, ChunkData::FrontendScreen(name, _version, data)) => {
+                let mut screen = FrontendScreen {
+                    name: &name.0,
+                    pages: Vec::with_capacity(data.page_names.len()),
+                };
+
+                for page in &data.page_names {
+                    if let Some(page) = index.get(tree, ChunkType::FrontendPage, page) {
+                        screen.pages.push(FrontendPage::from_chunk_with_index(page, tree, index)?);
+                    }
+                }
+
+                Ok(screen)
+            }
+            (typ, data) => Err(eyre!(
+                "FrontendScreen expected ChunkType::FrontendScreen with ChunkData::FrontendScreen but got a {:?} chunk with {:?}",
+                typ,
+                data
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FrontendProject<'a> {
+    pub name: &'a str,
+    pub resolution: (u32, u32),
+    pub platform: &'a str,
+    pub screens: Vec<FrontendScreen<'a>>,
+}
+
+impl<'a> FromChunk<'a> for FrontendProject<'a> {
+    type Output = FrontendProject<'a>;
+
+    fn from_chunk(chunk: &'a Chunk, tree: &'a [Chunk]) -> Result<Self::Output> {
+        Self::from_chunk_with_index(chunk, tree, &ChunkIndex::new(tree))
+    }
+
+    fn from_chunk_with_index(chunk: &'a Chunk, tree: &'a [Chunk], index: &ChunkIndex) -> Result<Self::Output> {
+        match (&chunk.typ, &chunk.data) {
+            (ChunkType::FrontendProject, ChunkData::FrontendProject(name, _version, data)) => {
+                let mut project = FrontendProject {
+                    name: &name.0,
+                    resolution: (data.resolution_x, data.resolution_y),
+                    platform: &data.platform,
+                    screens: Vec::new(),
+                };
+
+                for child in chunk.get_children_of_type(tree, ChunkType::FrontendScreen) {
+                    project.screens.push(FrontendScreen::from_chunk_with_index(child, tree, index)?);
+                }
+
+                Ok(project)
+            }
+            (typ, data) => Err(eyre!(
+                "FrontendProject expected ChunkType::FrontendProject with ChunkData::FrontendProject but got a {:?} chunk with {:?}",
                 typ,
                 data
             )),
@@ -504,35 +948,97 @@ impl<'a> FromChunk<'a> for Skin<'a> {
 pub enum HighLevelType<'a> {
     Mesh(Mesh<'a>),
     Skin(Skin<'a>),
+    LightGroup(LightGroup<'a>),
+    GameAttributes(GameAttributes<'a>),
+    FrontendProject(FrontendProject<'a>),
     AllTextures(AllTextures<'a>)
 }
 
+/// Builds every high level type in the tree. Anything that can't be built fails the whole call,
+/// see [`parse_high_level_types_with_options`] to carry on past everything but meshes and skins.
 // Keep these lifetimes since it matches everything else in the file and makes it clear.
 #[allow(clippy::needless_lifetimes)]
 pub fn parse_high_level_types<'a>(tree: &'a [Chunk]) -> Result<Vec<HighLevelType<'a>>> {
+    parse_high_level_types_with_index(tree, &ChunkIndex::new(tree))
+}
+
+/// Same as [`parse_high_level_types`], for callers that already built a [`ChunkIndex`] for this tree.
+pub fn parse_high_level_types_with_index<'a>(
+    tree: &'a [Chunk],
+    index: &ChunkIndex,
+) -> Result<Vec<HighLevelType<'a>>> {
+    Ok(parse_high_level_types_with_options(tree, index, &ParseOptions::strict())?.0)
+}
+
+/// Same as [`parse_high_level_types_with_index`]. A mesh or skin that can't be built always fails
+/// the call, but in lenient mode anything else that can't be built is left out and reported as a
+/// [`DiagnosticKind::Invalid`] instead.
+pub fn parse_high_level_types_with_options<'a>(
+    tree: &'a [Chunk],
+    index: &ChunkIndex,
+    options: &ParseOptions,
+) -> Result<(Vec<HighLevelType<'a>>, Vec<Diagnostic>)> {
     let mut types = Vec::new();
+    let mut diagnostics = Vec::new();
+
+    // Everything past meshes and skins is optional, one broken chunk doesn't have to stop an export
+    let mut optional =
+        |chunk: &Chunk, typ: Result<HighLevelType<'a>>| -> Result<Option<HighLevelType<'a>>> {
+            match typ {
+                Ok(typ) => Ok(Some(typ)),
+                Err(e) if !options.strict => {
+                    diagnostics.push(Diagnostic {
+                        offset: chunk.file_span.offset,
+                        typ: chunk.typ,
+                        lineage: chunk.get_lineage(tree),
+                        kind: DiagnosticKind::Invalid {
+                            message: e.to_string(),
+                        },
+                    });
+                    Ok(None)
+                }
+                Err(e) => Err(e),
+            }
+        };
 
     for chunk in tree.iter() {
         match &chunk.typ {
-            ChunkType::Mesh => types.push(HighLevelType::Mesh(Mesh::from_chunk(chunk, tree)?)),
-            ChunkType::Skin => types.push(HighLevelType::Skin(Skin::from_chunk(chunk, tree)?)),
+            ChunkType::Mesh => types.push(HighLevelType::Mesh(Mesh::from_chunk_with_index(chunk, tree, index)?)),
+            ChunkType::Skin => types.push(HighLevelType::Skin(Skin::from_chunk_with_index(chunk, tree, index)?)),
+            ChunkType::P3DLightGroup => types.extend(optional(
+                chunk,
+                LightGroup::from_chunk_with_index(chunk, tree, index).map(HighLevelType::LightGroup),
+            )?),
+            ChunkType::GameAttr => types.extend(optional(
+                chunk,
+                GameAttributes::from_chunk(chunk, tree).map(HighLevelType::GameAttributes),
+            )?),
+            ChunkType::FrontendProject => types.extend(optional(
+                chunk,
+                FrontendProject::from_chunk_with_index(chunk, tree, index).map(HighLevelType::FrontendProject),
+            )?),
             _ => {}
         }
     }
 
-    types.push(HighLevelType::AllTextures(AllTextures::from_data(tree)));
+    types.push(HighLevelType::AllTextures(AllTextures::from_data(tree, index)));
 
-    Ok(types)
+    Ok((types, diagnostics))
 }
 
 #[cfg(test)]
 mod test {
-    use p3dparse::chunk::{data::kinds::mesh::VertexType, Span};
+    use p3dparse::{
+        chunk::{data::kinds::mesh::VertexType, FileSpan, Span},
+        Bytes,
+    };
 
     use super::*;
 
     #[test]
     fn test_mesh() {
+        use p3dparse::chunk::edit::TreeEdit;
+
         let chunks = [
             Chunk {
                 typ: ChunkType::DataFile,
@@ -543,6 +1049,8 @@ mod test {
                 },
                 parent: None,
                 children: vec![1, 5],
+                unparsed_data: Bytes::new(),
+                file_span: FileSpan::default(),
             },
             Chunk {
                 typ: ChunkType::Mesh,
@@ -557,6 +1065,8 @@ mod test {
                 },
                 parent: Some(0),
                 children: vec![2],
+                unparsed_data: Bytes::new(),
+                file_span: FileSpan::default(),
             },
             Chunk {
                 typ: ChunkType::OldPrimGroup,
@@ -577,6 +1087,8 @@ mod test {
                 },
                 parent: Some(1),
                 children: vec![3, 4],
+                unparsed_data: Bytes::new(),
+                file_span: FileSpan::default(),
             },
             Chunk {
                 typ: ChunkType::PositionList,
@@ -589,6 +1101,8 @@ mod test {
                 },
                 parent: Some(2),
                 children: vec![],
+                unparsed_data: Bytes::new(),
+                file_span: FileSpan::default(),
             },
             Chunk {
                 typ: ChunkType::IndexList,
@@ -599,6 +1113,8 @@ mod test {
                 },
                 parent: Some(2),
                 children: vec![],
+                unparsed_data: Bytes::new(),
+                file_span: FileSpan::default(),
             },
             Chunk {
                 typ: ChunkType::Shader,
@@ -619,6 +1135,8 @@ mod test {
                 },
                 parent: Some(0),
                 children: vec![],
+                unparsed_data: Bytes::new(),
+                file_span: FileSpan::default(),
             },
         ];
         let types = parse_high_level_types(&chunks).expect("Failed to parse High Level Types");
@@ -654,6 +1172,41 @@ mod test {
                 }]
             })
         );
+
+        // Chunks that failed to parse don't take the mesh down with them
+        let mut chunks = chunks.to_vec();
+        chunks
+            .insert_chunk(0, 2, ChunkType::P3DLightGroup, ChunkData::Unknown)
+            .unwrap();
+        chunks
+            .insert_chunk(0, 3, ChunkType::GameAttr, ChunkData::Unknown)
+            .unwrap();
+        chunks
+            .insert_chunk(0, 4, ChunkType::FrontendProject, ChunkData::Unknown)
+            .unwrap();
+        assert!(parse_high_level_types(&chunks).is_err());
+        let (types, diagnostics) = parse_high_level_types_with_options(
+            &chunks,
+            &ChunkIndex::new(&chunks),
+            &ParseOptions::lenient(),
+        )
+        .expect("Failed to parse High Level Types");
+        assert!(matches!(
+            types[..],
+            [HighLevelType::Mesh(_), HighLevelType::AllTextures(_)]
+        ));
+        let broken: Vec<_> = diagnostics
+            .iter()
+            .map(|d| (d.typ, matches!(d.kind, DiagnosticKind::Invalid { .. })))
+            .collect();
+        assert_eq!(
+            broken,
+            [
+                (ChunkType::P3DLightGroup, true),
+                (ChunkType::GameAttr, true),
+                (ChunkType::FrontendProject, true)
+            ]
+        );
     }
 
     #[test]
@@ -668,6 +1221,8 @@ mod test {
                 },
                 parent: None,
                 children: vec![1, 5, 6],
+                unparsed_data: Bytes::new(),
+                file_span: FileSpan::default(),
             },
             Chunk {
                 typ: ChunkType::Skin,
@@ -685,6 +1240,8 @@ mod test {
                 },
                 parent: Some(0),
                 children: vec![2],
+                unparsed_data: Bytes::new(),
+                file_span: FileSpan::default(),
             },
             Chunk {
                 typ: ChunkType::OldPrimGroup,
@@ -705,6 +1262,8 @@ mod test {
                 },
                 parent: Some(1),
                 children: vec![3, 4],
+                unparsed_data: Bytes::new(),
+                file_span: FileSpan::default(),
             },
             Chunk {
                 typ: ChunkType::PositionList,
@@ -717,6 +1276,8 @@ mod test {
                 },
                 parent: Some(2),
                 children: vec![],
+                unparsed_data: Bytes::new(),
+                file_span: FileSpan::default(),
             },
             Chunk {
                 typ: ChunkType::IndexList,
@@ -727,6 +1288,8 @@ mod test {
                 },
                 parent: Some(2),
                 children: vec![],
+                unparsed_data: Bytes::new(),
+                file_span: FileSpan::default(),
             },
             Chunk {
                 typ: ChunkType::Shader,
@@ -747,6 +1310,8 @@ mod test {
                 },
                 parent: Some(0),
                 children: vec![],
+                unparsed_data: Bytes::new(),
+                file_span: FileSpan::default(),
             },
             Chunk {
                 typ: ChunkType::P3DSkeleton,
@@ -761,6 +1326,8 @@ mod test {
                 },
                 parent: Some(0),
                 children: vec![7],
+                unparsed_data: Bytes::new(),
+                file_span: FileSpan::default(),
             },
             Chunk {
                 typ: ChunkType::P3DSkeletonJoint,
@@ -782,6 +1349,8 @@ mod test {
                 },
                 parent: Some(6),
                 children: vec![],
+                unparsed_data: Bytes::new(),
+                file_span: FileSpan::default(),
             },
         ];
 
@@ -834,4 +1403,636 @@ mod test {
             })
         );
     }
+
+    #[test]
+    fn test_shader_textures() {
+        use p3dparse::chunk::edit::TreeEdit;
+
+        let mut tree = vec![Chunk {
+            typ: ChunkType::DataFile,
+            data: ChunkData::None,
+            span: Span {
+                absolute_index: 0,
+                relative_index: 0,
+            },
+            parent: None,
+            children: vec![],
+            unparsed_data: Bytes::new(),
+            file_span: FileSpan::default(),
+        }];
+        for (position, name) in ["unused.png", "b.png", "a.png"].into_iter().enumerate() {
+            let texture = tree
+                .insert_chunk(
+                    0,
+                    position,
+                    ChunkType::Texture,
+                    ChunkData::Texture(
+                        kinds::name::Name(name.into()),
+                        kinds::version::Version(0),
+                        kinds::texture::Texture {
+                            width: 1,
+                            height: 1,
+                            bpp: 32,
+                            alpha_depth: 8,
+                            num_mip_maps: 1,
+                            texture_type: 0,
+                            usage: 0,
+                            priority: 0,
+                        },
+                    ),
+                )
+                .unwrap();
+            let image = tree
+                .insert_chunk(
+                    texture,
+                    0,
+                    ChunkType::Image,
+                    ChunkData::Image(
+                        kinds::name::Name(name.into()),
+                        kinds::version::Version(0),
+                        kinds::image::Image {
+                            width: 1,
+                            height: 1,
+                            bpp: 32,
+                            palettized: 0,
+                            has_alpha: 1,
+                            image_format: ImageFormat::PNG,
+                        },
+                    ),
+                )
+                .unwrap();
+            tree.insert_chunk(
+                image,
+                0,
+                ChunkType::ImageData,
+                ChunkData::ImageRaw(kinds::image::ImageRaw {
+                    data: Bytes::copy_from_slice(name.as_bytes()),
+                }),
+            )
+            .unwrap();
+        }
+
+        let tex = |texture: &str| ShaderParam {
+            param: "TEX".into(),
+            value: ShaderParamValue::Texture(texture.into()),
+        };
+        let params = [tex("a.png"), tex("b.png"), tex("a.png"), tex("missing.png")];
+        let shaders = [&params[..2], &params[2..]].map(|params| Shader {
+            name: "shader",
+            params: params.iter().collect(),
+            texture: None,
+            lit: None,
+            two_sided: None,
+            specular: None,
+            emissive: None,
+        });
+
+        // Each texture once, in the order the shaders use them, and missing ones left out
+        let index = ChunkIndex::new(&tree);
+        assert_eq!(
+            shader_textures(&shaders, &tree, &index),
+            vec![
+                ("a.png", ImageFormat::PNG, b"a.png".as_slice()),
+                ("b.png", ImageFormat::PNG, b"b.png".as_slice()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_light_group() {
+        let light = kinds::light::Light {
+            light_type: LightType::Point,
+            colour: Colour::new(255, 255, 255, 255),
+            constant: 1.0,
+            linear: 0.0,
+            squared: 0.25,
+            enabled: 1,
+        };
+        let chunks = [
+            Chunk {
+                typ: ChunkType::DataFile,
+                data: ChunkData::None,
+                span: Span {
+                    absolute_index: 0,
+                    relative_index: 0,
+                },
+                parent: None,
+                children: vec![1, 3, 4],
+                unparsed_data: Bytes::new(),
+                file_span: FileSpan::default(),
+            },
+            Chunk {
+                typ: ChunkType::Light,
+                data: ChunkData::Light(
+                    kinds::name::Name("light1".into()),
+                    kinds::version::Version(0),
+                    light.clone(),
+                ),
+                span: Span {
+                    absolute_index: 1,
+                    relative_index: 0,
+                },
+                parent: Some(0),
+                children: vec![2],
+                unparsed_data: Bytes::new(),
+                file_span: FileSpan::default(),
+            },
+            Chunk {
+                typ: ChunkType::LightPosition,
+                data: ChunkData::LightPosition(kinds::light::LightPosition {
+                    position: [1., 2., 3.].into(),
+                }),
+                span: Span {
+                    absolute_index: 2,
+                    relative_index: 0,
+                },
+                parent: Some(1),
+                children: vec![],
+                unparsed_data: Bytes::new(),
+                file_span: FileSpan::default(),
+            },
+            Chunk {
+                typ: ChunkType::P3DLightGroup,
+                data: ChunkData::LightGroup(
+                    kinds::name::Name("group1".into()),
+                    kinds::light::LightGroup {
+                        lights: vec!["light1".into(), "missing".into()],
+                    },
+                ),
+                span: Span {
+                    absolute_index: 3,
+                    relative_index: 1,
+                },
+                parent: Some(0),
+                children: vec![],
+                unparsed_data: Bytes::new(),
+                file_span: FileSpan::default(),
+            },
+            Chunk {
+                typ: ChunkType::OldScenegraphLightGroup,
+                data: ChunkData::ScenegraphLightGroup(
+                    kinds::name::Name("sg_group1".into()),
+                    kinds::scenegraph::ScenegraphLightGroup {
+                        light_group_name: "group1".into(),
+                    },
+                ),
+                span: Span {
+                    absolute_index: 4,
+                    relative_index: 2,
+                },
+                parent: Some(0),
+                children: vec![],
+                unparsed_data: Bytes::new(),
+                file_span: FileSpan::default(),
+            },
+        ];
+        let group = LightGroup::from_chunk(&chunks[4], &chunks)
+            .expect("Failed to resolve the scenegraph light group");
+
+        assert_eq!(
+            group,
+            LightGroup {
+                name: "group1",
+                lights: vec![Light {
+                    name: "light1",
+                    kind: LightType::Point,
+                    colour: &light.colour,
+                    enabled: true,
+                    attenuation: Attenuation {
+                        constant: 1.0,
+                        linear: 0.0,
+                        squared: 0.25
+                    },
+                    position: Some(&[1., 2., 3.].into()),
+                    direction: None,
+                    cone: None,
+                    decay_range: None,
+                    shadow: None
+                }]
+            }
+        );
+
+        let types = parse_high_level_types(&chunks).expect("Failed to parse High Level Types");
+        assert_eq!(types.first(), Some(&HighLevelType::LightGroup(group)));
+    }
+
+    #[test]
+    fn test_game_attributes() {
+        let chunks = [
+            Chunk {
+                typ: ChunkType::GameAttr,
+                data: ChunkData::GameAttr(
+                    kinds::name::Name("tuning".into()),
+                    kinds::version::Version(0),
+                    kinds::gameattr::GameAttr { num_params: 2 },
+                ),
+                span: Span {
+                    absolute_index: 0,
+                    relative_index: 0,
+                },
+                parent: None,
+                children: vec![1, 2],
+                unparsed_data: Bytes::new(),
+                file_span: FileSpan::default(),
+            },
+            Chunk {
+                typ: ChunkType::GameAttrFloatParam,
+                data: ChunkData::GameAttrParam(kinds::gameattr::GameAttrParam {
+                    param: "speed".into(),
+                    value: GameAttrParamValue::Float(1.5),
+                }),
+                span: Span {
+                    absolute_index: 1,
+                    relative_index: 0,
+                },
+                parent: Some(0),
+                children: vec![],
+                unparsed_data: Bytes::new(),
+                file_span: FileSpan::default(),
+            },
+            Chunk {
+                typ: ChunkType::GameAttrVectorParam,
+                data: ChunkData::GameAttrParam(kinds::gameattr::GameAttrParam {
+                    param: "offset".into(),
+                    value: GameAttrParamValue::Vector([1., 2., 3.].into()),
+                }),
+                span: Span {
+                    absolute_index: 2,
+                    relative_index: 1,
+                },
+                parent: Some(0),
+                children: vec![],
+                unparsed_data: Bytes::new(),
+                file_span: FileSpan::default(),
+            },
+        ];
+        let attributes = GameAttributes::from_chunk(&chunks[0], &chunks)
+            .expect("Failed to parse GameAttributes");
+
+        assert_eq!(attributes.name, "tuning");
+        assert_eq!(
+            attributes.attributes["speed"],
+            &GameAttrParamValue::Float(1.5)
+        );
+        assert_eq!(
+            attributes.attributes["offset"],
+            &GameAttrParamValue::Vector([1., 2., 3.].into())
+        );
+        assert_eq!(
+            serde_json::to_string(&attributes).unwrap(),
+            r#"{"name":"tuning","attributes":{"offset":{"Vector":[1.0,2.0,3.0]},"speed":{"Float":1.5}}}"#
+        );
+    }
+
+    #[test]
+    fn test_frontend_project() {
+        use p3dparse::chunk::{data::kinds::frontend, edit::TreeEdit};
+
+        let name = |name: &str| (kinds::name::Name(name.into()), kinds::version::Version(1));
+        let placement = frontend::FrontendElement {
+            position_x: 10,
+            position_y: 20,
+            dimension_x: 64,
+            dimension_y: 32,
+            justification_x: 0,
+            justification_y: 2,
+            colour: Colour::new(255, 255, 255, 255),
+            translucency: 255,
+            rotation: 0.,
+        };
+
+        let mut tree = vec![Chunk {
+            typ: ChunkType::DataFile,
+            data: ChunkData::None,
+            span: Span {
+                absolute_index: 0,
+                relative_index: 0,
+            },
+            parent: None,
+            children: vec![],
+            unparsed_data: Bytes::new(),
+            file_span: FileSpan::default(),
+        }];
+        let (n, v) = name("project");
+        let project = tree
+            .insert_chunk(
+                0,
+                0,
+                ChunkType::FrontendProject,
+                ChunkData::FrontendProject(
+                    n,
+                    v,
+                    frontend::FrontendProject {
+                        resolution_x: 640,
+                        resolution_y: 480,
+                        platform: "PC".into(),
+                        page_path: Default::default(),
+                        resource_path: Default::default(),
+                        screen_path: Default::default(),
+                    },
+                ),
+            )
+            .unwrap();
+        let (n, v) = name("screen");
+        tree.insert_chunk(
+            project,
+            0,
+            ChunkType::FrontendScreen,
+            ChunkData::FrontendScreen(
+                n,
+                v,
+                frontend::FrontendScreen {
+                    page_names: vec!["page".into(), "missing".into()],
+                },
+            ),
+        )
+        .unwrap();
+        let (n, v) = name("page");
+        let page = tree
+            .insert_chunk(
+                project,
+                1,
+                ChunkType::FrontendPage,
+                ChunkData::FrontendPage(
+                    n,
+                    v,
+                    frontend::FrontendPage {
+                        resolution_x: 640,
+                        resolution_y: 480,
+                    },
+                ),
+            )
+            .unwrap();
+        let (n, v) = name("logo.png");
+        tree.insert_chunk(
+            page,
+            0,
+            ChunkType::FrontendImageResource,
+            ChunkData::FrontendImageResource(
+                n,
+                v,
+                frontend::FrontendImageResource {
+                    filename: "art/logo.png".into(),
+                },
+            ),
+        )
+        .unwrap();
+        let (n, v) = name("layer");
+        let layer = tree
+            .insert_chunk(
+                page,
+                1,
+                ChunkType::FrontendLayer,
+                ChunkData::FrontendLayer(
+                    n,
+                    v,
+                    frontend::FrontendLayer {
+                        visible: 1,
+                        editable: 0,
+                        alpha: 255,
+                    },
+                ),
+            )
+            .unwrap();
+        let (n, v) = name("group");
+        let group = tree
+            .insert_chunk(
+                layer,
+                0,
+                ChunkType::FrontendGroup,
+                ChunkData::FrontendGroup(n, v, frontend::FrontendGroup { alpha: 128 }),
+            )
+            .unwrap();
+        let (n, v) = name("logo");
+        tree.insert_chunk(
+            group,
+            0,
+            ChunkType::FrontendMultiSprite,
+            ChunkData::FrontendMultiSprite(
+                n,
+                v,
+                frontend::FrontendMultiSprite {
+                    element: placement.clone(),
+                    image_names: vec!["logo.png".into()],
+                },
+            ),
+        )
+        .unwrap();
+        let (n, v) = name("title");
+        let text = tree
+            .insert_chunk(
+                layer,
+                1,
+                ChunkType::FrontendMultiText,
+                ChunkData::FrontendMultiText(
+                    n,
+                    v,
+                    frontend::FrontendMultiText {
+                        element: placement.clone(),
+                        text_style_name: "style".into(),
+                        shadow_enabled: 0,
+                        shadow_colour: Colour::new(0, 0, 0, 0),
+                        shadow_offset_x: 0,
+                        shadow_offset_y: 0,
+                        current_text: 0,
+                    },
+                ),
+            )
+            .unwrap();
+        tree.insert_chunk(
+            text,
+            0,
+            ChunkType::FrontendStringHardCoded,
+            ChunkData::FrontendStringHardCoded(frontend::FrontendStringHardCoded {
+                string: "Title".into(),
+            }),
+        )
+        .unwrap();
+
+        let project = FrontendProject::from_chunk(&tree[1], &tree)
+            .expect("Failed to parse FrontendProject");
+
+        assert_eq!(project.name, "project");
+        assert_eq!(project.resolution, (640, 480));
+        assert_eq!(project.screens.len(), 1);
+        // The missing page is skipped
+        let pages = &project.screens[0].pages;
+        assert_eq!(pages.len(), 1);
+        assert_eq!(
+            pages[0].resources,
+            vec![FrontendResource {
+                name: "logo.png",
+                typ: ChunkType::FrontendImageResource,
+                filename: "art/logo.png",
+                inventory_name: None
+            }]
+        );
+
+        let layer = &pages[0].layers[0];
+        assert!(layer.visible && !layer.editable);
+        assert_eq!(
+            layer.elements[0],
+            FrontendElement {
+                name: "group",
+                placement: None,
+                kind: FrontendElementKind::Group {
+                    alpha: 128,
+                    children: vec![FrontendElement {
+                        name: "logo",
+                        placement: Some(&placement),
+                        kind: FrontendElementKind::Sprite {
+                            images: vec![("logo.png", Some("art/logo.png"))]
+                        }
+                    }]
+                }
+            }
+        );
+        match &layer.elements[1].kind {
+            FrontendElementKind::Text { data, strings } => {
+                assert_eq!(data.text_style_name, "style");
+                assert_eq!(strings, &[FrontendText::HardCoded("Title")]);
+            }
+            kind => panic!("Expected text, got {:?}", kind),
+        }
+    }
+
+    #[test]
+    fn test_frontend_page_images() {
+        use p3dparse::chunk::{data::kinds::frontend, edit::TreeEdit};
+
+        let name = |name: &str| (kinds::name::Name(name.into()), kinds::version::Version(1));
+        let image =
+            |tree: &mut Vec<Chunk>, page: usize, position: usize, image: &str, filename: &str| {
+                let (n, v) = name(image);
+                tree.insert_chunk(
+                    page,
+                    position,
+                    ChunkType::FrontendImageResource,
+                    ChunkData::FrontendImageResource(
+                        n,
+                        v,
+                        frontend::FrontendImageResource {
+                            filename: filename.into(),
+                        },
+                    ),
+                )
+                .unwrap();
+            };
+
+        let mut tree = vec![Chunk {
+            typ: ChunkType::DataFile,
+            data: ChunkData::None,
+            span: Span {
+                absolute_index: 0,
+                relative_index: 0,
+            },
+            parent: None,
+            children: vec![],
+            unparsed_data: Bytes::new(),
+            file_span: FileSpan::default(),
+        }];
+
+        // Both pages have their own logo.png, only the first has shared.png
+        for (position, (page_name, filename)) in
+            [("page1", "art/one.png"), ("page2", "art/two.png")]
+                .into_iter()
+                .enumerate()
+        {
+            let (n, v) = name(page_name);
+            let page = tree
+                .insert_chunk(
+                    0,
+                    position,
+                    ChunkType::FrontendPage,
+                    ChunkData::FrontendPage(
+                        n,
+                        v,
+                        frontend::FrontendPage {
+                            resolution_x: 640,
+                            resolution_y: 480,
+                        },
+                    ),
+                )
+                .unwrap();
+            image(&mut tree, page, 0, "logo.png", filename);
+            if position == 0 {
+                image(&mut tree, page, 1, "shared.png", "art/shared.png");
+            }
+            let (n, v) = name("layer");
+            let layer = tree
+                .insert_chunk(
+                    page,
+                    tree[page].children.len(),
+                    ChunkType::FrontendLayer,
+                    ChunkData::FrontendLayer(
+                        n,
+                        v,
+                        frontend::FrontendLayer {
+                            visible: 1,
+                            editable: 0,
+                            alpha: 255,
+                        },
+                    ),
+                )
+                .unwrap();
+            let (n, v) = name("sprite");
+            tree.insert_chunk(
+                layer,
+                0,
+                ChunkType::FrontendMultiSprite,
+                ChunkData::FrontendMultiSprite(
+                    n,
+                    v,
+                    frontend::FrontendMultiSprite {
+                        element: frontend::FrontendElement {
+                            position_x: 0,
+                            position_y: 0,
+                            dimension_x: 64,
+                            dimension_y: 32,
+                            justification_x: 0,
+                            justification_y: 0,
+                            colour: Colour::new(255, 255, 255, 255),
+                            translucency: 255,
+                            rotation: 0.,
+                        },
+                        image_names: vec![
+                            "logo.png".into(),
+                            "shared.png".into(),
+                            "missing.png".into(),
+                        ],
+                    },
+                ),
+            )
+            .unwrap();
+        }
+
+        let index = ChunkIndex::new(&tree);
+        let images = |page: &str| {
+            let page = index.get(&tree, ChunkType::FrontendPage, page).unwrap();
+            let page = FrontendPage::from_chunk_with_index(page, &tree, &index)
+                .expect("Failed to parse FrontendPage");
+            match &page.layers[0].elements[0].kind {
+                FrontendElementKind::Sprite { images } => images.clone(),
+                kind => panic!("Expected a sprite, got {:?}", kind),
+            }
+        };
+
+        assert_eq!(
+            images("page1"),
+            vec![
+                ("logo.png", Some("art/one.png")),
+                ("shared.png", Some("art/shared.png")),
+                ("missing.png", None),
+            ]
+        );
+        // The second page's own logo wins, shared.png falls back to the first page's
+        assert_eq!(
+            images("page2"),
+            vec![
+                ("logo.png", Some("art/two.png")),
+                ("shared.png", Some("art/shared.png")),
+                ("missing.png", None),
+            ]
+        );
+    }
 }
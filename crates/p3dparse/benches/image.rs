@@ -0,0 +1,67 @@
+use bytes::Buf;
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+use p3dparse::{
+    chunk::{
+        data::{data_enum::ChunkData, kinds::image::ImageRaw},
+        type_identifiers::ChunkType,
+        Chunk, FileSpan, Span,
+    },
+    parse_file, write_file, Bytes,
+};
+
+/// A file holding a single `ImageData` chunk with `size` bytes of pixels.
+fn synthetic_image_file(size: usize) -> Bytes {
+    let chunk = |typ, data, parent: Option<usize>, children| Chunk {
+        typ,
+        data,
+        span: Span {
+            absolute_index: parent.map_or(0, |_| 1),
+            relative_index: 0,
+        },
+        parent,
+        children,
+        unparsed_data: Bytes::new(),
+        file_span: FileSpan::default(),
+    };
+
+    let pixels: Vec<u8> = (0..size).map(|i| (i * 31) as u8).collect();
+    let tree = vec![
+        chunk(ChunkType::DataFile, ChunkData::None, None, vec![1]),
+        chunk(
+            ChunkType::ImageData,
+            ChunkData::ImageRaw(ImageRaw {
+                data: pixels.into(),
+            }),
+            Some(0),
+            Vec::new(),
+        ),
+    ];
+    write_file(&tree).expect("Failed to write file").into()
+}
+
+/// What `ImageRaw::parse` used to do, one checked byte at a time into a new `Vec`.
+fn copy_bytewise(mut bytes: Bytes) -> Vec<u8> {
+    let mut data = Vec::with_capacity(bytes.remaining());
+    while bytes.has_remaining() {
+        data.push(bytes.get_u8());
+    }
+    data
+}
+
+fn image(c: &mut Criterion) {
+    let size = 8 << 20;
+    let file = synthetic_image_file(size);
+
+    let mut group = c.benchmark_group("8 MiB image chunk");
+    group.throughput(Throughput::Bytes(size as u64));
+    group.bench_function("parse_file", |b| {
+        b.iter(|| parse_file(black_box(file.clone())).unwrap())
+    });
+    group.bench_function("bytewise copy baseline", |b| {
+        b.iter(|| copy_bytewise(black_box(file.slice(28..))))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, image);
+criterion_main!(benches);
@@ -0,0 +1,96 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use p3dparse::{
+    chunk::{
+        data::{data_enum::ChunkData, kinds::mesh::RenderStatus},
+        type_identifiers::ChunkType,
+        Chunk, FileSpan, Span,
+    },
+    Bytes,
+};
+
+/// A root with `groups` children, each with `per_group` leaves, in the same order the parser produces.
+fn synthetic_tree(groups: usize, per_group: usize) -> Vec<Chunk> {
+    let mut tree = Vec::with_capacity(1 + groups * (1 + per_group));
+    let push = |tree: &mut Vec<Chunk>, parent: Option<usize>, typ: ChunkType| {
+        let index = tree.len();
+        let relative_index = match parent {
+            Some(parent) => {
+                tree[parent].children.push(index);
+                tree[parent].children.len() - 1
+            }
+            None => 0,
+        };
+        tree.push(Chunk {
+            typ,
+            data: match typ {
+                ChunkType::RenderStatus => ChunkData::RenderStatus(RenderStatus {
+                    cast_shadow: index as u32,
+                }),
+                _ => ChunkData::None,
+            },
+            span: Span {
+                absolute_index: index,
+                relative_index,
+            },
+            parent,
+            children: Vec::new(),
+            unparsed_data: Bytes::new(),
+            file_span: FileSpan::default(),
+        });
+        index
+    };
+
+    let root = push(&mut tree, None, ChunkType::DataFile);
+    for _ in 0..groups {
+        let group = push(&mut tree, Some(root), ChunkType::Mesh);
+        for leaf in 0..per_group {
+            let typ = if leaf % 2 == 0 {
+                ChunkType::RenderStatus
+            } else {
+                ChunkType::OldPrimGroup
+            };
+            push(&mut tree, Some(group), typ);
+        }
+    }
+    tree
+}
+
+fn walk(tree: &[Chunk], chunk: &Chunk) -> usize {
+    chunk
+        .get_children(tree)
+        .map(|child| 1 + walk(tree, child))
+        .sum()
+}
+
+fn navigation(c: &mut Criterion) {
+    // Roughly the size of a large level file
+    let tree = synthetic_tree(5_000, 10);
+    let root = &tree[0];
+
+    c.bench_function("walk 55k chunks", |b| {
+        b.iter(|| walk(black_box(&tree), root))
+    });
+
+    c.bench_function("children of type", |b| {
+        b.iter(|| {
+            root.get_children(&tree)
+                .flat_map(|group| group.get_children_of_type(&tree, ChunkType::RenderStatus))
+                .count()
+        })
+    });
+
+    c.bench_function("child and parent access", |b| {
+        b.iter(|| {
+            let mut sum = 0;
+            for group in 0..root.children_len() {
+                let group = root.get_child(&tree, black_box(group)).unwrap();
+                let leaf = group.get_child(&tree, 0).unwrap();
+                sum += leaf.get_parent(&tree).unwrap().span.absolute_index;
+            }
+            sum
+        })
+    });
+}
+
+criterion_group!(benches, navigation);
+criterion_main!(benches);
@@ -0,0 +1,79 @@
+use std::{path::PathBuf, process::ExitCode};
+
+use clap::{arg, command, value_parser};
+use p3dparse::{
+    chunk::{
+        query::Query,
+        visitor::{walk, walk_from, ChunkVisitor, VisitContext, Walk},
+    },
+    map_file, parse_file_with_options, ParseOptions,
+};
+
+/// Prints one line per chunk, indented by depth, with where it lives in the file.
+struct Dump;
+
+impl<'a> ChunkVisitor<'a> for Dump {
+    fn enter(&mut self, ctx: &VisitContext<'a, '_>) -> eyre::Result<Walk> {
+        let chunk = ctx.chunk;
+        let line = format!(
+            "{:#010X} {:>10} {:>10}  {}{:?} {}",
+            chunk.file_span.offset,
+            chunk.file_span.data_size,
+            chunk.file_span.total_size,
+            "  ".repeat(ctx.depth),
+            chunk.typ,
+            chunk.data.get_name().map(|n| n.0).unwrap_or_default()
+        );
+        println!("{}", line.trim_end());
+        Ok(Walk::Continue)
+    }
+}
+
+fn main() -> ExitCode {
+    let matches = command!()
+        .arg(arg!(<FILE> "Source p3d file").value_parser(value_parser!(PathBuf)))
+        .arg(arg!([QUERY] "Only dump the chunks matching this path query, and their children"))
+        .get_matches();
+
+    let path = matches
+        .get_one::<PathBuf>("FILE")
+        .expect("FILE is required");
+    let query = match matches.get_one::<String>("QUERY").map(|q| Query::new(q)) {
+        Some(Ok(query)) => Some(query),
+        Some(Err(e)) => {
+            eprintln!("Invalid query: {}", e);
+            return ExitCode::FAILURE;
+        }
+        None => None,
+    };
+
+    let bytes = match map_file(path) {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            eprintln!("{}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    match parse_file_with_options(bytes, &ParseOptions::lenient()) {
+        Ok((tree, diagnostics)) => {
+            println!("{:>10} {:>10} {:>10}  Chunk", "Offset", "Data", "Total");
+            match &query {
+                Some(query) => {
+                    for index in query.run_indices(&tree) {
+                        walk_from(&tree, index, &mut Dump).expect("Dump never fails");
+                    }
+                }
+                None => walk(&tree, &mut Dump).expect("Dump never fails"),
+            }
+            for diagnostic in diagnostics {
+                eprintln!("{}", diagnostic);
+            }
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("Failed to parse {}: {}", path.display(), e);
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,107 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::ExitCode,
+};
+
+use clap::{arg, command, value_parser, ArgMatches, Command};
+use p3dparse::{
+    map_file, parse_file, parse_path, text, write_file, write_file_compressed, FileTypes,
+};
+
+fn is_po(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("po"))
+}
+
+fn path_arg<'a>(matches: &'a ArgMatches, id: &str) -> &'a Path {
+    matches
+        .get_one::<PathBuf>(id)
+        .expect("Path arguments are required")
+}
+
+fn export(file: &Path, out: &Path) -> eyre::Result<()> {
+    let tree = parse_path(file)?;
+    let tables = text::string_tables(&tree)?;
+    if tables.is_empty() {
+        return Err(eyre::eyre!(
+            "{} has no text bible languages",
+            file.display()
+        ));
+    }
+
+    let contents = match is_po(out) {
+        true => text::to_po(&tables),
+        false => text::to_json(&tables)?,
+    };
+    fs::write(out, contents)?;
+    Ok(())
+}
+
+fn import(file: &Path, tables: &Path, out: &Path) -> eyre::Result<()> {
+    let bytes = map_file(file)?;
+    let file_type = bytes
+        .get(..4)
+        .and_then(|magic| FileTypes::try_from(u32::from_le_bytes(magic.try_into().unwrap())).ok());
+    // The output is always little endian, which a console build can't read
+    if file_type.is_some_and(|file_type| file_type.is_big_endian()) {
+        return Err(eyre::eyre!(
+            "{} is big endian, only little endian files can be written",
+            file.display()
+        ));
+    }
+    let mut tree = parse_file(bytes)?;
+    let contents = fs::read_to_string(tables)?;
+    let tables = match is_po(tables) {
+        true => text::from_po(&contents)?,
+        false => text::from_json(&contents)?,
+    };
+
+    text::apply_string_tables(&mut tree, &tables)?;
+    // The tree still points into the mapped input, let go of it before the output can overwrite it
+    let bytes = match file_type {
+        Some(FileTypes::CompressedPure3D) => write_file_compressed(&tree)?,
+        _ => write_file(&tree)?,
+    };
+    drop(tree);
+    fs::write(out, bytes)?;
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    let path = || value_parser!(PathBuf);
+    let matches = command!()
+        .subcommand_required(true)
+        .subcommand(
+            Command::new("export")
+                .about("Write every language's strings to JSON, or PO if OUT ends in .po")
+                .arg(arg!(<FILE> "Source p3d file").value_parser(path()))
+                .arg(arg!(<OUT> "Destination .json or .po file").value_parser(path())),
+        )
+        .subcommand(
+            Command::new("import")
+                .about("Replace strings from a JSON or PO file and write the result to OUT")
+                .arg(arg!(<FILE> "Source p3d file").value_parser(path()))
+                .arg(arg!(<TABLES> "Edited .json or .po file").value_parser(path()))
+                .arg(arg!(<OUT> "Destination p3d file, can be FILE").value_parser(path())),
+        )
+        .get_matches();
+
+    let result = match matches.subcommand() {
+        Some(("export", matches)) => export(path_arg(matches, "FILE"), path_arg(matches, "OUT")),
+        Some(("import", matches)) => import(
+            path_arg(matches, "FILE"),
+            path_arg(matches, "TABLES"),
+            path_arg(matches, "OUT"),
+        ),
+        _ => unreachable!("A subcommand is required"),
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
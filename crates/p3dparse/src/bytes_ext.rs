@@ -1,10 +1,42 @@
-use std::ops::RangeBounds;
+use std::{cell::Cell, ops::RangeBounds};
 
 use bytes::Buf;
 use paste::paste;
 
 use eyre::{eyre, Error};
 
+use crate::error::Overrun;
+
+thread_local! {
+    static BIG_ENDIAN: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Sets the byte order used by the `safe_get_*` readers on this thread until it's dropped.
+/// P3D files are entirely one byte order, picked by the platform they were built for.
+pub(crate) struct EndianGuard {
+    previous: bool,
+}
+
+impl EndianGuard {
+    pub(crate) fn new(big_endian: bool) -> Self {
+        EndianGuard {
+            previous: BIG_ENDIAN.with(|b| b.replace(big_endian)),
+        }
+    }
+}
+
+impl Drop for EndianGuard {
+    fn drop(&mut self) {
+        BIG_ENDIAN.with(|b| b.set(self.previous));
+    }
+}
+
+/// The byte order of the file being parsed on this thread. [`BufResult`] already reads in it, this is
+/// for parsers that read through something else.
+pub fn is_big_endian() -> bool {
+    BIG_ENDIAN.with(|b| b.get())
+}
+
 macro_rules! safe {
     ($name:ident) => {
         paste! {
@@ -13,7 +45,7 @@ macro_rules! safe {
                 if self.remaining() >= SIZE {
                     Ok(self.[<get_ $name>]())
                 } else {
-                    Err(eyre!("Overrun by {} bytes", SIZE - self.remaining()))
+                    Err(Overrun(SIZE - self.remaining()).into())
                 }
             }
         }
@@ -26,43 +58,69 @@ macro_rules! safe {
                 if self.remaining() >= SIZE {
                     Ok(self.[<get_ $name>]())
                 } else {
-                    Err(eyre!("Overrun by {} bytes", SIZE - self.remaining()))
+                    Err(Overrun(SIZE - self.remaining()).into())
+                }
+            }
+        }
+    };
+}
+
+/// Reads in the byte order set by [`EndianGuard`], little endian by default.
+macro_rules! safe_endian {
+    ($name:ident) => {
+        paste! {
+            fn [<safe_get_ $name>](&mut self) -> Result<$name, Error> {
+                const SIZE: usize = ::std::mem::size_of::<$name>();
+                if self.remaining() >= SIZE {
+                    if is_big_endian() {
+                        Ok(self.[<get_ $name>]())
+                    } else {
+                        Ok(self.[<get_ $name _le>]())
+                    }
+                } else {
+                    Err(Overrun(SIZE - self.remaining()).into())
                 }
             }
         }
     };
 }
 
-pub(crate) trait BufResult: Buf {
+/// Reads that fail with an error instead of panicking when there aren't enough bytes left.
+///
+/// Multi-byte reads use the byte order of the file being parsed, so custom parsers should use these
+/// rather than [`Buf`]'s own getters.
+pub trait BufResult: Buf {
     safe!(u8);
-    safe!(u16);
-    safe!(u16_le, u16);
-    safe!(u32);
+    safe_endian!(u16);
+    safe_endian!(u32);
+    safe_endian!(i32);
+    safe_endian!(f32);
+    // File magic is always compared as little endian, whatever the file's byte order.
     safe!(u32_le, u32);
-    safe!(i32);
-    safe!(i32_le, i32);
-    safe!(f32);
-    safe!(f32_le, f32);
-
-    fn safe_advance(&mut self, cnt: usize) -> Result<(), Error> {
-        if self.remaining() >= cnt {
-            self.advance(cnt);
-            Ok(())
+
+    /// Checks a count read from the file against the bytes left, before anything is allocated
+    /// for it. `element_size` is the fewest bytes one element can take up.
+    fn safe_count(&self, count: usize, element_size: usize) -> Result<usize, Error> {
+        let needed = count.saturating_mul(element_size);
+        if needed <= self.remaining() {
+            Ok(count)
         } else {
-            Err(eyre!(
-                "Advance overrun by {} bytes.",
-                cnt - self.remaining()
-            ))
+            Err(Overrun(needed - self.remaining()).into())
         }
     }
 }
 
 impl<I> BufResult for I where I: Buf {}
 
-pub(crate) trait BytesExt {
+pub trait BytesExt {
     fn safe_slice(&self, range: impl RangeBounds<usize>) -> Result<Self, Error>
     where
         Self: Sized;
+
+    /// Splits off the next `count` bytes without copying them.
+    fn safe_split_to(&mut self, count: usize) -> Result<Self, Error>
+    where
+        Self: Sized;
 }
 
 impl BytesExt for bytes::Bytes {
@@ -92,9 +150,17 @@ impl BytesExt for bytes::Bytes {
         }
 
         if end > len {
-            return Err(eyre!("range end out of bounds: {:?} <= {:?}", end, len,));
+            return Err(Overrun(end - len).into());
         }
 
         Ok(self.slice(range))
     }
+
+    fn safe_split_to(&mut self, count: usize) -> Result<Self, Error> {
+        if count > self.remaining() {
+            return Err(Overrun(count - self.remaining()).into());
+        }
+
+        Ok(self.split_to(count))
+    }
 }
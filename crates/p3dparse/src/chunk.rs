@@ -1,17 +1,23 @@
 pub mod data;
+pub mod edit;
+pub mod index;
+pub mod query;
+pub mod registry;
 pub mod type_identifiers;
+pub mod visitor;
 
 use std::fmt::Display;
 
 use crate::{
-    bytes_ext::{BufResult, BytesExt},
+    bytes_ext::{is_big_endian, BufResult, BytesExt},
     chunk::{
         data::{data_enum::ChunkData, kinds::name::Name},
         type_identifiers::ChunkType,
     },
-    Result,
+    error::{Diagnostic, DiagnosticKind, ParseError},
+    ParseOptions, Result,
 };
-use bytes::Bytes;
+use bytes::{Buf, BufMut, Bytes};
 use eyre::eyre;
 use serde::{Deserialize, Serialize};
 
@@ -28,6 +34,30 @@ impl Display for Span {
     }
 }
 
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+/// Where a chunk was in the file it was parsed from, chunks built by hand are all zeroes.
+/// Display format is Offset+Data Size/Total Size, all in hex
+pub struct FileSpan {
+    /// Absolute byte offset of the chunk header in the (decompressed) file.
+    pub offset: usize,
+    /// Data size as read from the header, including the 12 byte header itself.
+    pub data_size: u32,
+    /// Total size as read from the header, including the header and all children.
+    pub total_size: u32,
+    /// Whether the file was big endian, which [`Chunk::unparsed_data`] still is.
+    pub big_endian: bool,
+}
+
+impl Display for FileSpan {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{:#X}+{:#X}/{:#X}",
+            self.offset, self.data_size, self.total_size
+        )
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
 pub struct Chunk {
     pub typ: ChunkType,
@@ -35,63 +65,114 @@ pub struct Chunk {
     pub span: Span,
     pub parent: Option<usize>,
     pub children: Vec<usize>,
+    /// Payload bytes that didn't end up in [`Chunk::data`], either because there's no parser for
+    /// this chunk type or because the parser left some bytes behind. These are written back verbatim,
+    /// straight after the data and before any children.
+    pub unparsed_data: Bytes,
+    /// Not updated by [`Chunk::write`], so this goes stale once the tree is modified.
+    pub file_span: FileSpan,
 }
 
 impl Chunk {
-    pub fn parse_root(bytes: &mut Bytes) -> Result<Vec<Chunk>> {
+    pub fn parse_root(
+        bytes: &mut Bytes,
+        options: &ParseOptions,
+    ) -> Result<(Vec<Chunk>, Vec<Diagnostic>), ParseError> {
         let mut vec = Vec::new();
+        let mut diagnostics = Vec::new();
 
-        let typ: ChunkType = bytes.clone().safe_get_u32_le()?.try_into()?;
-        if typ != ChunkType::DataFile {
-            return Err(eyre!("{:?} P3D files aren't currently supported.", typ));
+        let magic = bytes
+            .clone()
+            .safe_get_u32()
+            .map_err(|_| ParseError::UnrecognisedFile { magic: 0 })?;
+        if ChunkType::from(magic) != ChunkType::DataFile {
+            return Err(ParseError::UnrecognisedFile { magic });
         }
 
-        Chunk::parse(bytes, &mut vec, None, 0)?;
+        Chunk::parse(bytes, &mut vec, None, 0, 0, options, &mut diagnostics)?;
 
-        Ok(vec)
+        Ok((vec, diagnostics))
     }
 
+    /// Parses the chunk starting at `offset`, which is its absolute position in the file, and all of its children.
+    /// Anything we had to recover from is reported to `diagnostics`, or returned as an error in strict mode.
     pub fn parse(
         bytes: &mut Bytes,
         vec: &mut Vec<Chunk>,
         parent: Option<usize>,
         relative_index: usize,
-    ) -> Result<usize> {
-        // Note: C# BinaryReader is always LE unless otherwise stated
+        offset: usize,
+        options: &ParseOptions,
+        diagnostics: &mut Vec<Diagnostic>,
+    ) -> Result<usize, ParseError> {
+        let parent_lineage = |vec: &[Chunk]| match parent {
+            Some(parent) => vec[parent].get_lineage(vec),
+            None => "Unknown".to_owned(),
+        };
+
+        // Note: C# BinaryReader is always LE, console builds are BE, see EndianGuard
+        let header_field = |bytes: &mut Bytes, typ: Option<ChunkType>, field: &str| {
+            bytes.safe_get_u32().map_err(|_| ParseError::UnexpectedEof {
+                offset,
+                typ,
+                field: field.to_owned(),
+                needed: 4 - bytes.remaining(),
+                lineage: parent_lineage(vec),
+            })
+        };
         // First 4 bytes indicate the chunk type
-        let typ: ChunkType = bytes.safe_get_u32_le()?.try_into()?;
+        let typ = ChunkType::from(header_field(bytes, None, "chunk type")?);
         // Second 4 bytes indicate the data size including the 12 bytes for these fields
-        let data_size = bytes.safe_get_u32_le()?;
+        let data_size = header_field(bytes, Some(typ), "data size")?;
         // Third 4 bytes indicate entire size including children
-        let total_size = bytes.safe_get_u32_le()?;
+        let total_size = header_field(bytes, Some(typ), "total size")?;
 
-        // If the data size is larger than the total size, this is corrupted.
-        if data_size > total_size {
-            return Err(eyre!("File is corrupted. Data size {} is greater than total size {} for chunk {} (no lineage data available, this is a fatal error.)", data_size, total_size, relative_index));
+        // The data size can never be smaller than the header it includes,
+        // and if the data size is larger than the total size, this is corrupted.
+        if data_size < 12 || data_size > total_size {
+            return Err(ParseError::CorruptHeader {
+                offset,
+                typ,
+                data_size,
+                total_size,
+                lineage: parent_lineage(vec),
+            });
         }
 
         // We expect to read data_size - 12 bytes in data.
         let expected_parse_size = (data_size - 12) as usize;
         // So we'll only give that many bytes to the ChunkData parser.
-        let mut data_slice = bytes.safe_slice(0..expected_parse_size)?;
-        #[cfg(debug_assertions)]
-        let original_data_slice = data_slice.clone();
+        let data_offset = offset + 12;
+        let mut data_slice =
+            bytes
+                .safe_slice(0..expected_parse_size)
+                .map_err(|_| ParseError::UnexpectedEof {
+                    offset: data_offset,
+                    typ: Some(typ),
+                    field: "data".to_owned(),
+                    needed: expected_parse_size - bytes.remaining(),
+                    lineage: parent_lineage(vec),
+                })?;
         // Let the data get parsed...
         let data = match ChunkData::from_chunk_type(typ, &mut data_slice) {
             Ok(data) => data,
             Err(e) => {
-                let lineage = format!(
-                    "Error: Could not parse data. Lineage Info: {}",
-                    if let Some(parent) = parent {
-                        vec.get(parent).unwrap().get_lineage(vec)
-                    } else {
-                        "Unknown".to_owned()
-                    }
-                );
-                return Err(e.wrap_err(lineage));
+                return Err(ParseError::from_report(
+                    e,
+                    data_offset + expected_parse_size - data_slice.remaining(),
+                    typ,
+                    parent_lineage(vec),
+                ));
             }
         };
 
+        let mut unparsed_data = Bytes::new();
+        let unknown = data == ChunkData::Unknown;
+        if unknown {
+            // Nothing to recover here, we just don't know how to parse it. Keep it around for later.
+            unparsed_data = std::mem::take(&mut data_slice);
+        }
+
         let index = vec.len();
         vec.push(Chunk {
             typ,
@@ -102,64 +183,86 @@ impl Chunk {
             },
             data,
             parent,
+            unparsed_data: Bytes::new(),
+            file_span: FileSpan {
+                offset,
+                data_size,
+                total_size,
+                big_endian: is_big_endian(),
+            },
         });
 
+        let diagnostic = |vec: &[Chunk], kind| Diagnostic {
+            offset,
+            typ,
+            lineage: vec[index].get_lineage(vec),
+            kind,
+        };
+
+        if unknown {
+            let kind = match typ {
+                ChunkType::Other(_) => DiagnosticKind::UnrecognisedChunk,
+                _ => DiagnosticKind::UnparsedChunk,
+            };
+            diagnostic(vec, kind).report(options, diagnostics)?;
+        }
+
         let mut children = Vec::new();
 
         // It's okay if we fail to parse a chunk because we always know the size and can keep our framing intact.
         if !data_slice.is_empty() {
-            // eprintln!(
-            //     "Warning: Chunk {} was expected to parse {:?} bytes but only parsed {:?}, data result: {:?}",
-            //     &chunk.get_lineage(), expected_parse_size, expected_parse_size - data_slice.len(), chunk.data
-            // );
+            // Strict mode won't accept whatever we'd recover, so don't bother trying
+            if options.strict {
+                let kind = DiagnosticKind::LeftoverBytes {
+                    count: data_slice.len(),
+                };
+                return Err(ParseError::Strict(diagnostic(vec, kind)));
+            }
+
             let actually_consumed = expected_parse_size - data_slice.len();
             // Our original bytes slice has already consumed the 12 byte header, so we have to subtract it here
             // Potential Children Size can never be 0 because we know we have leftover data in the data slice.
             let potential_children_size = total_size as usize - actually_consumed - 12;
             // However, that does not mean we want to index further than what we have read
-            let mut potential_children_slice =
-                bytes.safe_slice(actually_consumed..actually_consumed + potential_children_size)?;
-            #[cfg(debug_assertions)]
-            let original_potential_children_slice = potential_children_slice.clone();
-            #[cfg(debug_assertions)]
-            {
-                eprintln!(
-                    "Recovery: We expected to parse {} bytes but actually parsed {} bytes",
-                    expected_parse_size, actually_consumed
-                );
-                eprintln!(
-                    "Recovery: We will try to find children in the remaining {} bytes",
-                    potential_children_size
-                );
-            }
+            let mut potential_children_slice = bytes
+                .safe_slice(actually_consumed..actually_consumed + potential_children_size)
+                .map_err(|_| ParseError::UnexpectedEof {
+                    offset: data_offset + actually_consumed,
+                    typ: Some(typ),
+                    field: "children".to_owned(),
+                    needed: actually_consumed + potential_children_size - bytes.remaining(),
+                    lineage: vec[index].get_lineage(vec),
+                })?;
 
             let mut child_count = 0;
             let mut parsed_so_far = 0;
             while parsed_so_far < potential_children_size {
+                // Unrecognised IDs are only trustworthy when the framing is, which it isn't here.
+                if let Ok(ChunkType::Other(_)) = potential_children_slice
+                    .clone()
+                    .safe_get_u32()
+                    .map(ChunkType::from)
+                {
+                    break;
+                }
+
                 let before_parse = potential_children_slice.len();
-                match Chunk::parse(&mut potential_children_slice, vec, Some(index), child_count) {
-                    Ok(child) => {
-                        #[cfg(debug_assertions)]
-                        eprintln!(
-                            "Recovery: We sucessfully parsed a misaligned child at index {}",
-                            child // vec.get(child).unwrap().get_lineage()
-                        );
-                        children.push(child);
-                    }
-                    Err(_e) => {
-                        #[cfg(debug_assertions)]
-                        {
-                            eprintln!(
-                                "Recovery: We failed to parse a misaligned child for {}",
-                                vec.last().unwrap().get_lineage(vec)
-                            );
-                            eprintln!("Recovery: This was caused by: {:?}", _e);
-                            eprintln!("-- Full Data Hexdump --");
-                            hexdump::hexdump(&original_data_slice);
-                            eprintln!("-- Misaligned Data Hexdump --");
-                            hexdump::hexdump(&original_potential_children_slice);
-                        }
-                        // If any child parsing fails then we just have to ignore the rest of these...
+                let (vec_len, diagnostics_len) = (vec.len(), diagnostics.len());
+                match Chunk::parse(
+                    &mut potential_children_slice,
+                    vec,
+                    Some(index),
+                    child_count,
+                    data_offset + actually_consumed + parsed_so_far,
+                    options,
+                    diagnostics,
+                ) {
+                    Ok(child) => children.push(child),
+                    Err(_) => {
+                        // If any child parsing fails then we just have to ignore the rest of these,
+                        // along with anything the failed attempt left behind.
+                        vec.truncate(vec_len);
+                        diagnostics.truncate(diagnostics_len);
                         break;
                     }
                 }
@@ -167,10 +270,26 @@ impl Chunk {
                 parsed_so_far += before_parse - after_parse;
                 child_count += 1;
             }
+
+            // The leftovers weren't children after all, so they're just data we don't understand.
+            let kind = if children.is_empty() {
+                let count = data_slice.len();
+                unparsed_data = data_slice;
+                DiagnosticKind::LeftoverBytes { count }
+            } else {
+                // Whatever came after the last child we could parse is kept the same way
+                unparsed_data = potential_children_slice;
+                DiagnosticKind::MisalignedChildren {
+                    consumed: actually_consumed,
+                    expected: expected_parse_size,
+                    children: children.len(),
+                }
+            };
+            diagnostic(vec, kind).report(options, diagnostics)?;
         }
 
         // We parsed something, maybe an Unknown chunk, we need to move past it to keep framing intact.
-        bytes.safe_advance(expected_parse_size)?;
+        bytes.advance(expected_parse_size);
 
         // Only parse children if we didn't do so already in fallback
         if children.is_empty() {
@@ -181,7 +300,15 @@ impl Chunk {
                 let mut child_count = 0;
                 while parsed_so_far < total_children_size {
                     let before_parse = bytes.len();
-                    let child = Chunk::parse(bytes, vec, Some(index), child_count)?;
+                    let child = Chunk::parse(
+                        bytes,
+                        vec,
+                        Some(index),
+                        child_count,
+                        offset + data_size as usize + parsed_so_far,
+                        options,
+                        diagnostics,
+                    )?;
                     children.push(child);
                     let after_parse = bytes.len();
                     parsed_so_far += before_parse - after_parse;
@@ -190,20 +317,49 @@ impl Chunk {
             }
         }
 
-        vec.get_mut(index).unwrap().children = children;
+        let chunk = vec.get_mut(index).unwrap();
+        chunk.children = children;
+        chunk.unparsed_data = unparsed_data;
 
         Ok(index)
     }
 
-    /// See [`Chunk::get_name`] for information about the span format.
+    /// Writes this chunk and all of its children into `bytes`.
+    ///
+    /// The data size and total size in the header are recomputed from what is actually written,
+    /// so they don't have to be kept in sync by hand when the tree is modified.
+    pub fn write(&self, vec: &[Chunk], bytes: &mut Vec<u8>) {
+        let start = bytes.len();
+
+        bytes.put_u32_le(self.typ.into());
+        // Data size and total size, we fill these in once we know them
+        bytes.put_u32_le(0);
+        bytes.put_u32_le(0);
+
+        self.data.write(self.typ, bytes);
+        bytes.put_slice(&self.unparsed_data);
+        let data_size = (bytes.len() - start) as u32;
+
+        for child in &self.children {
+            vec.get(*child)
+                .expect("Invariant violated: Chunk thought it had a child at an invalid index")
+                .write(vec, bytes);
+        }
+        let total_size = (bytes.len() - start) as u32;
+
+        bytes[start + 4..start + 8].copy_from_slice(&data_size.to_le_bytes());
+        bytes[start + 8..start + 12].copy_from_slice(&total_size.to_le_bytes());
+    }
+
+    /// See [`Chunk::get_name`] for information about the span format, each name is followed by the [`FileSpan`].
     pub fn get_lineage(&self, vec: &[Chunk]) -> String {
-        let mut string = self.get_name();
+        let mut string = format!("{}@{}", self.get_name(), self.file_span);
         let mut target: &Chunk = self;
         while let Some(parent) = target.parent {
             let parent = vec
                 .get(parent)
                 .expect("Invariant violated: Child thought it had a parent at an invalid index!");
-            string.push_str(&format!(" -> {}", parent.get_name()));
+            string.push_str(&format!(" -> {}@{}", parent.get_name(), parent.file_span));
             target = parent;
         }
         string
@@ -229,28 +385,22 @@ impl Chunk {
             .get(index)
             .ok_or_else(|| eyre!("Invalid child index"))?;
 
-        vec.iter()
-            .enumerate()
-            .find_map(|(index, chunk)| {
-                if index == *child_index {
-                    Some(chunk)
-                } else {
-                    None
-                }
-            })
-            .ok_or_else(|| {
-                panic!("Invariant violated: Chunk thought it had a child at an invalid index")
-            })
+        Ok(vec
+            .get(*child_index)
+            .expect("Invariant violated: Chunk thought it had a child at an invalid index"))
     }
 
     pub fn get_children<'a>(&self, vec: &'a [Chunk]) -> impl Iterator<Item = &'a Chunk> + 'a {
-        let children = self.children.clone();
-        vec.iter().enumerate().filter_map(move |(index, chunk)| {
-            if children.contains(&index) {
-                Some(chunk)
-            } else {
-                None
-            }
+        self.children.clone().into_iter().map(move |child| {
+            vec.get(child)
+                .expect("Invariant violated: Chunk thought it had a child at an invalid index")
+        })
+    }
+
+    pub fn get_parent<'a>(&self, vec: &'a [Chunk]) -> Option<&'a Chunk> {
+        self.parent.map(|parent| {
+            vec.get(parent)
+                .expect("Invariant violated: Child thought it had a parent at an invalid index!")
         })
     }
 
@@ -265,11 +415,21 @@ impl Chunk {
 
 pub trait VecChunkExtension {
     fn get_root(&self) -> Result<&Chunk>;
+    /// Returns every chunk whose ID isn't in [`ChunkType`], along with its lineage.
+    /// Their payload and children are kept as-is, so they still round trip.
+    fn get_unrecognised(&self) -> Vec<(&Chunk, String)>;
 }
 
 impl VecChunkExtension for Vec<Chunk> {
     fn get_root(&self) -> Result<&Chunk> {
-        self.get(0)
+        self.first()
             .ok_or_else(|| eyre!("Vec does not contain root chunk"))
     }
+
+    fn get_unrecognised(&self) -> Vec<(&Chunk, String)> {
+        self.iter()
+            .filter(|c| matches!(c.typ, ChunkType::Other(_)))
+            .map(|c| (c, c.get_lineage(self)))
+            .collect()
+    }
 }
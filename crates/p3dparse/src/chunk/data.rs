@@ -1,6 +1,9 @@
-mod helpers;
-mod parse_trait;
+pub mod helpers;
+pub mod parse_trait;
+pub mod schema;
+pub mod write_trait;
 
+pub mod custom;
 pub mod data_enum;
 pub mod data_enum_impl;
 pub mod kinds;
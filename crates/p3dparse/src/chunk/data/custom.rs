@@ -0,0 +1,235 @@
+//! Chunk data defined outside of p3dparse, see [`registry`](crate::chunk::registry).
+
+use std::{any::Any, fmt};
+
+use serde::{
+    de::{self, DeserializeOwned, DeserializeSeed, MapAccess, SeqAccess, Visitor},
+    ser::SerializeStruct,
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+
+use crate::chunk::{
+    data::{
+        kinds::{name::Name, version::Version},
+        parse_trait::Parse,
+        write_trait::Write,
+    },
+    registry,
+    type_identifiers::ChunkType,
+};
+
+/// A payload type that can be stored in [`ChunkData::Custom`](crate::chunk::data::data_enum::ChunkData::Custom).
+///
+/// Register it with [`register_custom`](registry::register_custom) before parsing, or before
+/// deserializing a tree that contains it.
+pub trait CustomPayload:
+    Parse
+    + Write
+    + Serialize
+    + DeserializeOwned
+    + Clone
+    + PartialEq
+    + fmt::Debug
+    + Send
+    + Sync
+    + 'static
+{
+    /// Identifies the type in serialized trees, so it has to be unique among registered payloads.
+    const NAME: &'static str;
+
+    fn get_name(&self) -> Option<Name> {
+        None
+    }
+
+    fn get_name_mut(&mut self) -> Option<&mut Name> {
+        None
+    }
+
+    fn get_version(&self) -> Option<Version> {
+        None
+    }
+}
+
+/// The object safe half of [`CustomPayload`].
+trait DynPayload: fmt::Debug + Send + Sync {
+    fn payload_name(&self) -> &'static str;
+    fn write(&self, bytes: &mut Vec<u8>, typ: ChunkType);
+    fn clone_box(&self) -> Box<dyn DynPayload>;
+    fn eq_dyn(&self, other: &dyn DynPayload) -> bool;
+    fn as_any(&self) -> &dyn Any;
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+    fn as_serialize(&self) -> &dyn erased_serde::Serialize;
+    fn get_name(&self) -> Option<Name>;
+    fn get_name_mut(&mut self) -> Option<&mut Name>;
+    fn get_version(&self) -> Option<Version>;
+}
+
+impl<T: CustomPayload> DynPayload for T {
+    fn payload_name(&self) -> &'static str {
+        T::NAME
+    }
+
+    fn write(&self, bytes: &mut Vec<u8>, typ: ChunkType) {
+        Write::write(self, bytes, typ)
+    }
+
+    fn clone_box(&self) -> Box<dyn DynPayload> {
+        Box::new(self.clone())
+    }
+
+    fn eq_dyn(&self, other: &dyn DynPayload) -> bool {
+        other.as_any().downcast_ref::<T>() == Some(self)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn as_serialize(&self) -> &dyn erased_serde::Serialize {
+        self
+    }
+
+    fn get_name(&self) -> Option<Name> {
+        CustomPayload::get_name(self)
+    }
+
+    fn get_name_mut(&mut self) -> Option<&mut Name> {
+        CustomPayload::get_name_mut(self)
+    }
+
+    fn get_version(&self) -> Option<Version> {
+        CustomPayload::get_version(self)
+    }
+}
+
+/// A boxed [`CustomPayload`]. Serializes as `{ "name": T::NAME, "data": ... }`.
+#[derive(Debug)]
+pub struct CustomData(Box<dyn DynPayload>);
+
+impl CustomData {
+    pub fn new<T: CustomPayload>(payload: T) -> Self {
+        CustomData(Box::new(payload))
+    }
+
+    /// The [`CustomPayload::NAME`] of the payload.
+    pub fn payload_name(&self) -> &'static str {
+        self.0.payload_name()
+    }
+
+    pub fn downcast_ref<T: CustomPayload>(&self) -> Option<&T> {
+        self.0.as_any().downcast_ref()
+    }
+
+    pub fn downcast_mut<T: CustomPayload>(&mut self) -> Option<&mut T> {
+        self.0.as_any_mut().downcast_mut()
+    }
+
+    pub fn get_name(&self) -> Option<Name> {
+        self.0.get_name()
+    }
+
+    pub fn get_name_mut(&mut self) -> Option<&mut Name> {
+        self.0.get_name_mut()
+    }
+
+    pub fn get_version(&self) -> Option<Version> {
+        self.0.get_version()
+    }
+}
+
+impl Write for CustomData {
+    fn write(&self, bytes: &mut Vec<u8>, typ: ChunkType) {
+        self.0.write(bytes, typ)
+    }
+}
+
+impl Clone for CustomData {
+    fn clone(&self) -> Self {
+        CustomData(self.0.clone_box())
+    }
+}
+
+impl PartialEq for CustomData {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.eq_dyn(&*other.0)
+    }
+}
+
+impl Serialize for CustomData {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("CustomData", 2)?;
+        state.serialize_field("name", self.payload_name())?;
+        state.serialize_field("data", self.0.as_serialize())?;
+        state.end()
+    }
+}
+
+pub(crate) type DeserializeFn =
+    fn(&mut dyn erased_serde::Deserializer) -> Result<CustomData, erased_serde::Error>;
+
+pub(crate) fn deserialize_payload<T: CustomPayload>(
+    deserializer: &mut dyn erased_serde::Deserializer,
+) -> Result<CustomData, erased_serde::Error> {
+    erased_serde::deserialize::<T>(deserializer).map(CustomData::new)
+}
+
+fn lookup<E: de::Error>(name: &str) -> Result<DeserializeFn, E> {
+    registry::payload_deserializer(name)
+        .ok_or_else(|| E::custom(format!("Custom payload {:?} is not registered", name)))
+}
+
+struct PayloadSeed(DeserializeFn);
+
+impl<'de> DeserializeSeed<'de> for PayloadSeed {
+    type Value = CustomData;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<CustomData, D::Error> {
+        let mut erased = <dyn erased_serde::Deserializer>::erase(deserializer);
+        (self.0)(&mut erased).map_err(de::Error::custom)
+    }
+}
+
+struct CustomDataVisitor;
+
+impl<'de> Visitor<'de> for CustomDataVisitor {
+    type Value = CustomData;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a custom payload name followed by its data")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<CustomData, A::Error> {
+        let name: String = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+        seq.next_element_seed(PayloadSeed(lookup(&name)?))?
+            .ok_or_else(|| de::Error::invalid_length(1, &self))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<CustomData, A::Error> {
+        // The name decides how to read the data, so it has to come first
+        match map.next_key::<String>()?.as_deref() {
+            Some("name") => {}
+            Some(key) => return Err(de::Error::custom(format!("Expected name, found {}", key))),
+            None => return Err(de::Error::missing_field("name")),
+        }
+        let name: String = map.next_value()?;
+        let deserialize = lookup(&name)?;
+
+        match map.next_key::<String>()?.as_deref() {
+            Some("data") => map.next_value_seed(PayloadSeed(deserialize)),
+            Some(key) => Err(de::Error::unknown_field(key, &["name", "data"])),
+            None => Err(de::Error::missing_field("data")),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for CustomData {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_struct("CustomData", &["name", "data"], CustomDataVisitor)
+    }
+}
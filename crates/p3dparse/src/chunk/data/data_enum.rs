@@ -1,192 +1,304 @@
-use crate::chunk::data::kinds::{
-    animation::{Animation, AnimationGroup, AnimationGroupList, AnimationSize},
-    channel::{Channel, ChannelInterpolation},
-    collision::{
-        CollisionBoundingBox, CollisionCylinder, CollisionObject, CollisionObjectAttribute,
-        CollisionOblongBox, CollisionSphere, CollisionVector, CollisionVolume,
-        CollisionVolumeOwner, IntersectDSG, TerrainTypeList,
+use crate::chunk::data::{
+    custom::CustomData,
+    kinds::{
+        animation::{Animation, AnimationGroup, AnimationGroupList, AnimationSize},
+        channel::{Channel, ChannelInterpolation},
+        collision::{
+            CollisionBoundingBox, CollisionCylinder, CollisionObject, CollisionObjectAttribute,
+            CollisionOblongBox, CollisionSphere, CollisionVector, CollisionVolume,
+            CollisionVolumeOwner, IntersectDSG, TerrainTypeList,
+        },
+        explosion::BreakableObject,
+        file_metadata::{ExportInfoNamedInt, ExportInfoNamedString, History},
+        frontend::{
+            FrontendGroup, FrontendImageResource, FrontendLanguage, FrontendLayer, FrontendMovie,
+            FrontendMultiSprite, FrontendMultiText, FrontendPage, FrontendPolygon, FrontendProject,
+            FrontendPure3DObject, FrontendResource, FrontendScreen, FrontendStringHardCoded,
+            FrontendStringTextBible, FrontendTextBible,
+        },
+        game_metadata::{FollowCameraData, Locator},
+        gameattr::{GameAttr, GameAttrParam},
+        image::{Image, ImageRaw},
+        light::{
+            Light, LightConeParam, LightDecayRange, LightDecayRangeRotationY, LightDirection,
+            LightGroup, LightIlluminationType, LightPosition, LightShadow,
+        },
+        locator::{WBLocator, WBMatrix, WBRail, WBSpline, WBTriggerVolume},
+        mesh::{
+            BinormalList, ColourList, CompositeDrawable, CompositeDrawableEffect,
+            CompositeDrawableEffectList, CompositeDrawableProp, CompositeDrawablePropList,
+            CompositeDrawableSkin, CompositeDrawableSkinList, CompositeDrawableSortOrder,
+            IndexList, MatrixList, MatrixPalette, Mesh, NormalList, OldPrimGroup, PackedNormalList,
+            PositionList, RenderStatus, Skin, TangentList, UVList, WeightList,
+        },
+        name::Name,
+        object::{
+            AnimatedObject, AnimatedObjectAnimation, AnimatedObjectDSGWrapper,
+            AnimatedObjectFactory, MultiController, MultiControllerTracks, ObjectDSG,
+            OldFrameController,
+        },
+        old_billboard::{
+            OldBillboardDisplayInfo, OldBillboardPerspectiveInfo, OldBillboardQuad,
+            OldBillboardQuadGroup,
+        },
+        old_particle_system::{
+            InstanceableParticleSystem, OldBaseEmitter, OldParticleSystem,
+            OldParticleSystemFactory, OldParticleSystemInstancingInfo, OldSpriteEmitter,
+        },
+        physics::{
+            BoundingBox, BoundingSphere, PhysicsInertiaMatrix, PhysicsJoint, PhysicsObject,
+            PhysicsVector,
+        },
+        prop_state::{
+            ObjectAttributes, StatePropCallbackData, StatePropDataV1, StatePropEventData,
+            StatePropFrameControllerData, StatePropStateDataV1, StatePropVisibilitiesData,
+        },
+        pure3d_other::Camera,
+        scenegraph::{
+            ScenegraphAttachment, ScenegraphAttachmentPoint, ScenegraphBranch, ScenegraphCamera,
+            ScenegraphDrawable, ScenegraphLightGroup, ScenegraphSortOrder, ScenegraphTransform,
+            ScenegraphVisibility,
+        },
+        shader::{Shader, VertexShader},
+        shader_param::ShaderParam,
+        skeleton::{Skeleton, SkeletonJoint, SkeletonJointBonePreserve, SkeletonJointMirrorMap},
+        texture::Texture,
+        version::Version,
     },
-    explosion::BreakableObject,
-    file_metadata::{ExportInfoNamedInt, ExportInfoNamedString, History},
-    game_metadata::{FollowCameraData, Locator},
-    gameattr::{GameAttr, GameAttrParam},
-    image::{Image, ImageRaw},
-    locator::{WBLocator, WBMatrix, WBRail, WBSpline, WBTriggerVolume},
-    mesh::{
-        BinormalList, ColourList, CompositeDrawable, CompositeDrawableEffect,
-        CompositeDrawableEffectList, CompositeDrawableProp, CompositeDrawablePropList,
-        CompositeDrawableSkin, CompositeDrawableSkinList, CompositeDrawableSortOrder, IndexList,
-        MatrixList, MatrixPalette, Mesh, NormalList, OldPrimGroup, PackedNormalList, PositionList,
-        RenderStatus, Skin, TangentList, UVList, WeightList,
-    },
-    name::Name,
-    object::{
-        AnimatedObject, AnimatedObjectAnimation, AnimatedObjectDSGWrapper, AnimatedObjectFactory,
-        MultiController, MultiControllerTracks, ObjectDSG, OldFrameController,
-    },
-    old_billboard::{
-        OldBillboardDisplayInfo, OldBillboardPerspectiveInfo, OldBillboardQuad,
-        OldBillboardQuadGroup,
-    },
-    old_particle_system::{
-        InstanceableParticleSystem, OldBaseEmitter, OldParticleSystem, OldParticleSystemFactory,
-        OldParticleSystemInstancingInfo, OldSpriteEmitter,
-    },
-    physics::{
-        BoundingBox, BoundingSphere, PhysicsInertiaMatrix, PhysicsJoint, PhysicsObject,
-        PhysicsVector,
-    },
-    prop_state::{
-        ObjectAttributes, StatePropCallbackData, StatePropDataV1, StatePropEventData,
-        StatePropFrameControllerData, StatePropStateDataV1, StatePropVisibilitiesData,
-    },
-    pure3d_other::Camera,
-    scenegraph::{
-        ScenegraphAttachment, ScenegraphAttachmentPoint, ScenegraphBranch, ScenegraphCamera,
-        ScenegraphDrawable, ScenegraphLightGroup, ScenegraphSortOrder, ScenegraphTransform,
-        ScenegraphVisibility,
-    },
-    shader::{Shader, VertexShader},
-    shader_param::ShaderParam,
-    skeleton::{Skeleton, SkeletonJoint, SkeletonJointBonePreserve, SkeletonJointMirrorMap},
-    texture::Texture,
-    version::Version,
 };
 use serde::{Deserialize, Serialize};
 
-#[derive(Clone, PartialEq, Debug, Deserialize, Serialize)]
-#[allow(dead_code)]
-pub enum ChunkData {
-    // Others
-    None,
-    Unknown,
-    // Rendering
-    Shader(Name, Version, Shader),
-    ShaderParam(ShaderParam),
-    Texture(Name, Version, Texture),
-    Image(Name, Version, Image),
-    ImageRaw(ImageRaw),
-    VertexShader(VertexShader),
-    // Old Particle System
-    OldParticleSystem(Version, Name, OldParticleSystem),
-    OldParticleSystemFactory(Version, Name, OldParticleSystemFactory),
-    OldParticleInstancingInfo(Version, OldParticleSystemInstancingInfo),
-    OldParticleAnimation(Version),
-    OldEmitterAnimation(Version),
-    OldGeneratorAnimation(Version),
-    OldBaseEmitter(Version, Name, OldBaseEmitter),
-    OldSpriteEmitter(Version, Name, OldSpriteEmitter),
-    InstanceableParticleSystem(InstanceableParticleSystem),
-    // Animations
-    Animation(Version, Name, Animation),
-    AnimationSize(Version, AnimationSize),
-    AnimationGroup(Version, Name, AnimationGroup),
-    AnimationGroupList(Version, AnimationGroupList),
-    Channel(Version, Channel),
-    ChannelInterpolation(Version, ChannelInterpolation),
-    OldFrameController(Version, Name, OldFrameController),
-    MultiController(Name, Version, MultiController),
-    MultiControllerTracks(MultiControllerTracks),
-    // Old Billboards
-    OldBillboardQuad(Version, Name, OldBillboardQuad),
-    OldBillboardQuadGroup(Version, Name, OldBillboardQuadGroup),
-    OldBillboardDisplayInfo(Version, OldBillboardDisplayInfo),
-    OldBillboardPerspectiveInfo(Version, OldBillboardPerspectiveInfo),
-    // Breakable Objects
-    BreakableObject(BreakableObject),
-    // Skinning
-    Skeleton(Name, Version, Skeleton),
-    SkeletonJoint(Name, SkeletonJoint),
-    SkeletonJointMirrorMap(SkeletonJointMirrorMap),
-    SkeletonJointBonePreserve(SkeletonJointBonePreserve),
-    Skin(Name, Version, Skin),
-    MatrixList(MatrixList),
-    MatrixPalette(MatrixPalette),
-    WeightList(WeightList),
-    // Meshes
-    Mesh(Name, Version, Mesh),
-    PrimGroup(Version, OldPrimGroup),
-    PositionList(PositionList),
-    NormalList(NormalList),
-    PackedNormalList(PackedNormalList),
-    TangentList(TangentList),
-    BinormalList(BinormalList),
-    UVList(UVList),
-    ColourList(ColourList),
-    IndexList(IndexList),
-    // Composite Drawables (multiple meshes/skins in one)
-    RenderStatus(RenderStatus),
-    CompositeDrawable(Name, CompositeDrawable),
-    CompositeDrawableEffect(Name, CompositeDrawableEffect),
-    CompositeDrawableEffectList(CompositeDrawableEffectList),
-    CompositeDrawableProp(Name, CompositeDrawableProp),
-    CompositeDrawablePropList(CompositeDrawablePropList),
-    CompositeDrawableSkin(Name, CompositeDrawableSkin),
-    CompositeDrawableSkinList(CompositeDrawableSkinList),
-    CompositeDrawableSortOrder(CompositeDrawableSortOrder),
-    AnimatedObjectFactory(Version, Name, AnimatedObjectFactory),
-    AnimatedObject(Version, Name, AnimatedObject),
-    AnimatedObjectAnimation(Version, Name, AnimatedObjectAnimation),
-    ObjectDSG(Name, Version, ObjectDSG),
-    AnimatedObjectDSGWrapper(Name, AnimatedObjectDSGWrapper),
-    // Physics
-    BoundingBox(BoundingBox),
-    BoundingSphere(BoundingSphere),
-    PhysicsObject(Name, Version, PhysicsObject),
-    PhysicsJoint(PhysicsJoint),
-    PhysicsVector(PhysicsVector),
-    PhysicsInertiaMatrix(PhysicsInertiaMatrix),
-    // Collision
-    CollisionObject(Name, Version, CollisionObject),
-    CollisionVolume(CollisionVolume),
-    CollisionVolumeOwner(CollisionVolumeOwner),
-    CollisionVolumeOwnerName(Name),
-    CollisionBoundingBox(CollisionBoundingBox),
-    CollisionOblongBox(CollisionOblongBox),
-    CollisionCylinder(CollisionCylinder),
-    CollisionSphere(CollisionSphere),
-    CollisionVector(CollisionVector),
-    CollisionObjectAttribute(CollisionObjectAttribute),
-    IntersectDSG(IntersectDSG),
-    TerrainTypeList(Version, TerrainTypeList),
-    StaticPhysicsDSG(Name, Version),
-    // Prop Data
-    StatePropDataV1(Version, Name, StatePropDataV1),
-    StatePropStateDataV1(Name, StatePropStateDataV1),
-    StatePropVisibilitiesData(Name, StatePropVisibilitiesData),
-    StatePropFrameControllerData(Name, StatePropFrameControllerData),
-    StatePropEventData(Name, StatePropEventData),
-    StatePropCallbackData(Name, StatePropCallbackData),
-    PropInstanceList(Name),
-    ObjectAttributes(ObjectAttributes),
-    // Scenegraph
-    Scenegraph(Name, Version),
-    ScenegraphBranch(Name, ScenegraphBranch),
-    ScenegraphTransform(Name, ScenegraphTransform),
-    ScenegraphVisibility(Name, ScenegraphVisibility),
-    ScenegraphAttachment(Name, ScenegraphAttachment),
-    ScenegraphAttachmentPoint(ScenegraphAttachmentPoint),
-    ScenegraphDrawable(Name, ScenegraphDrawable),
-    ScenegraphCamera(Name, ScenegraphCamera),
-    ScenegraphLightGroup(Name, ScenegraphLightGroup),
-    ScenegraphSortOrder(ScenegraphSortOrder),
-    // Game attributes
-    GameAttr(Name, Version, GameAttr),
-    GameAttrParam(GameAttrParam),
-    // Game Metadata
-    Locator(Name, Version, Locator),
-    FollowCameraData(FollowCameraData),
-    // SHAR specific locators (no idea what WB stands for)
-    WBLocator(Name, WBLocator),
-    WBTriggerVolume(Name, WBTriggerVolume),
-    WBMatrix(WBMatrix),
-    WBSpline(Name, WBSpline),
-    WBRail(Name, WBRail),
-    // File Metadata
-    ExportInfo(Name),
-    ExportInfoNamedString(Name, ExportInfoNamedString),
-    ExportInfoNamedInt(Name, ExportInfoNamedInt),
-    History(History),
-    // Other P3D chunks
-    Camera(Name, Version, Camera),
+/// Every [`ChunkData`] variant that holds data, as
+/// `Variant(field: Type, ..) = [ChunkType, ..] => visitor_hook`, passed on to `$callback`.
+///
+/// This is the only place a chunk type has to be added. The enum, the built-in parsers, which read
+/// each field in order with [`Parse::parse_field`](crate::chunk::data::parse_trait::Parse::parse_field),
+/// [`ChunkData::write`], the name and version accessors and
+/// [`ChunkVisitor`](crate::chunk::visitor::ChunkVisitor) are all generated from it.
+/// Fields called `name` and `version` are what [`ChunkData::get_name`] and
+/// [`ChunkData::get_version`] return.
+macro_rules! chunk_data_variants {
+    ($callback:ident) => {
+        $callback! {
+            // Rendering
+            Shader(name: Name, version: Version, data: Shader) = [Shader] => visit_shader,
+            ShaderParam(data: ShaderParam) = [
+                ShaderTextureParam,
+                ShaderIntParam,
+                ShaderFloatParam,
+                ShaderColourParam,
+                ShaderVectorParam,
+                ShaderMatrixParam,
+                P3DV14ShaderTextureParam,
+                P3DV14ShaderIntParam,
+                P3DV14ShaderFloatParam,
+                P3DV14ShaderColourParam,
+                P3DV14ShaderVectorParam,
+                P3DV14ShaderMatrixParam,
+            ] => visit_shader_param,
+            Texture(name: Name, version: Version, data: Texture) = [Texture] => visit_texture,
+            Image(name: Name, version: Version, data: Image) = [Image] => visit_image,
+            ImageRaw(data: ImageRaw) = [ImageData] => visit_image_raw,
+            VertexShader(data: VertexShader) = [VertexShader] => visit_vertex_shader,
+            // Old Particle System
+            OldParticleSystem(version: Version, name: Name, data: OldParticleSystem) = [OldParticleSystem] => visit_old_particle_system,
+            OldParticleSystemFactory(version: Version, name: Name, data: OldParticleSystemFactory) = [OldParticleSystemFactory] => visit_old_particle_system_factory,
+            OldParticleInstancingInfo(version: Version, data: OldParticleSystemInstancingInfo) = [OldParticleInstancingInfo] => visit_old_particle_instancing_info,
+            OldParticleAnimation(version: Version) = [OldParticleAnimation] => visit_old_particle_animation,
+            OldEmitterAnimation(version: Version) = [OldEmitterAnimation] => visit_old_emitter_animation,
+            OldGeneratorAnimation(version: Version) = [OldGeneratorAnimation] => visit_old_generator_animation,
+            OldBaseEmitter(version: Version, name: Name, data: OldBaseEmitter) = [OldBaseEmitter] => visit_old_base_emitter,
+            OldSpriteEmitter(version: Version, name: Name, data: OldSpriteEmitter) = [OldSpriteEmitter] => visit_old_sprite_emitter,
+            InstanceableParticleSystem(data: InstanceableParticleSystem) = [InstanceableParticleSystem] => visit_instanceable_particle_system,
+            // Animations
+            Animation(version: Version, name: Name, data: Animation) = [Animation] => visit_animation,
+            AnimationSize(version: Version, data: AnimationSize) = [AnimationSize] => visit_animation_size,
+            AnimationGroup(version: Version, name: Name, data: AnimationGroup) = [AnimationGroup] => visit_animation_group,
+            AnimationGroupList(version: Version, data: AnimationGroupList) = [AnimationGroupList] => visit_animation_group_list,
+            Channel(version: Version, data: Channel) = [
+                Float1Channel,
+                Float2Channel,
+                IntChannel,
+                Vector1DOFChannel,
+                Vector2DOFChannel,
+                Vector3DOFChannel,
+                QuaternionChannel,
+                CompressedQuaternionChannel,
+                ColourChannel,
+                BoolChannel,
+                EntityChannel,
+            ] => visit_channel,
+            ChannelInterpolation(version: Version, data: ChannelInterpolation) = [ChannelInterpolationMode] => visit_channel_interpolation,
+            OldFrameController(version: Version, name: Name, data: OldFrameController) = [OldFrameController] => visit_old_frame_controller,
+            MultiController(name: Name, version: Version, data: MultiController) = [P3DMultiController] => visit_multi_controller,
+            MultiControllerTracks(data: MultiControllerTracks) = [P3DMultiControllerTracks] => visit_multi_controller_tracks,
+            // Old Billboards
+            OldBillboardQuad(version: Version, name: Name, data: OldBillboardQuad) = [OldBillboardQuad] => visit_old_billboard_quad,
+            OldBillboardQuadGroup(version: Version, name: Name, data: OldBillboardQuadGroup) = [OldBillboardQuadGroup] => visit_old_billboard_quad_group,
+            OldBillboardDisplayInfo(version: Version, data: OldBillboardDisplayInfo) = [OldBillboardDisplayInfo] => visit_old_billboard_display_info,
+            OldBillboardPerspectiveInfo(version: Version, data: OldBillboardPerspectiveInfo) = [OldBillboardPerspectiveInfo] => visit_old_billboard_perspective_info,
+            // Breakable Objects
+            BreakableObject(data: BreakableObject) = [BreakableObject] => visit_breakable_object,
+            // Skinning
+            Skeleton(name: Name, version: Version, data: Skeleton) = [P3DSkeleton] => visit_skeleton,
+            SkeletonJoint(name: Name, data: SkeletonJoint) = [P3DSkeletonJoint] => visit_skeleton_joint,
+            SkeletonJointMirrorMap(data: SkeletonJointMirrorMap) = [P3DSkeletonJointMirrorMap] => visit_skeleton_joint_mirror_map,
+            SkeletonJointBonePreserve(data: SkeletonJointBonePreserve) = [P3DSkeletonJointBonePreserve] => visit_skeleton_joint_bone_preserve,
+            Skin(name: Name, version: Version, data: Skin) = [Skin] => visit_skin,
+            MatrixList(data: MatrixList) = [MatrixList] => visit_matrix_list,
+            MatrixPalette(data: MatrixPalette) = [MatrixPalette] => visit_matrix_palette,
+            WeightList(data: WeightList) = [WeightList] => visit_weight_list,
+            // Meshes
+            Mesh(name: Name, version: Version, data: Mesh) = [Mesh] => visit_mesh,
+            PrimGroup(version: Version, data: OldPrimGroup) = [OldPrimGroup] => visit_prim_group,
+            PositionList(data: PositionList) = [PositionList] => visit_position_list,
+            NormalList(data: NormalList) = [NormalList] => visit_normal_list,
+            PackedNormalList(data: PackedNormalList) = [PackedNormalList] => visit_packed_normal_list,
+            TangentList(data: TangentList) = [TangentList] => visit_tangent_list,
+            BinormalList(data: BinormalList) = [BinormalList] => visit_binormal_list,
+            UVList(data: UVList) = [UVList] => visit_uv_list,
+            ColourList(data: ColourList) = [ColourList] => visit_colour_list,
+            IndexList(data: IndexList) = [IndexList] => visit_index_list,
+            // Composite Drawables (multiple meshes/skins in one)
+            RenderStatus(data: RenderStatus) = [RenderStatus] => visit_render_status,
+            CompositeDrawable(name: Name, data: CompositeDrawable) = [P3DCompositeDrawable] => visit_composite_drawable,
+            CompositeDrawableEffect(name: Name, data: CompositeDrawableEffect) = [P3DCompositeDrawableEffect] => visit_composite_drawable_effect,
+            CompositeDrawableEffectList(data: CompositeDrawableEffectList) = [P3DCompositeDrawableEffectList] => visit_composite_drawable_effect_list,
+            CompositeDrawableProp(name: Name, data: CompositeDrawableProp) = [P3DCompositeDrawableProp] => visit_composite_drawable_prop,
+            CompositeDrawablePropList(data: CompositeDrawablePropList) = [P3DCompositeDrawablePropList] => visit_composite_drawable_prop_list,
+            CompositeDrawableSkin(name: Name, data: CompositeDrawableSkin) = [P3DCompositeDrawableSkin] => visit_composite_drawable_skin,
+            CompositeDrawableSkinList(data: CompositeDrawableSkinList) = [P3DCompositeDrawableSkinList] => visit_composite_drawable_skin_list,
+            CompositeDrawableSortOrder(data: CompositeDrawableSortOrder) = [P3DCompositeDrawableSortOrder] => visit_composite_drawable_sort_order,
+            AnimatedObjectFactory(version: Version, name: Name, data: AnimatedObjectFactory) = [AnimatedObjectFactory] => visit_animated_object_factory,
+            AnimatedObject(version: Version, name: Name, data: AnimatedObject) = [AnimatedObject] => visit_animated_object,
+            AnimatedObjectAnimation(version: Version, name: Name, data: AnimatedObjectAnimation) = [AnimatedObjectAnimation] => visit_animated_object_animation,
+            ObjectDSG(name: Name, version: Version, data: ObjectDSG) = [
+                EntityDSG,
+                InstanceableAnimatedDynamicPhysicsDSG,
+                DynamicPhysicsDSG,
+                InstanceableStaticPhysicsDSG,
+            ] => visit_object_dsg,
+            AnimatedObjectDSGWrapper(name: Name, data: AnimatedObjectDSGWrapper) = [AnimatedObjectDSGWrapper] => visit_animated_object_dsg_wrapper,
+            // Physics
+            BoundingBox(data: BoundingBox) = [BBox] => visit_bounding_box,
+            BoundingSphere(data: BoundingSphere) = [BSphere] => visit_bounding_sphere,
+            PhysicsObject(name: Name, version: Version, data: PhysicsObject) = [PhysicsObject] => visit_physics_object,
+            PhysicsJoint(data: PhysicsJoint) = [PhysicsJoint] => visit_physics_joint,
+            PhysicsVector(data: PhysicsVector) = [PhysicsVector] => visit_physics_vector,
+            PhysicsInertiaMatrix(data: PhysicsInertiaMatrix) = [PhysicsInertiaMatrix] => visit_physics_inertia_matrix,
+            // Collision
+            CollisionObject(name: Name, version: Version, data: CollisionObject) = [CollisionObject] => visit_collision_object,
+            CollisionVolume(data: CollisionVolume) = [CollisionVolume] => visit_collision_volume,
+            CollisionVolumeOwner(data: CollisionVolumeOwner) = [CollisionVolumeOwner] => visit_collision_volume_owner,
+            CollisionVolumeOwnerName(name: Name) = [CollisionVolumeOwnerName] => visit_collision_volume_owner_name,
+            CollisionBoundingBox(data: CollisionBoundingBox) = [CollisionBoundingBox] => visit_collision_bounding_box,
+            CollisionOblongBox(data: CollisionOblongBox) = [CollisionOblongBox] => visit_collision_oblong_box,
+            CollisionCylinder(data: CollisionCylinder) = [CollisionCylinder] => visit_collision_cylinder,
+            CollisionSphere(data: CollisionSphere) = [CollisionSphere] => visit_collision_sphere,
+            CollisionVector(data: CollisionVector) = [CollisionVector] => visit_collision_vector,
+            CollisionObjectAttribute(data: CollisionObjectAttribute) = [CollisionObjectAttribute] => visit_collision_object_attribute,
+            IntersectDSG(data: IntersectDSG) = [IntersectDSG] => visit_intersect_dsg,
+            TerrainTypeList(version: Version, data: TerrainTypeList) = [TerrainTypeList] => visit_terrain_type_list,
+            StaticPhysicsDSG(name: Name, version: Version) = [StaticPhysicsDSG] => visit_static_physics_dsg,
+            // Prop Data
+            StatePropDataV1(version: Version, name: Name, data: StatePropDataV1) = [StatePropDataV1] => visit_state_prop_data_v1,
+            StatePropStateDataV1(name: Name, data: StatePropStateDataV1) = [StatePropStateDataV1] => visit_state_prop_state_data_v1,
+            StatePropVisibilitiesData(name: Name, data: StatePropVisibilitiesData) = [StatePropVisibilitiesData] => visit_state_prop_visibilities_data,
+            StatePropFrameControllerData(name: Name, data: StatePropFrameControllerData) = [StatePropFrameControllerData] => visit_state_prop_frame_controller_data,
+            StatePropEventData(name: Name, data: StatePropEventData) = [StatePropEventData] => visit_state_prop_event_data,
+            StatePropCallbackData(name: Name, data: StatePropCallbackData) = [StatePropCallbackData] => visit_state_prop_callback_data,
+            PropInstanceList(name: Name) = [PropInstanceList] => visit_prop_instance_list,
+            ObjectAttributes(data: ObjectAttributes) = [ObjectAttributes] => visit_object_attributes,
+            // Scenegraph
+            Scenegraph(name: Name, version: Version) = [Scenegraph] => visit_scenegraph,
+            ScenegraphBranch(name: Name, data: ScenegraphBranch) = [OldScenegraphBranch] => visit_scenegraph_branch,
+            ScenegraphTransform(name: Name, data: ScenegraphTransform) = [OldScenegraphTransform] => visit_scenegraph_transform,
+            ScenegraphVisibility(name: Name, data: ScenegraphVisibility) = [OldScenegraphVisibility] => visit_scenegraph_visibility,
+            ScenegraphAttachment(name: Name, data: ScenegraphAttachment) = [OldScenegraphAttachment] => visit_scenegraph_attachment,
+            ScenegraphAttachmentPoint(data: ScenegraphAttachmentPoint) = [OldScenegraphAttachmentPoint] => visit_scenegraph_attachment_point,
+            ScenegraphDrawable(name: Name, data: ScenegraphDrawable) = [OldScenegraphDrawable] => visit_scenegraph_drawable,
+            ScenegraphCamera(name: Name, data: ScenegraphCamera) = [OldScenegraphCamera] => visit_scenegraph_camera,
+            ScenegraphLightGroup(name: Name, data: ScenegraphLightGroup) = [OldScenegraphLightGroup] => visit_scenegraph_light_group,
+            ScenegraphSortOrder(data: ScenegraphSortOrder) = [OldScenegraphSortOrder] => visit_scenegraph_sort_order,
+            // Lights
+            Light(name: Name, version: Version, data: Light) = [Light] => visit_light,
+            LightDirection(data: LightDirection) = [LightDirection] => visit_light_direction,
+            LightPosition(data: LightPosition) = [LightPosition] => visit_light_position,
+            LightConeParam(data: LightConeParam) = [LightConeParam] => visit_light_cone_param,
+            LightShadow(data: LightShadow) = [LightShadow] => visit_light_shadow,
+            LightDecayRange(data: LightDecayRange) = [LightDecayRange] => visit_light_decay_range,
+            LightDecayRangeRotationY(data: LightDecayRangeRotationY) = [LightDecayRangeRotationY] => visit_light_decay_range_rotation_y,
+            LightIlluminationType(data: LightIlluminationType) = [LightIlluminationType] => visit_light_illumination_type,
+            LightGroup(name: Name, data: LightGroup) = [P3DLightGroup] => visit_light_group,
+            // Game attributes
+            GameAttr(name: Name, version: Version, data: GameAttr) = [GameAttr] => visit_game_attr,
+            GameAttrParam(data: GameAttrParam) = [
+                GameAttrIntParam,
+                GameAttrFloatParam,
+                GameAttrColourParam,
+                GameAttrVectorParam,
+                GameAttrMatrixParam,
+            ] => visit_game_attr_param,
+            // Game Metadata
+            Locator(name: Name, version: Version, data: Locator) = [Locator] => visit_locator,
+            FollowCameraData(data: FollowCameraData) = [FollowCameraData] => visit_follow_camera_data,
+            // SHAR specific locators (no idea what WB stands for)
+            WBLocator(name: Name, data: WBLocator) = [WBLocator] => visit_wb_locator,
+            WBTriggerVolume(name: Name, data: WBTriggerVolume) = [WBTriggerVolume] => visit_wb_trigger_volume,
+            WBMatrix(data: WBMatrix) = [WBMatrix] => visit_wb_matrix,
+            WBSpline(name: Name, data: WBSpline) = [WBSpline] => visit_wb_spline,
+            WBRail(name: Name, data: WBRail) = [WBRail] => visit_wb_rail,
+            // Frontend (menus and HUD)
+            FrontendProject(name: Name, version: Version, data: FrontendProject) = [FrontendProject] => visit_frontend_project,
+            FrontendScreen(name: Name, version: Version, data: FrontendScreen) = [FrontendScreen] => visit_frontend_screen,
+            FrontendPage(name: Name, version: Version, data: FrontendPage) = [FrontendPage] => visit_frontend_page,
+            FrontendLayer(name: Name, version: Version, data: FrontendLayer) = [FrontendLayer] => visit_frontend_layer,
+            FrontendGroup(name: Name, version: Version, data: FrontendGroup) = [FrontendGroup] => visit_frontend_group,
+            FrontendMovie(name: Name, version: Version, data: FrontendMovie) = [FrontendMovie] => visit_frontend_movie,
+            FrontendMultiSprite(name: Name, version: Version, data: FrontendMultiSprite) = [
+                FrontendMultiSprite,
+                FrontendSprite,
+            ] => visit_frontend_multi_sprite,
+            FrontendMultiText(name: Name, version: Version, data: FrontendMultiText) = [FrontendMultiText] => visit_frontend_multi_text,
+            FrontendPure3DObject(name: Name, version: Version, data: FrontendPure3DObject) = [FrontendPure3DObject] => visit_frontend_pure3d_object,
+            FrontendPolygon(name: Name, version: Version, data: FrontendPolygon) = [FrontendPolygon] => visit_frontend_polygon,
+            FrontendStringTextBible(data: FrontendStringTextBible) = [FrontendStringTextBible] => visit_frontend_string_text_bible,
+            FrontendStringHardCoded(data: FrontendStringHardCoded) = [FrontendStringHardCoded] => visit_frontend_string_hard_coded,
+            FrontendTextBible(name: Name, data: FrontendTextBible) = [FrontendTextBible] => visit_frontend_text_bible,
+            FrontendLanguage(name: Name, data: FrontendLanguage) = [FrontendLanguage] => visit_frontend_language,
+            FrontendImageResource(name: Name, version: Version, data: FrontendImageResource) = [FrontendImageResource] => visit_frontend_image_resource,
+            FrontendResource(name: Name, version: Version, data: FrontendResource) = [
+                FrontendPure3DResource,
+                FrontendTextStyleResource,
+                FrontendTextBibleResource,
+                FrontendOldResourceTextStyle,
+                FrontendOldResourceTextBible,
+            ] => visit_frontend_resource,
+            // File Metadata
+            ExportInfo(name: Name) = [P3DExportInfo] => visit_export_info,
+            ExportInfoNamedString(name: Name, data: ExportInfoNamedString) = [P3DExportInfoNamedString] => visit_export_info_named_string,
+            ExportInfoNamedInt(name: Name, data: ExportInfoNamedInt) = [P3DExportInfoNamedInt] => visit_export_info_named_int,
+            History(data: History) = [P3DHistory] => visit_history,
+            // Other P3D chunks
+            Camera(name: Name, version: Version, data: Camera) = [P3DCamera] => visit_camera,
+        }
+    };
 }
+
+pub(crate) use chunk_data_variants;
+
+macro_rules! chunk_data_enum {
+    ($($variant:ident($($field:ident: $typ:ty),*) = [$($chunk:ident),* $(,)?] => $method:ident,)*) => {
+        #[derive(Clone, PartialEq, Debug, Deserialize, Serialize)]
+        pub enum ChunkData {
+            // Others
+            None,
+            Unknown,
+            $($variant($($typ),*),)*
+            // Registered by downstream crates, see crate::chunk::registry
+            Custom(CustomData),
+        }
+    };
+}
+
+chunk_data_variants!(chunk_data_enum);
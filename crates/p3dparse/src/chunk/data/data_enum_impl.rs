@@ -1,7 +1,7 @@
 use crate::{
     chunk::{
         data::{
-            data_enum::ChunkData,
+            data_enum::{chunk_data_variants, ChunkData},
             kinds::{
                 animation::{Animation, AnimationGroup, AnimationGroupList, AnimationSize},
                 channel::{Channel, ChannelInterpolation},
@@ -12,9 +12,20 @@ use crate::{
                 },
                 explosion::BreakableObject,
                 file_metadata::{ExportInfoNamedInt, ExportInfoNamedString, History},
+                frontend::{
+                    FrontendGroup, FrontendImageResource, FrontendLanguage, FrontendLayer,
+                    FrontendMovie, FrontendMultiSprite, FrontendMultiText, FrontendPage,
+                    FrontendPolygon, FrontendProject, FrontendPure3DObject, FrontendResource,
+                    FrontendScreen, FrontendStringHardCoded, FrontendStringTextBible,
+                    FrontendTextBible,
+                },
                 game_metadata::{FollowCameraData, Locator},
                 gameattr::{GameAttr, GameAttrParam},
                 image::{Image, ImageRaw},
+                light::{
+                    Light, LightConeParam, LightDecayRange, LightDecayRangeRotationY,
+                    LightDirection, LightGroup, LightIlluminationType, LightPosition, LightShadow,
+                },
                 locator::{WBLocator, WBMatrix, WBRail, WBSpline, WBTriggerVolume},
                 mesh::{
                     BinormalList, ColourList, CompositeDrawable, CompositeDrawableEffect,
@@ -61,7 +72,9 @@ use crate::{
                 version::Version,
             },
             parse_trait::Parse,
+            write_trait::Write,
         },
+        registry::{self, ParserRegistry},
         type_identifiers::ChunkType,
     },
     Result,
@@ -69,472 +82,101 @@ use crate::{
 use bytes::Bytes;
 
 impl ChunkData {
+    /// Parses the payload of a chunk of type `typ` with whichever parser is registered for it,
+    /// see [`registry`](crate::chunk::registry). Types without a parser produce [`ChunkData::Unknown`],
+    /// [`Chunk::parse`](crate::chunk::Chunk::parse) reports these as diagnostics.
     pub fn from_chunk_type(typ: ChunkType, bytes: &mut Bytes) -> Result<ChunkData> {
-        match typ {
-            ChunkType::DataFile => Ok(ChunkData::None),
-            // Rendering
-            ChunkType::Shader => Ok(ChunkData::Shader(
-                Name::parse(bytes, typ)?,
-                Version::parse(bytes, typ)?,
-                Shader::parse(bytes, typ)?,
-            )),
-            ChunkType::ShaderTextureParam
-            | ChunkType::ShaderIntParam
-            | ChunkType::ShaderFloatParam
-            | ChunkType::ShaderColourParam => {
-                Ok(ChunkData::ShaderParam(ShaderParam::parse(bytes, typ)?))
-            }
-            ChunkType::Texture => Ok(ChunkData::Texture(
-                Name::parse(bytes, typ)?,
-                Version::parse(bytes, typ)?,
-                Texture::parse(bytes, typ)?,
-            )),
-            ChunkType::Image => Ok(ChunkData::Image(
-                Name::parse(bytes, typ)?,
-                Version::parse(bytes, typ)?,
-                Image::parse(bytes, typ)?,
-            )),
-            ChunkType::ImageData => Ok(ChunkData::ImageRaw(ImageRaw::parse(bytes, typ)?)),
-            ChunkType::VertexShader => {
-                Ok(ChunkData::VertexShader(VertexShader::parse(bytes, typ)?))
-            }
-            // Old Particle System
-            ChunkType::OldParticleSystem => Ok(ChunkData::OldParticleSystem(
-                Version::parse(bytes, typ)?,
-                Name::parse(bytes, typ)?,
-                OldParticleSystem::parse(bytes, typ)?,
-            )),
-            ChunkType::OldParticleSystemFactory => Ok(ChunkData::OldParticleSystemFactory(
-                Version::parse(bytes, typ)?,
-                Name::parse(bytes, typ)?,
-                OldParticleSystemFactory::parse(bytes, typ)?,
-            )),
-            ChunkType::OldParticleInstancingInfo => Ok(ChunkData::OldParticleInstancingInfo(
-                Version::parse(bytes, typ)?,
-                OldParticleSystemInstancingInfo::parse(bytes, typ)?,
-            )),
-            ChunkType::OldParticleAnimation => {
-                Ok(ChunkData::OldParticleAnimation(Version::parse(bytes, typ)?))
-            }
-            ChunkType::OldEmitterAnimation => {
-                Ok(ChunkData::OldEmitterAnimation(Version::parse(bytes, typ)?))
-            }
-            ChunkType::OldGeneratorAnimation => Ok(ChunkData::OldGeneratorAnimation(
-                Version::parse(bytes, typ)?,
-            )),
-            ChunkType::OldBaseEmitter => Ok(ChunkData::OldBaseEmitter(
-                Version::parse(bytes, typ)?,
-                Name::parse(bytes, typ)?,
-                OldBaseEmitter::parse(bytes, typ)?,
-            )),
-            ChunkType::OldSpriteEmitter => Ok(ChunkData::OldSpriteEmitter(
-                Version::parse(bytes, typ)?,
-                Name::parse(bytes, typ)?,
-                OldSpriteEmitter::parse(bytes, typ)?,
-            )),
-            ChunkType::InstanceableParticleSystem => Ok(ChunkData::InstanceableParticleSystem(
-                InstanceableParticleSystem::parse(bytes, typ)?,
-            )),
-            // Animations
-            ChunkType::Animation => Ok(ChunkData::Animation(
-                Version::parse(bytes, typ)?,
-                Name::parse(bytes, typ)?,
-                Animation::parse(bytes, typ)?,
-            )),
-            ChunkType::AnimationSize => Ok(ChunkData::AnimationSize(
-                Version::parse(bytes, typ)?,
-                AnimationSize::parse(bytes, typ)?,
-            )),
-            ChunkType::AnimationGroup => Ok(ChunkData::AnimationGroup(
-                Version::parse(bytes, typ)?,
-                Name::parse(bytes, typ)?,
-                AnimationGroup::parse(bytes, typ)?,
-            )),
-            ChunkType::AnimationGroupList => Ok(ChunkData::AnimationGroupList(
-                Version::parse(bytes, typ)?,
-                AnimationGroupList::parse(bytes, typ)?,
-            )),
-            ChunkType::Float1Channel
-            | ChunkType::Float2Channel
-            | ChunkType::IntChannel
-            | ChunkType::Vector1DOFChannel
-            | ChunkType::Vector2DOFChannel
-            | ChunkType::Vector3DOFChannel
-            | ChunkType::QuaternionChannel
-            | ChunkType::CompressedQuaternionChannel
-            | ChunkType::ColourChannel
-            | ChunkType::BoolChannel
-            | ChunkType::EntityChannel => Ok(ChunkData::Channel(
-                Version::parse(bytes, typ)?,
-                Channel::parse(bytes, typ)?,
-            )),
-            ChunkType::ChannelInterpolationMode => Ok(ChunkData::ChannelInterpolation(
-                Version::parse(bytes, typ)?,
-                ChannelInterpolation::parse(bytes, typ)?,
-            )),
-            ChunkType::OldFrameController => Ok(ChunkData::OldFrameController(
-                Version::parse(bytes, typ)?,
-                Name::parse(bytes, typ)?,
-                OldFrameController::parse(bytes, typ)?,
-            )),
-            ChunkType::P3DMultiController => Ok(ChunkData::MultiController(
-                Name::parse(bytes, typ)?,
-                Version::parse(bytes, typ)?,
-                MultiController::parse(bytes, typ)?,
-            )),
-            ChunkType::P3DMultiControllerTracks => Ok(ChunkData::MultiControllerTracks(
-                MultiControllerTracks::parse(bytes, typ)?,
-            )),
-            // Old Billboards
-            ChunkType::OldBillboardQuad => Ok(ChunkData::OldBillboardQuad(
-                Version::parse(bytes, typ)?,
-                Name::parse(bytes, typ)?,
-                OldBillboardQuad::parse(bytes, typ)?,
-            )),
-            ChunkType::OldBillboardQuadGroup => Ok(ChunkData::OldBillboardQuadGroup(
-                Version::parse(bytes, typ)?,
-                Name::parse(bytes, typ)?,
-                OldBillboardQuadGroup::parse(bytes, typ)?,
-            )),
-            ChunkType::OldBillboardDisplayInfo => Ok(ChunkData::OldBillboardDisplayInfo(
-                Version::parse(bytes, typ)?,
-                OldBillboardDisplayInfo::parse(bytes, typ)?,
-            )),
-            ChunkType::OldBillboardPerspectiveInfo => Ok(ChunkData::OldBillboardPerspectiveInfo(
-                Version::parse(bytes, typ)?,
-                OldBillboardPerspectiveInfo::parse(bytes, typ)?,
-            )),
-            // Breakable Objects
-            ChunkType::BreakableObject => Ok(ChunkData::BreakableObject(BreakableObject::parse(
-                bytes, typ,
-            )?)),
-            // Skinning
-            ChunkType::P3DSkeleton => Ok(ChunkData::Skeleton(
-                Name::parse(bytes, typ)?,
-                Version::parse(bytes, typ)?,
-                Skeleton::parse(bytes, typ)?,
-            )),
-            ChunkType::P3DSkeletonJoint => Ok(ChunkData::SkeletonJoint(
-                Name::parse(bytes, typ)?,
-                SkeletonJoint::parse(bytes, typ)?,
-            )),
-            ChunkType::P3DSkeletonJointMirrorMap => Ok(ChunkData::SkeletonJointMirrorMap(
-                SkeletonJointMirrorMap::parse(bytes, typ)?,
-            )),
-            ChunkType::P3DSkeletonJointBonePreserve => Ok(ChunkData::SkeletonJointBonePreserve(
-                SkeletonJointBonePreserve::parse(bytes, typ)?,
-            )),
-            ChunkType::MatrixList => Ok(ChunkData::MatrixList(MatrixList::parse(bytes, typ)?)),
-            ChunkType::MatrixPalette => {
-                Ok(ChunkData::MatrixPalette(MatrixPalette::parse(bytes, typ)?))
-            }
-            ChunkType::WeightList => Ok(ChunkData::WeightList(WeightList::parse(bytes, typ)?)),
-            // Meshes
-            ChunkType::Mesh => Ok(ChunkData::Mesh(
-                Name::parse(bytes, typ)?,
-                Version::parse(bytes, typ)?,
-                Mesh::parse(bytes, typ)?,
-            )),
-            ChunkType::Skin => Ok(ChunkData::Skin(
-                Name::parse(bytes, typ)?,
-                Version::parse(bytes, typ)?,
-                Skin::parse(bytes, typ)?,
-            )),
-            ChunkType::OldPrimGroup => Ok(ChunkData::PrimGroup(
-                Version::parse(bytes, typ)?,
-                OldPrimGroup::parse(bytes, typ)?,
-            )),
-            ChunkType::PositionList => {
-                Ok(ChunkData::PositionList(PositionList::parse(bytes, typ)?))
-            }
-            ChunkType::NormalList => Ok(ChunkData::NormalList(NormalList::parse(bytes, typ)?)),
-            ChunkType::TangentList => Ok(ChunkData::TangentList(TangentList::parse(bytes, typ)?)),
-            ChunkType::BinormalList => {
-                Ok(ChunkData::BinormalList(BinormalList::parse(bytes, typ)?))
-            }
-            ChunkType::PackedNormalList => Ok(ChunkData::PackedNormalList(
-                PackedNormalList::parse(bytes, typ)?,
-            )),
-            ChunkType::UVList => Ok(ChunkData::UVList(UVList::parse(bytes, typ)?)),
-            ChunkType::ColourList => Ok(ChunkData::ColourList(ColourList::parse(bytes, typ)?)),
-            ChunkType::IndexList => Ok(ChunkData::IndexList(IndexList::parse(bytes, typ)?)),
-            // Composite Drawables (multiple meshes/skins in one)
-            ChunkType::RenderStatus => {
-                Ok(ChunkData::RenderStatus(RenderStatus::parse(bytes, typ)?))
-            }
-            ChunkType::P3DCompositeDrawable => Ok(ChunkData::CompositeDrawable(
-                Name::parse(bytes, typ)?,
-                CompositeDrawable::parse(bytes, typ)?,
-            )),
-            ChunkType::P3DCompositeDrawableEffect => Ok(ChunkData::CompositeDrawableEffect(
-                Name::parse(bytes, typ)?,
-                CompositeDrawableEffect::parse(bytes, typ)?,
-            )),
-            ChunkType::P3DCompositeDrawableEffectList => {
-                Ok(ChunkData::CompositeDrawableEffectList(
-                    CompositeDrawableEffectList::parse(bytes, typ)?,
-                ))
-            }
-            ChunkType::P3DCompositeDrawableProp => Ok(ChunkData::CompositeDrawableProp(
-                Name::parse(bytes, typ)?,
-                CompositeDrawableProp::parse(bytes, typ)?,
-            )),
-            ChunkType::P3DCompositeDrawablePropList => Ok(ChunkData::CompositeDrawablePropList(
-                CompositeDrawablePropList::parse(bytes, typ)?,
-            )),
-            ChunkType::P3DCompositeDrawableSkin => Ok(ChunkData::CompositeDrawableSkin(
-                Name::parse(bytes, typ)?,
-                CompositeDrawableSkin::parse(bytes, typ)?,
-            )),
-            ChunkType::P3DCompositeDrawableSkinList => Ok(ChunkData::CompositeDrawableSkinList(
-                CompositeDrawableSkinList::parse(bytes, typ)?,
-            )),
-            ChunkType::P3DCompositeDrawableSortOrder => Ok(ChunkData::CompositeDrawableSortOrder(
-                CompositeDrawableSortOrder::parse(bytes, typ)?,
-            )),
-            ChunkType::AnimatedObjectFactory => Ok(ChunkData::AnimatedObjectFactory(
-                Version::parse(bytes, typ)?,
-                Name::parse(bytes, typ)?,
-                AnimatedObjectFactory::parse(bytes, typ)?,
-            )),
-            ChunkType::AnimatedObject => Ok(ChunkData::AnimatedObject(
-                Version::parse(bytes, typ)?,
-                Name::parse(bytes, typ)?,
-                AnimatedObject::parse(bytes, typ)?,
-            )),
-            ChunkType::AnimatedObjectAnimation => Ok(ChunkData::AnimatedObjectAnimation(
-                Version::parse(bytes, typ)?,
-                Name::parse(bytes, typ)?,
-                AnimatedObjectAnimation::parse(bytes, typ)?,
-            )),
-            ChunkType::EntityDSG
-            | ChunkType::InstanceableAnimatedDynamicPhysicsDSG
-            | ChunkType::DynamicPhysicsDSG
-            | ChunkType::InstanceableStaticPhysicsDSG => Ok(ChunkData::ObjectDSG(
-                Name::parse(bytes, typ)?,
-                Version::parse(bytes, typ)?,
-                ObjectDSG::parse(bytes, typ)?,
-            )),
-            ChunkType::AnimatedObjectDSGWrapper => Ok(ChunkData::AnimatedObjectDSGWrapper(
-                Name::parse(bytes, typ)?,
-                AnimatedObjectDSGWrapper::parse(bytes, typ)?,
-            )),
-            // Physics
-            ChunkType::BBox => Ok(ChunkData::BoundingBox(BoundingBox::parse(bytes, typ)?)),
-            ChunkType::BSphere => Ok(ChunkData::BoundingSphere(BoundingSphere::parse(
-                bytes, typ,
-            )?)),
-            ChunkType::PhysicsObject => Ok(ChunkData::PhysicsObject(
-                Name::parse(bytes, typ)?,
-                Version::parse(bytes, typ)?,
-                PhysicsObject::parse(bytes, typ)?,
-            )),
-            ChunkType::PhysicsJoint => {
-                Ok(ChunkData::PhysicsJoint(PhysicsJoint::parse(bytes, typ)?))
-            }
-            ChunkType::PhysicsVector => {
-                Ok(ChunkData::PhysicsVector(PhysicsVector::parse(bytes, typ)?))
-            }
-            ChunkType::PhysicsInertiaMatrix => Ok(ChunkData::PhysicsInertiaMatrix(
-                PhysicsInertiaMatrix::parse(bytes, typ)?,
-            )),
-            // Collision
-            ChunkType::CollisionObject => Ok(ChunkData::CollisionObject(
-                Name::parse(bytes, typ)?,
-                Version::parse(bytes, typ)?,
-                CollisionObject::parse(bytes, typ)?,
-            )),
-            ChunkType::CollisionVolume => Ok(ChunkData::CollisionVolume(CollisionVolume::parse(
-                bytes, typ,
-            )?)),
-            ChunkType::CollisionVolumeOwner => Ok(ChunkData::CollisionVolumeOwner(
-                CollisionVolumeOwner::parse(bytes, typ)?,
-            )),
-            ChunkType::CollisionVolumeOwnerName => Ok(ChunkData::CollisionVolumeOwnerName(
-                Name::parse(bytes, typ)?,
-            )),
-            ChunkType::CollisionBoundingBox => Ok(ChunkData::CollisionBoundingBox(
-                CollisionBoundingBox::parse(bytes, typ)?,
-            )),
-            ChunkType::CollisionOblongBox => Ok(ChunkData::CollisionOblongBox(
-                CollisionOblongBox::parse(bytes, typ)?,
-            )),
-            ChunkType::CollisionCylinder => Ok(ChunkData::CollisionCylinder(
-                CollisionCylinder::parse(bytes, typ)?,
-            )),
-            ChunkType::CollisionSphere => Ok(ChunkData::CollisionSphere(CollisionSphere::parse(
-                bytes, typ,
-            )?)),
-            ChunkType::CollisionVector => Ok(ChunkData::CollisionVector(CollisionVector::parse(
-                bytes, typ,
-            )?)),
-            ChunkType::CollisionObjectAttribute => Ok(ChunkData::CollisionObjectAttribute(
-                CollisionObjectAttribute::parse(bytes, typ)?,
-            )),
-            ChunkType::IntersectDSG => {
-                Ok(ChunkData::IntersectDSG(IntersectDSG::parse(bytes, typ)?))
+        match registry::parser(typ) {
+            Some(parser) => parser(bytes, typ),
+            None => Ok(ChunkData::Unknown),
+        }
+    }
+}
+
+/// Finds the binding for the field called `name` or `version`. Each field is passed twice, once
+/// to match against and once to use, so the binding keeps the hygiene of the match it came from.
+macro_rules! field_named {
+    ($target:ident;) => {
+        None
+    };
+    (name; name $found:ident $(, $field:ident $binding:ident)*) => {
+        Some($found)
+    };
+    (version; version $found:ident $(, $field:ident $binding:ident)*) => {
+        Some($found)
+    };
+    ($target:ident; $other:ident $unused:ident $(, $field:ident $binding:ident)*) => {
+        field_named!($target; $($field $binding),*)
+    };
+}
+
+macro_rules! chunk_data_impl {
+    ($($variant:ident($($field:ident: $typ:ty),*) = [$($chunk:ident),* $(,)?] => $method:ident,)*) => {
+        impl ChunkData {
+            /// Serializes this data back into the payload of a chunk of type `typ`.
+            /// This is the inverse of [`ChunkData::from_chunk_type`] and does not include the chunk header.
+            pub fn write(&self, typ: ChunkType, bytes: &mut Vec<u8>) {
+                match self {
+                    ChunkData::None => {}
+                    // The payload of chunks we can't parse is kept in Chunk::unparsed_data instead.
+                    ChunkData::Unknown => {}
+                    ChunkData::Custom(data) => data.write(bytes, typ),
+                    $(ChunkData::$variant($($field),*) => {
+                        $($field.write(bytes, typ);)*
+                    })*
+                }
             }
-            ChunkType::TerrainTypeList => Ok(ChunkData::TerrainTypeList(
-                Version::parse(bytes, typ)?,
-                TerrainTypeList::parse(bytes, typ)?,
-            )),
-            ChunkType::StaticPhysicsDSG => Ok(ChunkData::StaticPhysicsDSG(
-                Name::parse(bytes, typ)?,
-                Version::parse(bytes, typ)?,
-            )),
-            // Prop Data
-            ChunkType::StatePropDataV1 => Ok(ChunkData::StatePropDataV1(
-                Version::parse(bytes, typ)?,
-                Name::parse(bytes, typ)?,
-                StatePropDataV1::parse(bytes, typ)?,
-            )),
-            ChunkType::StatePropStateDataV1 => Ok(ChunkData::StatePropStateDataV1(
-                Name::parse(bytes, typ)?,
-                StatePropStateDataV1::parse(bytes, typ)?,
-            )),
-            ChunkType::StatePropVisibilitiesData => Ok(ChunkData::StatePropVisibilitiesData(
-                Name::parse(bytes, typ)?,
-                StatePropVisibilitiesData::parse(bytes, typ)?,
-            )),
-            ChunkType::StatePropFrameControllerData => Ok(ChunkData::StatePropFrameControllerData(
-                Name::parse(bytes, typ)?,
-                StatePropFrameControllerData::parse(bytes, typ)?,
-            )),
-            ChunkType::StatePropEventData => Ok(ChunkData::StatePropEventData(
-                Name::parse(bytes, typ)?,
-                StatePropEventData::parse(bytes, typ)?,
-            )),
-            ChunkType::StatePropCallbackData => Ok(ChunkData::StatePropCallbackData(
-                Name::parse(bytes, typ)?,
-                StatePropCallbackData::parse(bytes, typ)?,
-            )),
-            ChunkType::PropInstanceList => {
-                Ok(ChunkData::PropInstanceList(Name::parse(bytes, typ)?))
+
+            /// The name of this chunk, for every variant with a `name` field.
+            #[allow(unused_variables)]
+            pub fn get_name(&self) -> Option<Name> {
+                match self {
+                    ChunkData::None | ChunkData::Unknown => None,
+                    ChunkData::Custom(data) => data.get_name(),
+                    $(ChunkData::$variant($($field),*) => {
+                        let name: Option<&Name> = field_named!(name; $($field $field),*);
+                        name.cloned()
+                    })*
+                }
             }
-            ChunkType::ObjectAttributes => Ok(ChunkData::ObjectAttributes(
-                ObjectAttributes::parse(bytes, typ)?,
-            )),
-            // Scenegraph
-            ChunkType::Scenegraph => Ok(ChunkData::Scenegraph(
-                Name::parse(bytes, typ)?,
-                Version::parse(bytes, typ)?,
-            )),
-            ChunkType::OldScenegraphRoot => Ok(ChunkData::None),
-            ChunkType::OldScenegraphBranch => Ok(ChunkData::ScenegraphBranch(
-                Name::parse(bytes, typ)?,
-                ScenegraphBranch::parse(bytes, typ)?,
-            )),
-            ChunkType::OldScenegraphTransform => Ok(ChunkData::ScenegraphTransform(
-                Name::parse(bytes, typ)?,
-                ScenegraphTransform::parse(bytes, typ)?,
-            )),
-            ChunkType::OldScenegraphVisibility => Ok(ChunkData::ScenegraphVisibility(
-                Name::parse(bytes, typ)?,
-                ScenegraphVisibility::parse(bytes, typ)?,
-            )),
-            ChunkType::OldScenegraphAttachment => Ok(ChunkData::ScenegraphAttachment(
-                Name::parse(bytes, typ)?,
-                ScenegraphAttachment::parse(bytes, typ)?,
-            )),
-            ChunkType::OldScenegraphAttachmentPoint => Ok(ChunkData::ScenegraphAttachmentPoint(
-                ScenegraphAttachmentPoint::parse(bytes, typ)?,
-            )),
-            ChunkType::OldScenegraphDrawable => Ok(ChunkData::ScenegraphDrawable(
-                Name::parse(bytes, typ)?,
-                ScenegraphDrawable::parse(bytes, typ)?,
-            )),
-            ChunkType::OldScenegraphCamera => Ok(ChunkData::ScenegraphCamera(
-                Name::parse(bytes, typ)?,
-                ScenegraphCamera::parse(bytes, typ)?,
-            )),
-            ChunkType::OldScenegraphLightGroup => Ok(ChunkData::ScenegraphLightGroup(
-                Name::parse(bytes, typ)?,
-                ScenegraphLightGroup::parse(bytes, typ)?,
-            )),
-            ChunkType::OldScenegraphSortOrder => Ok(ChunkData::ScenegraphSortOrder(
-                ScenegraphSortOrder::parse(bytes, typ)?,
-            )),
-            // Game Attributes
-            ChunkType::GameAttr => Ok(ChunkData::GameAttr(
-                Name::parse(bytes, typ)?,
-                Version::parse(bytes, typ)?,
-                GameAttr::parse(bytes, typ)?,
-            )),
-            ChunkType::GameAttrIntParam
-            | ChunkType::GameAttrFloatParam
-            | ChunkType::GameAttrColourParam
-            | ChunkType::GameAttrVectorParam
-            | ChunkType::GameAttrMatrixParam => {
-                Ok(ChunkData::GameAttrParam(GameAttrParam::parse(bytes, typ)?))
+
+            /// Mutable access to the [`Name`] of a named chunk, see [`ChunkData::get_name`].
+            #[allow(unused_variables)]
+            pub fn get_name_mut(&mut self) -> Option<&mut Name> {
+                match self {
+                    ChunkData::None | ChunkData::Unknown => None,
+                    ChunkData::Custom(data) => data.get_name_mut(),
+                    $(ChunkData::$variant($($field),*) => field_named!(name; $($field $field),*),)*
+                }
             }
-            // Game Metadata
-            ChunkType::Locator => Ok(ChunkData::Locator(
-                Name::parse(bytes, typ)?,
-                Version::parse(bytes, typ)?,
-                Locator::parse(bytes, typ)?,
-            )),
-            ChunkType::FollowCameraData => Ok(ChunkData::FollowCameraData(
-                FollowCameraData::parse(bytes, typ)?,
-            )),
-            // SHAR specific locators (no idea what WB stands for)
-            ChunkType::WBLocator => Ok(ChunkData::WBLocator(
-                Name::parse(bytes, typ)?,
-                WBLocator::parse(bytes, typ)?,
-            )),
-            ChunkType::WBTriggerVolume => Ok(ChunkData::WBTriggerVolume(
-                Name::parse(bytes, typ)?,
-                WBTriggerVolume::parse(bytes, typ)?,
-            )),
-            ChunkType::WBMatrix => Ok(ChunkData::WBMatrix(WBMatrix::parse(bytes, typ)?)),
-            ChunkType::WBSpline => Ok(ChunkData::WBSpline(
-                Name::parse(bytes, typ)?,
-                WBSpline::parse(bytes, typ)?,
-            )),
-            ChunkType::WBRail => Ok(ChunkData::WBRail(
-                Name::parse(bytes, typ)?,
-                WBRail::parse(bytes, typ)?,
-            )),
-            // -- Export Info -- //
-            ChunkType::P3DExportInfo => Ok(ChunkData::ExportInfo(Name::parse(bytes, typ)?)),
-            ChunkType::P3DExportInfoNamedString => Ok(ChunkData::ExportInfoNamedString(
-                Name::parse(bytes, typ)?,
-                ExportInfoNamedString::parse(bytes, typ)?,
-            )),
-            ChunkType::P3DExportInfoNamedInt => Ok(ChunkData::ExportInfoNamedInt(
-                Name::parse(bytes, typ)?,
-                ExportInfoNamedInt::parse(bytes, typ)?,
-            )),
-            ChunkType::P3DHistory => Ok(ChunkData::History(History::parse(bytes, typ)?)),
-            // -- P3D Other -- //
-            ChunkType::P3DCamera => Ok(ChunkData::Camera(
-                Name::parse(bytes, typ)?,
-                Version::parse(bytes, typ)?,
-                Camera::parse(bytes, typ)?,
-            )),
-            // -- Other produces Unknown -- //
-            _typ => {
-                #[cfg(debug_assertions)]
-                eprintln!(
-                    "Warning: ChunkData parsing is not implemented for {:?}",
-                    _typ
-                );
-                Ok(ChunkData::Unknown)
+
+            /// The version of this chunk, for every variant with a `version` field.
+            #[allow(unused_variables)]
+            pub fn get_version(&self) -> Option<Version> {
+                match self {
+                    ChunkData::None | ChunkData::Unknown => None,
+                    ChunkData::Custom(data) => data.get_version(),
+                    $(ChunkData::$variant($($field),*) => {
+                        let version: Option<&Version> = field_named!(version; $($field $field),*);
+                        version.cloned()
+                    })*
+                }
             }
         }
-    }
 
-    /// WARNING: This may produce None for data that is actually named!
-    /// Consequence of being bad at coding
-    pub fn get_name(&self) -> Option<Name> {
-        match self {
-            ChunkData::Texture(name, _, _) => Some(name.clone()),
-            ChunkData::Image(name, _, _) => Some(name.clone()),
-            ChunkData::Shader(name, _, _) => Some(name.clone()),
-            ChunkData::Mesh(name, _, _) => Some(name.clone()),
-            ChunkData::OldBaseEmitter(_, name, _) => Some(name.clone()),
-            ChunkData::OldSpriteEmitter(_, name, _) => Some(name.clone()),
-            ChunkData::OldParticleSystemFactory(_, name, _) => Some(name.clone()),
-            _ => None,
+        /// Every parser that ships with p3dparse. These go through the same registry as downstream parsers,
+        /// so any of them can be replaced with [`registry::register_parser`].
+        pub(crate) fn register_builtins(registry: &mut ParserRegistry) {
+            registry.register(ChunkType::DataFile, |_, _| Ok(ChunkData::None));
+            registry.register(ChunkType::OldScenegraphRoot, |_, _| Ok(ChunkData::None));
+            $({
+                fn parse(bytes: &mut Bytes, typ: ChunkType) -> Result<ChunkData> {
+                    Ok(ChunkData::$variant($(<$typ as Parse>::parse_field(bytes, typ, stringify!($field))?),*))
+                }
+                $(registry.register(ChunkType::$chunk, parse);)*
+            })*
         }
-    }
+    };
 }
+
+chunk_data_variants!(chunk_data_impl);
@@ -1,18 +1,17 @@
-use super::kinds::shared::{Colour, Quaternion, Vector2, Vector3};
-use crate::{bytes_ext::BufResult, Result};
-use bytes::Bytes;
+use super::kinds::{
+    pure3d_string::Pure3DString,
+    shared::{Colour, Quaternion, Vector2, Vector3},
+};
+use crate::{
+    bytes_ext::{BufResult, BytesExt},
+    Result,
+};
+use bytes::{BufMut, Bytes};
 use nalgebra::Transform3;
 
-pub fn pure3d_read_string(bytes: &mut Bytes) -> Result<String> {
+pub fn pure3d_read_string(bytes: &mut Bytes) -> Result<Pure3DString> {
     let count = bytes.safe_get_u8()?;
-    let mut string_bytes = Vec::with_capacity(count as usize);
-    for _ in 0..count {
-        let byte = bytes.safe_get_u8()?;
-        if byte.is_ascii() && byte != 0 {
-            string_bytes.push(byte);
-        }
-    }
-    Ok(String::from_utf8(string_bytes)?)
+    Ok(Pure3DString::from_raw(bytes.safe_split_to(count as usize)?))
 }
 
 pub fn pure3d_read_fourcc(bytes: &mut Bytes) -> Result<String> {
@@ -27,25 +26,25 @@ pub fn pure3d_read_fourcc(bytes: &mut Bytes) -> Result<String> {
 }
 
 pub fn read_vec2(bytes: &mut Bytes) -> Result<Vector2> {
-    let data = [bytes.safe_get_f32_le()?, bytes.safe_get_f32_le()?];
+    let data = [bytes.safe_get_f32()?, bytes.safe_get_f32()?];
     Ok(data.into())
 }
 
 pub fn read_vec3(bytes: &mut Bytes) -> Result<Vector3> {
     let data = [
-        bytes.safe_get_f32_le()?,
-        bytes.safe_get_f32_le()?,
-        bytes.safe_get_f32_le()?,
+        bytes.safe_get_f32()?,
+        bytes.safe_get_f32()?,
+        bytes.safe_get_f32()?,
     ];
     Ok(data.into())
 }
 
 pub fn read_quaternion(bytes: &mut Bytes) -> Result<Quaternion> {
     let data = [
-        bytes.safe_get_f32_le()?,
-        bytes.safe_get_f32_le()?,
-        bytes.safe_get_f32_le()?,
-        bytes.safe_get_f32_le()?,
+        bytes.safe_get_f32()?,
+        bytes.safe_get_f32()?,
+        bytes.safe_get_f32()?,
+        bytes.safe_get_f32()?,
     ];
 
     Ok(data.into())
@@ -55,30 +54,18 @@ const QUATERNION_INVERSE_COMPRESSION_FACTOR: f32 = 1.0 / 32767.0;
 
 pub fn read_compressed_quaternion(bytes: &mut Bytes) -> Result<Quaternion> {
     let data = [
-        (bytes.safe_get_u16_le()? as f32) * QUATERNION_INVERSE_COMPRESSION_FACTOR,
-        (bytes.safe_get_u16_le()? as f32) * QUATERNION_INVERSE_COMPRESSION_FACTOR,
-        (bytes.safe_get_u16_le()? as f32) * QUATERNION_INVERSE_COMPRESSION_FACTOR,
-        (bytes.safe_get_u16_le()? as f32) * QUATERNION_INVERSE_COMPRESSION_FACTOR,
+        (bytes.safe_get_u16()? as f32) * QUATERNION_INVERSE_COMPRESSION_FACTOR,
+        (bytes.safe_get_u16()? as f32) * QUATERNION_INVERSE_COMPRESSION_FACTOR,
+        (bytes.safe_get_u16()? as f32) * QUATERNION_INVERSE_COMPRESSION_FACTOR,
+        (bytes.safe_get_u16()? as f32) * QUATERNION_INVERSE_COMPRESSION_FACTOR,
     ];
 
     Ok(data.into())
 }
 
 pub fn read_colour(bytes: &mut Bytes) -> Result<Colour> {
-    let mut slice = [
-        // B
-        bytes.safe_get_u8()?,
-        // G
-        bytes.safe_get_u8()?,
-        // R
-        bytes.safe_get_u8()?,
-        // A
-        bytes.safe_get_u8()?,
-    ];
-
-    slice.reverse();
-
-    Ok(slice.into())
+    // Stored as a 0xAARRGGBB u32, so BGRA on disk in little endian files and ARGB in big endian ones
+    Ok(bytes.safe_get_u32()?.to_be_bytes().into())
 }
 
 pub fn read_matrix(bytes: &mut Bytes) -> Result<Transform3<f32>> {
@@ -86,9 +73,60 @@ pub fn read_matrix(bytes: &mut Bytes) -> Result<Transform3<f32>> {
 
     for i in 0..4 {
         for j in 0..4 {
-            transform3[(i, j)] = bytes.safe_get_f32_le()?;
+            transform3[(i, j)] = bytes.safe_get_f32()?;
         }
     }
 
     Ok(transform3)
 }
+
+pub fn pure3d_write_string(bytes: &mut Vec<u8>, string: &Pure3DString) {
+    // The length prefix includes any padding, see Pure3DString
+    let raw = string.raw();
+    bytes.put_u8(raw.len() as u8);
+    bytes.put_slice(&raw);
+}
+
+pub fn pure3d_write_fourcc(bytes: &mut Vec<u8>, string: &str) {
+    let string = &string.as_bytes()[..string.len().min(4)];
+    bytes.put_slice(string);
+    bytes.put_bytes(0, 4 - string.len());
+}
+
+pub fn write_vec2(bytes: &mut Vec<u8>, vec: &Vector2) {
+    bytes.put_f32_le(vec.x);
+    bytes.put_f32_le(vec.y);
+}
+
+pub fn write_vec3(bytes: &mut Vec<u8>, vec: &Vector3) {
+    bytes.put_f32_le(vec.x);
+    bytes.put_f32_le(vec.y);
+    bytes.put_f32_le(vec.z);
+}
+
+pub fn write_quaternion(bytes: &mut Vec<u8>, quaternion: &Quaternion) {
+    for f in quaternion.coords.iter() {
+        bytes.put_f32_le(*f);
+    }
+}
+
+pub fn write_compressed_quaternion(bytes: &mut Vec<u8>, quaternion: &Quaternion) {
+    for f in quaternion.coords.iter() {
+        bytes.put_u16_le((f / QUATERNION_INVERSE_COMPRESSION_FACTOR).round() as u16);
+    }
+}
+
+pub fn write_colour(bytes: &mut Vec<u8>, colour: &Colour) {
+    // Stored as BGRA, see read_colour
+    for c in colour.iter().rev() {
+        bytes.put_u8(*c);
+    }
+}
+
+pub fn write_matrix(bytes: &mut Vec<u8>, matrix: &Transform3<f32>) {
+    for i in 0..4 {
+        for j in 0..4 {
+            bytes.put_f32_le(matrix[(i, j)]);
+        }
+    }
+}
@@ -3,9 +3,11 @@ pub mod channel;
 pub mod collision;
 pub mod explosion;
 pub mod file_metadata;
+pub mod frontend;
 pub mod game_metadata;
 pub mod gameattr;
 pub mod image;
+pub mod light;
 pub mod locator;
 pub mod mesh;
 pub mod name;
@@ -15,6 +17,7 @@ pub mod old_particle_system;
 pub mod physics;
 pub mod prop_state;
 pub mod pure3d_other;
+pub mod pure3d_string;
 pub mod scenegraph;
 pub mod shader;
 pub mod shader_param;
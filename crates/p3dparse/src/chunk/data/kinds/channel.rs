@@ -3,14 +3,19 @@ use crate::{
     chunk::{
         data::{
             helpers,
-            kinds::shared::{Colour, Quaternion, Vector2, Vector3},
+            kinds::{
+                pure3d_string::Pure3DString,
+                shared::{Colour, Quaternion, Vector2, Vector3},
+            },
             parse_trait::Parse,
+            schema::chunk_struct,
+            write_trait::Write,
         },
         type_identifiers::ChunkType,
     },
     Result,
 };
-use bytes::Bytes;
+use bytes::{BufMut, Bytes};
 use eyre::eyre;
 use serde::{Deserialize, Serialize};
 
@@ -23,20 +28,28 @@ pub struct Channel {
 }
 
 impl Channel {
+    /// The frame count prefix doubles as the value count for every channel type.
+    fn write_frames(&self, bytes: &mut Vec<u8>) {
+        bytes.put_u32_le(self.frames.len() as u32);
+        for frame in &self.frames {
+            bytes.put_u16_le(*frame);
+        }
+    }
+
     fn parse_vector1dof(bytes: &mut Bytes, _: ChunkType) -> Result<Self> {
         let param = helpers::pure3d_read_fourcc(bytes)?;
-        let mapping = bytes.safe_get_u16_le()?;
+        let mapping = bytes.safe_get_u16()?;
         let constants = helpers::read_vec3(bytes)?;
-        let frame_count = bytes.safe_get_u32_le()? as usize;
+        let frame_count = bytes.safe_get_u32()? as usize;
 
-        let mut frames = Vec::with_capacity(frame_count);
+        let mut frames = Vec::with_capacity(bytes.safe_count(frame_count, 2)?);
         for _ in 0..frame_count {
-            frames.push(bytes.safe_get_u16_le()?);
+            frames.push(bytes.safe_get_u16()?);
         }
 
-        let mut values = Vec::with_capacity(frame_count);
+        let mut values = Vec::with_capacity(bytes.safe_count(frame_count, 4)?);
         for _ in 0..frame_count {
-            values.push(bytes.safe_get_f32_le()?);
+            values.push(bytes.safe_get_f32()?);
         }
 
         Ok(Channel {
@@ -48,16 +61,16 @@ impl Channel {
 
     fn parse_vector2dof(bytes: &mut Bytes, _: ChunkType) -> Result<Self> {
         let param = helpers::pure3d_read_fourcc(bytes)?;
-        let mapping = bytes.safe_get_u16_le()?;
+        let mapping = bytes.safe_get_u16()?;
         let constants = helpers::read_vec3(bytes)?;
-        let frame_count = bytes.safe_get_u32_le()? as usize;
+        let frame_count = bytes.safe_get_u32()? as usize;
 
-        let mut frames = Vec::with_capacity(frame_count);
+        let mut frames = Vec::with_capacity(bytes.safe_count(frame_count, 2)?);
         for _ in 0..frame_count {
-            frames.push(bytes.safe_get_u16_le()?);
+            frames.push(bytes.safe_get_u16()?);
         }
 
-        let mut values = Vec::with_capacity(frame_count);
+        let mut values = Vec::with_capacity(bytes.safe_count(frame_count, 8)?);
         for _ in 0..frame_count {
             values.push(helpers::read_vec2(bytes)?);
         }
@@ -71,12 +84,12 @@ impl Channel {
 
     fn parse_bool(bytes: &mut Bytes, _: ChunkType) -> Result<Self> {
         let param = helpers::pure3d_read_fourcc(bytes)?;
-        let start_state = bytes.safe_get_u16_le()?;
-        let frame_count = bytes.safe_get_u32_le()? as usize;
+        let start_state = bytes.safe_get_u16()?;
+        let frame_count = bytes.safe_get_u32()? as usize;
 
-        let mut values = Vec::with_capacity(frame_count);
+        let mut values = Vec::with_capacity(bytes.safe_count(frame_count, 2)?);
         for _ in 0..frame_count {
-            values.push(bytes.safe_get_u16_le()?);
+            values.push(bytes.safe_get_u16()?);
         }
 
         Ok(Channel {
@@ -97,18 +110,18 @@ impl Parse for Channel {
         }
 
         let param = helpers::pure3d_read_fourcc(bytes)?;
-        let frame_count = bytes.safe_get_u32_le()? as usize;
+        let frame_count = bytes.safe_get_u32()? as usize;
 
-        let mut frames = Vec::with_capacity(frame_count);
+        let mut frames = Vec::with_capacity(bytes.safe_count(frame_count, 2)?);
         for _ in 0..frame_count {
-            frames.push(bytes.safe_get_u16_le()?);
+            frames.push(bytes.safe_get_u16()?);
         }
 
         match typ {
             ChunkType::Float1Channel => {
-                let mut values = Vec::with_capacity(frame_count);
+                let mut values = Vec::with_capacity(bytes.safe_count(frame_count, 4)?);
                 for _ in 0..frame_count {
-                    values.push(bytes.safe_get_f32_le()?);
+                    values.push(bytes.safe_get_f32()?);
                 }
                 let values = ChannelValues::Float1(values);
                 Ok(Channel {
@@ -118,7 +131,7 @@ impl Parse for Channel {
                 })
             }
             ChunkType::Float2Channel => {
-                let mut values = Vec::with_capacity(frame_count);
+                let mut values = Vec::with_capacity(bytes.safe_count(frame_count, 8)?);
                 for _ in 0..frame_count {
                     values.push(helpers::read_vec2(bytes)?);
                 }
@@ -130,9 +143,9 @@ impl Parse for Channel {
                 })
             }
             ChunkType::IntChannel => {
-                let mut values = Vec::with_capacity(frame_count);
+                let mut values = Vec::with_capacity(bytes.safe_count(frame_count, 4)?);
                 for _ in 0..frame_count {
-                    values.push(bytes.safe_get_u32_le()?)
+                    values.push(bytes.safe_get_u32()?)
                 }
                 let values = ChannelValues::Int(values);
                 Ok(Channel {
@@ -142,7 +155,7 @@ impl Parse for Channel {
                 })
             }
             ChunkType::Vector3DOFChannel => {
-                let mut values = Vec::with_capacity(frame_count);
+                let mut values = Vec::with_capacity(bytes.safe_count(frame_count, 12)?);
                 for _ in 0..frame_count {
                     values.push(helpers::read_vec3(bytes)?)
                 }
@@ -154,7 +167,7 @@ impl Parse for Channel {
                 })
             }
             ChunkType::QuaternionChannel => {
-                let mut values = Vec::with_capacity(frame_count);
+                let mut values = Vec::with_capacity(bytes.safe_count(frame_count, 16)?);
                 for _ in 0..frame_count {
                     values.push(helpers::read_quaternion(bytes)?)
                 }
@@ -166,7 +179,7 @@ impl Parse for Channel {
                 })
             }
             ChunkType::CompressedQuaternionChannel => {
-                let mut values = Vec::with_capacity(frame_count);
+                let mut values = Vec::with_capacity(bytes.safe_count(frame_count, 8)?);
                 for _ in 0..frame_count {
                     values.push(helpers::read_compressed_quaternion(bytes)?)
                 }
@@ -178,7 +191,7 @@ impl Parse for Channel {
                 })
             }
             ChunkType::ColourChannel => {
-                let mut values = Vec::with_capacity(frame_count);
+                let mut values = Vec::with_capacity(bytes.safe_count(frame_count, 4)?);
                 for _ in 0..frame_count {
                     values.push(helpers::read_colour(bytes)?)
                 }
@@ -190,7 +203,7 @@ impl Parse for Channel {
                 })
             }
             ChunkType::EntityChannel => {
-                let mut values = Vec::with_capacity(frame_count);
+                let mut values = Vec::with_capacity(bytes.safe_count(frame_count, 1)?);
                 for _ in 0..frame_count {
                     values.push(helpers::pure3d_read_string(bytes)?)
                 }
@@ -209,6 +222,84 @@ impl Parse for Channel {
     }
 }
 
+impl Write for Channel {
+    fn write(&self, bytes: &mut Vec<u8>, typ: ChunkType) {
+        helpers::pure3d_write_fourcc(bytes, &self.param);
+
+        match &self.values {
+            ChannelValues::Vector1OF(mapping, constants, values) => {
+                bytes.put_u16_le(*mapping);
+                helpers::write_vec3(bytes, constants);
+                self.write_frames(bytes);
+                for x in values {
+                    bytes.put_f32_le(*x);
+                }
+            }
+            ChannelValues::Vector2OF(mapping, constants, values) => {
+                bytes.put_u16_le(*mapping);
+                helpers::write_vec3(bytes, constants);
+                self.write_frames(bytes);
+                for x in values {
+                    helpers::write_vec2(bytes, x);
+                }
+            }
+            ChannelValues::Bool(start_state, values) => {
+                bytes.put_u16_le(*start_state);
+                bytes.put_u32_le(values.len() as u32);
+                for x in values {
+                    bytes.put_u16_le(*x);
+                }
+            }
+            ChannelValues::Float1(values) => {
+                self.write_frames(bytes);
+                for x in values {
+                    bytes.put_f32_le(*x);
+                }
+            }
+            ChannelValues::Float2(values) => {
+                self.write_frames(bytes);
+                for x in values {
+                    helpers::write_vec2(bytes, x);
+                }
+            }
+            ChannelValues::Int(values) => {
+                self.write_frames(bytes);
+                for x in values {
+                    bytes.put_u32_le(*x);
+                }
+            }
+            ChannelValues::Vector3OF(values) => {
+                self.write_frames(bytes);
+                for x in values {
+                    helpers::write_vec3(bytes, x);
+                }
+            }
+            ChannelValues::Quaternion(values) => {
+                self.write_frames(bytes);
+                for x in values {
+                    if typ == ChunkType::CompressedQuaternionChannel {
+                        helpers::write_compressed_quaternion(bytes, x);
+                    } else {
+                        helpers::write_quaternion(bytes, x);
+                    }
+                }
+            }
+            ChannelValues::Colour(values) => {
+                self.write_frames(bytes);
+                for x in values {
+                    helpers::write_colour(bytes, x);
+                }
+            }
+            ChannelValues::Entity(values) => {
+                self.write_frames(bytes);
+                for x in values {
+                    helpers::pure3d_write_string(bytes, x);
+                }
+            }
+        }
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 #[allow(clippy::upper_case_acronyms)]
 pub enum ChannelValues {
@@ -221,19 +312,13 @@ pub enum ChannelValues {
     Quaternion(Vec<Quaternion>),
     Colour(Vec<Colour>),
     Bool(u16, Vec<u16>),
-    Entity(Vec<String>),
+    Entity(Vec<Pure3DString>),
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
-#[allow(clippy::upper_case_acronyms)]
-pub struct ChannelInterpolation {
-    pub interpolate: u32,
-}
-
-impl Parse for ChannelInterpolation {
-    fn parse(bytes: &mut Bytes, _: ChunkType) -> Result<Self> {
-        Ok(ChannelInterpolation {
-            interpolate: bytes.safe_get_u32_le()?,
-        })
+chunk_struct! {
+    #[derive(Eq, PartialOrd, Ord)]
+    #[allow(clippy::upper_case_acronyms)]
+    pub struct ChannelInterpolation {
+        pub interpolate: u32,
     }
 }
@@ -1,173 +1,104 @@
 use crate::{
-    bytes_ext::BufResult,
+    bytes_ext::{BufResult, BytesExt},
     chunk::{
         data::{
-            helpers::{pure3d_read_string, read_vec3},
-            kinds::shared::Vector3,
+            helpers::{read_vec3, write_vec3},
+            kinds::{pure3d_string::Pure3DString, shared::Vector3},
             parse_trait::Parse,
+            schema::chunk_struct,
+            write_trait::Write,
         },
         type_identifiers::ChunkType,
     },
     Result,
 };
-use bytes::Bytes;
+use bytes::{BufMut, Bytes};
 use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
-#[allow(non_snake_case)]
-pub struct CollisionObject {
-    pub material_name: String,
-    pub num_sub_object: u32,
-    pub num_owner: u32,
-}
-
-impl Parse for CollisionObject {
-    fn parse(bytes: &mut Bytes, _: ChunkType) -> Result<Self> {
-        Ok(CollisionObject {
-            material_name: pure3d_read_string(bytes)?,
-            num_sub_object: bytes.safe_get_u32_le()?,
-            num_owner: bytes.safe_get_u32_le()?,
-        })
+chunk_struct! {
+    #[derive(Eq, PartialOrd, Ord)]
+    #[allow(non_snake_case)]
+    pub struct CollisionObject {
+        pub material_name: Pure3DString,
+        pub num_sub_object: u32,
+        pub num_owner: u32,
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
-#[allow(non_snake_case)]
-pub struct CollisionVolume {
-    pub object_reference_index: u32,
-    pub owner_index: i32,
-    pub num_volume: u32,
-}
-
-impl Parse for CollisionVolume {
-    fn parse(bytes: &mut Bytes, _: ChunkType) -> Result<Self> {
-        Ok(CollisionVolume {
-            object_reference_index: bytes.safe_get_u32_le()?,
-            owner_index: bytes.safe_get_i32_le()?,
-            num_volume: bytes.safe_get_u32_le()?,
-        })
+chunk_struct! {
+    #[derive(Eq, PartialOrd, Ord)]
+    #[allow(non_snake_case)]
+    pub struct CollisionVolume {
+        pub object_reference_index: u32,
+        pub owner_index: i32,
+        pub num_volume: u32,
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
-#[allow(non_snake_case)]
-pub struct CollisionVolumeOwner {
-    pub num_names: u32,
-}
-
-impl Parse for CollisionVolumeOwner {
-    fn parse(bytes: &mut Bytes, _: ChunkType) -> Result<Self> {
-        Ok(CollisionVolumeOwner {
-            num_names: bytes.safe_get_u32_le()?,
-        })
+chunk_struct! {
+    #[derive(Eq, PartialOrd, Ord)]
+    #[allow(non_snake_case)]
+    pub struct CollisionVolumeOwner {
+        pub num_names: u32,
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
-#[allow(non_snake_case)]
-pub struct CollisionBoundingBox {
-    /// Literally the same in SRR2 source code, "dummy" or "Nothing"
-    pub nothing: u32,
-}
-
-impl Parse for CollisionBoundingBox {
-    fn parse(bytes: &mut Bytes, _: ChunkType) -> Result<Self> {
-        Ok(CollisionBoundingBox {
-            nothing: bytes.safe_get_u32_le()?,
-        })
+chunk_struct! {
+    #[derive(Eq, PartialOrd, Ord)]
+    #[allow(non_snake_case)]
+    pub struct CollisionBoundingBox {
+        /// Literally the same in SRR2 source code, "dummy" or "Nothing"
+        pub nothing: u32,
     }
 }
 
-#[derive(Clone, Debug, PartialEq, PartialOrd, Serialize, Deserialize)]
-#[allow(non_snake_case)]
-pub struct CollisionOblongBox {
-    pub half_extent_x: f32,
-    pub half_extent_y: f32,
-    pub half_extent_z: f32,
-}
-
-impl Parse for CollisionOblongBox {
-    fn parse(bytes: &mut Bytes, _: ChunkType) -> Result<Self> {
-        Ok(CollisionOblongBox {
-            half_extent_x: bytes.safe_get_f32_le()?,
-            half_extent_y: bytes.safe_get_f32_le()?,
-            half_extent_z: bytes.safe_get_f32_le()?,
-        })
+chunk_struct! {
+    #[derive(PartialOrd)]
+    #[allow(non_snake_case)]
+    pub struct CollisionOblongBox {
+        pub half_extent_x: f32,
+        pub half_extent_y: f32,
+        pub half_extent_z: f32,
     }
 }
 
-#[derive(Clone, Debug, PartialEq, PartialOrd, Serialize, Deserialize)]
-#[allow(non_snake_case)]
-pub struct CollisionCylinder {
-    pub cylinder_radius: f32,
-    pub length: f32,
-    pub flat_end: u16,
-}
-
-impl Parse for CollisionCylinder {
-    fn parse(bytes: &mut Bytes, _: ChunkType) -> Result<Self> {
-        Ok(CollisionCylinder {
-            cylinder_radius: bytes.safe_get_f32_le()?,
-            length: bytes.safe_get_f32_le()?,
-            flat_end: bytes.safe_get_u16_le()?,
-        })
+chunk_struct! {
+    #[derive(PartialOrd)]
+    #[allow(non_snake_case)]
+    pub struct CollisionCylinder {
+        pub cylinder_radius: f32,
+        pub length: f32,
+        pub flat_end: u16,
     }
 }
 
-#[derive(Clone, Debug, PartialEq, PartialOrd, Serialize, Deserialize)]
-#[allow(non_snake_case)]
-pub struct CollisionSphere {
-    pub radius: f32,
-}
-
-impl Parse for CollisionSphere {
-    fn parse(bytes: &mut Bytes, _: ChunkType) -> Result<Self> {
-        Ok(CollisionSphere {
-            radius: bytes.safe_get_f32_le()?,
-        })
+chunk_struct! {
+    #[derive(PartialOrd)]
+    #[allow(non_snake_case)]
+    pub struct CollisionSphere {
+        pub radius: f32,
     }
 }
-#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
-#[allow(non_snake_case)]
-pub struct CollisionVector {
-    pub vector: Vector3,
-}
-
-impl Parse for CollisionVector {
-    fn parse(bytes: &mut Bytes, _: ChunkType) -> Result<Self> {
-        Ok(CollisionVector {
-            vector: read_vec3(bytes)?,
-        })
+chunk_struct! {
+    #[allow(non_snake_case)]
+    pub struct CollisionVector {
+        pub vector: Vector3,
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
-#[allow(non_snake_case)]
-pub struct CollisionObjectAttribute {
-    pub static_attribute: u16,
-    pub default_area: u32,
-    pub can_roll: u16,
-    pub can_slide: u16,
-    pub can_spin: u16,
-    pub can_bounce: u16,
-    pub extra_attribute_1: u32,
-    pub extra_attribute_2: u32,
-    pub extra_attribute_3: u32,
-}
-
-impl Parse for CollisionObjectAttribute {
-    fn parse(bytes: &mut Bytes, _: ChunkType) -> Result<Self> {
-        Ok(CollisionObjectAttribute {
-            static_attribute: bytes.safe_get_u16_le()?,
-            default_area: bytes.safe_get_u32_le()?,
-            can_roll: bytes.safe_get_u16_le()?,
-            can_slide: bytes.safe_get_u16_le()?,
-            can_spin: bytes.safe_get_u16_le()?,
-            can_bounce: bytes.safe_get_u16_le()?,
-            extra_attribute_1: bytes.safe_get_u32_le()?,
-            extra_attribute_2: bytes.safe_get_u32_le()?,
-            extra_attribute_3: bytes.safe_get_u32_le()?,
-        })
+chunk_struct! {
+    #[derive(Eq, PartialOrd, Ord)]
+    #[allow(non_snake_case)]
+    pub struct CollisionObjectAttribute {
+        pub static_attribute: u16,
+        pub default_area: u32,
+        pub can_roll: u16,
+        pub can_slide: u16,
+        pub can_spin: u16,
+        pub can_bounce: u16,
+        pub extra_attribute_1: u32,
+        pub extra_attribute_2: u32,
+        pub extra_attribute_3: u32,
     }
 }
 
@@ -181,18 +112,18 @@ pub struct IntersectDSG {
 
 impl Parse for IntersectDSG {
     fn parse(bytes: &mut Bytes, _: ChunkType) -> Result<Self> {
-        let indices_len = bytes.safe_get_u32_le()?;
-        let mut indices = Vec::with_capacity(indices_len as usize);
+        let indices_len = bytes.safe_get_u32()?;
+        let mut indices = Vec::with_capacity(bytes.safe_count(indices_len as usize, 4)?);
         for _ in 0..indices_len {
-            indices.push(bytes.safe_get_u32_le()?)
+            indices.push(bytes.safe_get_u32()?)
         }
-        let positions_len = bytes.safe_get_u32_le()?;
-        let mut positions = Vec::with_capacity(positions_len as usize);
+        let positions_len = bytes.safe_get_u32()?;
+        let mut positions = Vec::with_capacity(bytes.safe_count(positions_len as usize, 12)?);
         for _ in 0..positions_len {
             positions.push(read_vec3(bytes)?)
         }
-        let normals_len = bytes.safe_get_u32_le()?;
-        let mut normals = Vec::with_capacity(normals_len as usize);
+        let normals_len = bytes.safe_get_u32()?;
+        let mut normals = Vec::with_capacity(bytes.safe_count(normals_len as usize, 12)?);
         for _ in 0..normals_len {
             normals.push(read_vec3(bytes)?)
         }
@@ -204,19 +135,41 @@ impl Parse for IntersectDSG {
     }
 }
 
+impl Write for IntersectDSG {
+    fn write(&self, bytes: &mut Vec<u8>, _: ChunkType) {
+        bytes.put_u32_le(self.indices.len() as u32);
+        for x in &self.indices {
+            bytes.put_u32_le(*x);
+        }
+        bytes.put_u32_le(self.positions.len() as u32);
+        for x in &self.positions {
+            write_vec3(bytes, x);
+        }
+        bytes.put_u32_le(self.normals.len() as u32);
+        for x in &self.normals {
+            write_vec3(bytes, x);
+        }
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 pub struct TerrainTypeList {
-    pub types: Vec<u8>,
+    pub types: Bytes,
 }
 
 impl Parse for TerrainTypeList {
     fn parse(bytes: &mut Bytes, _: ChunkType) -> Result<Self> {
-        let types_len = bytes.safe_get_u32_le()?;
-        let mut types = Vec::with_capacity(types_len as usize);
-        for _ in 0..types_len {
-            types.push(bytes.safe_get_u8()?)
-        }
-        Ok(TerrainTypeList { types })
+        let types_len = bytes.safe_get_u32()?;
+        Ok(TerrainTypeList {
+            types: bytes.safe_split_to(types_len as usize)?,
+        })
+    }
+}
+
+impl Write for TerrainTypeList {
+    fn write(&self, bytes: &mut Vec<u8>, _: ChunkType) {
+        bytes.put_u32_le(self.types.len() as u32);
+        bytes.put_slice(&self.types);
     }
 }
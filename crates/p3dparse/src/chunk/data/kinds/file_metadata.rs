@@ -1,17 +1,19 @@
 use crate::{
     bytes_ext::BufResult,
     chunk::{
-        data::{helpers, parse_trait::Parse},
+        data::{
+            helpers, kinds::pure3d_string::Pure3DString, parse_trait::Parse, write_trait::Write,
+        },
         type_identifiers::ChunkType,
     },
     Result,
 };
-use bytes::Bytes;
+use bytes::{BufMut, Bytes};
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub struct ExportInfoNamedString {
-    value: String,
+    value: Pure3DString,
 }
 
 impl Parse for ExportInfoNamedString {
@@ -22,6 +24,12 @@ impl Parse for ExportInfoNamedString {
     }
 }
 
+impl Write for ExportInfoNamedString {
+    fn write(&self, bytes: &mut Vec<u8>, _: ChunkType) {
+        helpers::pure3d_write_string(bytes, &self.value);
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub struct ExportInfoNamedInt {
     value: u32,
@@ -30,23 +38,38 @@ pub struct ExportInfoNamedInt {
 impl Parse for ExportInfoNamedInt {
     fn parse(bytes: &mut Bytes, _: ChunkType) -> Result<Self> {
         Ok(ExportInfoNamedInt {
-            value: bytes.safe_get_u32_le()?,
+            value: bytes.safe_get_u32()?,
         })
     }
 }
 
+impl Write for ExportInfoNamedInt {
+    fn write(&self, bytes: &mut Vec<u8>, _: ChunkType) {
+        bytes.put_u32_le(self.value);
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub struct History {
-    history: Vec<String>,
+    history: Vec<Pure3DString>,
 }
 
 impl Parse for History {
     fn parse(bytes: &mut Bytes, _: ChunkType) -> Result<Self> {
         let mut history = History { history: vec![] };
-        let num_lines = bytes.safe_get_u16_le()?;
+        let num_lines = bytes.safe_get_u16()?;
         for _ in 0..num_lines {
             history.history.push(helpers::pure3d_read_string(bytes)?)
         }
         Ok(history)
     }
 }
+
+impl Write for History {
+    fn write(&self, bytes: &mut Vec<u8>, _: ChunkType) {
+        bytes.put_u16_le(self.history.len() as u16);
+        for line in &self.history {
+            helpers::pure3d_write_string(bytes, line);
+        }
+    }
+}
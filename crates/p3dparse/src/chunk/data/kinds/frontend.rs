@@ -0,0 +1,319 @@
+use crate::{
+    bytes_ext::BufResult,
+    chunk::{
+        data::{
+            helpers,
+            kinds::{
+                pure3d_string::Pure3DString,
+                shared::{Colour, Vector3},
+            },
+            parse_trait::Parse,
+            schema::chunk_struct,
+            write_trait::Write,
+        },
+        type_identifiers::ChunkType,
+    },
+    Result,
+};
+use bytes::{BufMut, Bytes};
+use serde::{Deserialize, Serialize};
+
+chunk_struct! {
+    #[derive(Eq)]
+    pub struct FrontendProject {
+        pub resolution_x: u32,
+        pub resolution_y: u32,
+        pub platform: Pure3DString,
+        pub page_path: Pure3DString,
+        pub resource_path: Pure3DString,
+        pub screen_path: Pure3DString,
+    }
+}
+
+chunk_struct! {
+    #[derive(Eq)]
+    pub struct FrontendScreen {
+        /// Names of [`FrontendPage`] chunks in the same project
+        pub page_names: Vec<Pure3DString>,
+    }
+}
+
+chunk_struct! {
+    #[derive(Eq)]
+    pub struct FrontendPage {
+        pub resolution_x: u32,
+        pub resolution_y: u32,
+    }
+}
+
+chunk_struct! {
+    #[derive(Eq)]
+    pub struct FrontendLayer {
+        pub visible: u32,
+        pub editable: u32,
+        pub alpha: u32,
+    }
+}
+
+chunk_struct! {
+    #[derive(Eq)]
+    pub struct FrontendGroup {
+        pub alpha: u32,
+    }
+}
+
+chunk_struct! {
+    /// Placement shared by every drawable element of a page
+    pub struct FrontendElement {
+        pub position_x: i32,
+        pub position_y: i32,
+        pub dimension_x: u32,
+        pub dimension_y: u32,
+        /// 0 left, 1 right, 4 centre
+        pub justification_x: u32,
+        /// 2 top, 3 bottom, 4 centre
+        pub justification_y: u32,
+        pub colour: Colour,
+        pub translucency: u32,
+        pub rotation: f32,
+    }
+}
+
+chunk_struct! {
+    pub struct FrontendMovie {
+        pub element: FrontendElement,
+    }
+}
+
+chunk_struct! {
+    /// Also used by plain sprites, which are written the same way
+    pub struct FrontendMultiSprite {
+        pub element: FrontendElement,
+        /// Names of [`FrontendImageResource`] chunks
+        pub image_names: Vec<Pure3DString>,
+    }
+}
+
+chunk_struct! {
+    /// The text itself is in [`FrontendStringTextBible`] and [`FrontendStringHardCoded`] children
+    pub struct FrontendMultiText {
+        pub element: FrontendElement,
+        pub text_style_name: Pure3DString,
+        pub shadow_enabled: u8,
+        pub shadow_colour: Colour,
+        pub shadow_offset_x: i32,
+        pub shadow_offset_y: i32,
+        pub current_text: u32,
+    }
+}
+
+chunk_struct! {
+    pub struct FrontendPure3DObject {
+        pub element: FrontendElement,
+        pub pure3d_filename: Pure3DString,
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct FrontendPolygon {
+    pub translucency: u32,
+    pub points: Vec<Vector3>,
+    /// One per point. The file has a single count for both, so if the two lengths differ only as
+    /// many points and colours as the shorter one has are written.
+    pub colours: Vec<Colour>,
+}
+
+impl Parse for FrontendPolygon {
+    fn parse(bytes: &mut Bytes, _: ChunkType) -> Result<Self> {
+        let translucency = bytes.safe_get_u32()?;
+        // Points and colours share a single count
+        let num_points = bytes.safe_get_u32()? as usize;
+
+        let mut points = Vec::with_capacity(bytes.safe_count(num_points, 12)?);
+        for _ in 0..num_points {
+            points.push(helpers::read_vec3(bytes)?);
+        }
+
+        let mut colours = Vec::with_capacity(bytes.safe_count(num_points, 4)?);
+        for _ in 0..num_points {
+            colours.push(helpers::read_colour(bytes)?);
+        }
+
+        Ok(FrontendPolygon {
+            translucency,
+            points,
+            colours,
+        })
+    }
+}
+
+impl Write for FrontendPolygon {
+    fn write(&self, bytes: &mut Vec<u8>, _: ChunkType) {
+        let num_points = self.points.len().min(self.colours.len());
+        bytes.put_u32_le(self.translucency);
+        bytes.put_u32_le(num_points as u32);
+        for point in &self.points[..num_points] {
+            helpers::write_vec3(bytes, point);
+        }
+        for colour in &self.colours[..num_points] {
+            helpers::write_colour(bytes, colour);
+        }
+    }
+}
+
+chunk_struct! {
+    #[derive(Eq)]
+    pub struct FrontendStringTextBible {
+        pub bible_name: Pure3DString,
+        pub string_id: Pure3DString,
+    }
+}
+
+chunk_struct! {
+    #[derive(Eq)]
+    pub struct FrontendStringHardCoded {
+        pub string: Pure3DString,
+    }
+}
+
+chunk_struct! {
+    #[derive(Eq)]
+    pub struct FrontendImageResource {
+        pub filename: Pure3DString,
+    }
+}
+
+chunk_struct! {
+    /// Pure3D, text style and text bible resources, including the old style and bible chunk types
+    #[derive(Eq)]
+    pub struct FrontendResource {
+        pub filename: Pure3DString,
+        pub inventory_name: Pure3DString,
+    }
+}
+
+chunk_struct! {
+    #[derive(Eq)]
+    pub struct FrontendTextBible {
+        pub num_languages: u32,
+        /// One character per [`FrontendLanguage`] child, e.g. `EFGS`
+        pub languages: Pure3DString,
+    }
+}
+
+/// Every string of a text bible in one language.
+///
+/// Only a hash of each string's key is stored. The strings are UCS-2, each one null terminated,
+/// and kept as they are in the file so untouched languages are written back unchanged. Use
+/// [`FrontendLanguage::entries`] and [`FrontendLanguage::set_entries`] to read and replace them.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FrontendLanguage {
+    /// The character this language has in [`FrontendTextBible::languages`]
+    pub language: u8,
+    pub modulo: u32,
+    pub hashes: Vec<u32>,
+    /// Where each string starts in [`FrontendLanguage::buffer`], in UCS-2 characters
+    pub offsets: Vec<u32>,
+    pub buffer: Vec<u16>,
+    /// The last byte of a buffer with an odd size in bytes, which isn't a whole character. Kept so
+    /// the buffer is written back the same size.
+    pub trailing_byte: Option<u8>,
+}
+
+impl FrontendLanguage {
+    /// The hash and string of every entry, in file order.
+    pub fn entries(&self) -> impl Iterator<Item = (u32, String)> + '_ {
+        self.hashes
+            .iter()
+            .zip(&self.offsets)
+            .map(|(hash, offset)| (*hash, self.string_at(*offset as usize)))
+    }
+
+    /// The string for a key hash, if this language has one.
+    pub fn get(&self, hash: u32) -> Option<String> {
+        let index = self.hashes.iter().position(|h| *h == hash)?;
+        Some(self.string_at(self.offsets[index] as usize))
+    }
+
+    /// Replaces every entry, rebuilding the offsets and buffer. Characters outside the Basic
+    /// Multilingual Plane are written as surrogate pairs, which the game will show as two
+    /// characters. [`FrontendLanguage::trailing_byte`] is left alone.
+    pub fn set_entries<'s>(&mut self, entries: impl IntoIterator<Item = (u32, &'s str)>) {
+        self.hashes.clear();
+        self.offsets.clear();
+        self.buffer.clear();
+
+        for (hash, string) in entries {
+            self.hashes.push(hash);
+            self.offsets.push(self.buffer.len() as u32);
+            self.buffer.extend(string.encode_utf16());
+            self.buffer.push(0);
+        }
+    }
+
+    fn string_at(&self, offset: usize) -> String {
+        let units = self.buffer.get(offset..).unwrap_or_default();
+        let end = units.iter().position(|u| *u == 0).unwrap_or(units.len());
+        String::from_utf16_lossy(&units[..end])
+    }
+}
+
+impl Parse for FrontendLanguage {
+    fn parse(bytes: &mut Bytes, _: ChunkType) -> Result<Self> {
+        let language = bytes.safe_get_u8()?;
+        let num_entries = bytes.safe_get_u32()? as usize;
+        let modulo = bytes.safe_get_u32()?;
+
+        // Both hashes and offsets have to fit
+        bytes.safe_count(num_entries, 8)?;
+        let mut hashes = Vec::with_capacity(num_entries);
+        for _ in 0..num_entries {
+            hashes.push(bytes.safe_get_u32()?);
+        }
+        let mut offsets = Vec::with_capacity(num_entries);
+        for _ in 0..num_entries {
+            offsets.push(bytes.safe_get_u32()?);
+        }
+
+        // In bytes
+        let buffer_size = bytes.safe_get_u32()? as usize;
+        let mut buffer = Vec::with_capacity(bytes.safe_count(buffer_size / 2, 2)?);
+        for _ in 0..buffer_size / 2 {
+            buffer.push(bytes.safe_get_u16()?);
+        }
+        let trailing_byte = match buffer_size % 2 {
+            1 => Some(bytes.safe_get_u8()?),
+            _ => None,
+        };
+
+        Ok(FrontendLanguage {
+            language,
+            modulo,
+            hashes,
+            offsets,
+            buffer,
+            trailing_byte,
+        })
+    }
+}
+
+impl Write for FrontendLanguage {
+    fn write(&self, bytes: &mut Vec<u8>, _: ChunkType) {
+        bytes.put_u8(self.language);
+        bytes.put_u32_le(self.hashes.len() as u32);
+        bytes.put_u32_le(self.modulo);
+        for hash in &self.hashes {
+            bytes.put_u32_le(*hash);
+        }
+        for offset in &self.offsets {
+            bytes.put_u32_le(*offset);
+        }
+        bytes.put_u32_le(self.buffer.len() as u32 * 2 + self.trailing_byte.is_some() as u32);
+        for unit in &self.buffer {
+            bytes.put_u16_le(*unit);
+        }
+        if let Some(byte) = self.trailing_byte {
+            bytes.put_u8(byte);
+        }
+    }
+}
@@ -1,12 +1,12 @@
 use crate::{
     bytes_ext::BufResult,
     chunk::{
-        data::{helpers, kinds::shared::Vector3, parse_trait::Parse},
+        data::{helpers, kinds::shared::Vector3, parse_trait::Parse, write_trait::Write},
         type_identifiers::ChunkType,
     },
     Result,
 };
-use bytes::Bytes;
+use bytes::{BufMut, Bytes};
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
@@ -22,6 +22,12 @@ impl Parse for Locator {
     }
 }
 
+impl Write for Locator {
+    fn write(&self, bytes: &mut Vec<u8>, _: ChunkType) {
+        helpers::write_vec3(bytes, &self.position);
+    }
+}
+
 #[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
 pub struct FollowCameraData {
     id: u32,
@@ -36,11 +42,21 @@ pub struct FollowCameraData {
 impl Parse for FollowCameraData {
     fn parse(bytes: &mut Bytes, _: ChunkType) -> Result<Self> {
         Ok(FollowCameraData {
-            id: bytes.safe_get_u32_le()?,
-            rotation: bytes.safe_get_f32_le()?,
-            elevation: bytes.safe_get_f32_le()?,
-            magnitude: bytes.safe_get_f32_le()?,
+            id: bytes.safe_get_u32()?,
+            rotation: bytes.safe_get_f32()?,
+            elevation: bytes.safe_get_f32()?,
+            magnitude: bytes.safe_get_f32()?,
             target_offset: helpers::read_vec3(bytes)?,
         })
     }
 }
+
+impl Write for FollowCameraData {
+    fn write(&self, bytes: &mut Vec<u8>, _: ChunkType) {
+        bytes.put_u32_le(self.id);
+        bytes.put_f32_le(self.rotation);
+        bytes.put_f32_le(self.elevation);
+        bytes.put_f32_le(self.magnitude);
+        helpers::write_vec3(bytes, &self.target_offset);
+    }
+}
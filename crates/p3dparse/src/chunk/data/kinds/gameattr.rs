@@ -2,33 +2,32 @@ use crate::{
     bytes_ext::BufResult,
     chunk::{
         data::{
-            helpers::{self, read_vec3},
-            kinds::shared::{Colour, Matrix, Vector3},
+            helpers,
+            kinds::{
+                pure3d_string::Pure3DString,
+                shared::{Colour, Matrix, Vector3},
+            },
             parse_trait::Parse,
+            schema::chunk_struct,
+            write_trait::Write,
         },
         type_identifiers::ChunkType,
     },
     Result,
 };
-use bytes::Bytes;
+use bytes::{BufMut, Bytes};
 use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
-pub struct GameAttr {
-    pub num_params: u32,
-}
-
-impl Parse for GameAttr {
-    fn parse(bytes: &mut Bytes, _: ChunkType) -> Result<Self> {
-        Ok(GameAttr {
-            num_params: bytes.safe_get_u32_le()?,
-        })
+chunk_struct! {
+    #[derive(Eq, PartialOrd, Ord)]
+    pub struct GameAttr {
+        pub num_params: u32,
     }
 }
 
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct GameAttrParam {
-    pub param: String,
+    pub param: Pure3DString,
     pub value: GameAttrParamValue,
 }
 
@@ -37,14 +36,14 @@ impl Parse for GameAttrParam {
         Ok(GameAttrParam {
             param: helpers::pure3d_read_string(bytes)?,
             value: match typ {
-                ChunkType::GameAttrIntParam => GameAttrParamValue::Int(bytes.safe_get_u32_le()?),
-                ChunkType::GameAttrFloatParam => {
-                    GameAttrParamValue::Float(bytes.safe_get_f32_le()?)
-                }
+                ChunkType::GameAttrIntParam => GameAttrParamValue::Int(bytes.safe_get_u32()?),
+                ChunkType::GameAttrFloatParam => GameAttrParamValue::Float(bytes.safe_get_f32()?),
                 ChunkType::GameAttrColourParam => {
                     GameAttrParamValue::Colour(helpers::read_colour(bytes)?)
                 }
-                ChunkType::GameAttrVectorParam => GameAttrParamValue::Vector(read_vec3(bytes)?),
+                ChunkType::GameAttrVectorParam => {
+                    GameAttrParamValue::Vector(helpers::read_vec3(bytes)?)
+                }
                 ChunkType::GameAttrMatrixParam => {
                     GameAttrParamValue::Matrix(helpers::read_matrix(bytes)?)
                 }
@@ -54,6 +53,20 @@ impl Parse for GameAttrParam {
     }
 }
 
+impl Write for GameAttrParam {
+    fn write(&self, bytes: &mut Vec<u8>, _: ChunkType) {
+        helpers::pure3d_write_string(bytes, &self.param);
+        match &self.value {
+            GameAttrParamValue::Int(int) => bytes.put_u32_le(*int),
+            GameAttrParamValue::Float(float) => bytes.put_f32_le(*float),
+            GameAttrParamValue::Colour(colour) => helpers::write_colour(bytes, colour),
+            GameAttrParamValue::Vector(vector) => helpers::write_vec3(bytes, vector),
+            GameAttrParamValue::Matrix(matrix) => helpers::write_matrix(bytes, matrix),
+            GameAttrParamValue::None => {}
+        }
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub enum GameAttrParamValue {
     Int(u32),
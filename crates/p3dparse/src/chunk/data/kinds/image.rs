@@ -1,9 +1,15 @@
 use crate::{
-    bytes_ext::BufResult,
-    chunk::{data::parse_trait::Parse, type_identifiers::ChunkType},
+    bytes_ext::{BufResult, BytesExt},
+    chunk::{
+        data::{
+            parse_trait::{in_field, Parse},
+            write_trait::Write,
+        },
+        type_identifiers::ChunkType,
+    },
     Result,
 };
-use bytes::Bytes;
+use bytes::{BufMut, Bytes};
 use num_enum::{IntoPrimitive, TryFromPrimitive};
 use serde::{Deserialize, Serialize};
 
@@ -174,30 +180,51 @@ pub struct Image {
 impl Parse for Image {
     fn parse(bytes: &mut Bytes, _: ChunkType) -> Result<Self> {
         Ok(Image {
-            width: bytes.safe_get_u32_le()?,
-            height: bytes.safe_get_u32_le()?,
-            bpp: bytes.safe_get_u32_le()?,
-            palettized: bytes.safe_get_u32_le()?,
-            has_alpha: bytes.safe_get_u32_le()?,
-            image_format: bytes.safe_get_u32_le()?.try_into()?,
+            width: bytes.safe_get_u32()?,
+            height: bytes.safe_get_u32()?,
+            bpp: bytes.safe_get_u32()?,
+            palettized: bytes.safe_get_u32()?,
+            has_alpha: bytes.safe_get_u32()?,
+            image_format: in_field(
+                "image_format",
+                bytes
+                    .safe_get_u32()
+                    .and_then(|format| Ok(format.try_into()?)),
+            )?,
         })
     }
 }
 
+impl Write for Image {
+    fn write(&self, bytes: &mut Vec<u8>, _: ChunkType) {
+        bytes.put_u32_le(self.width);
+        bytes.put_u32_le(self.height);
+        bytes.put_u32_le(self.bpp);
+        bytes.put_u32_le(self.palettized);
+        bytes.put_u32_le(self.has_alpha);
+        bytes.put_u32_le(self.image_format.into());
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub struct ImageRaw {
-    pub data: Vec<u8>,
+    /// A slice of the parsed file, not a copy.
+    pub data: Bytes,
 }
 
 impl Parse for ImageRaw {
     fn parse(bytes: &mut Bytes, _: ChunkType) -> Result<Self> {
-        let size = bytes.safe_get_u32_le()?;
+        let size = bytes.safe_get_u32()? as usize;
 
-        let mut data = Vec::with_capacity(size as usize);
-        for _ in 0..size {
-            data.push(bytes.safe_get_u8()?);
-        }
+        Ok(ImageRaw {
+            data: bytes.safe_split_to(size)?,
+        })
+    }
+}
 
-        Ok(ImageRaw { data })
+impl Write for ImageRaw {
+    fn write(&self, bytes: &mut Vec<u8>, _: ChunkType) {
+        bytes.put_u32_le(self.data.len() as u32);
+        bytes.put_slice(&self.data);
     }
 }
@@ -0,0 +1,121 @@
+use crate::{
+    bytes_ext::BufResult,
+    chunk::data::{
+        kinds::{
+            pure3d_string::Pure3DString,
+            shared::{Colour, Vector3},
+        },
+        schema::{chunk_struct, Field},
+    },
+    Result,
+};
+use bytes::{BufMut, Bytes};
+use num_enum::{FromPrimitive, IntoPrimitive};
+use serde::{Deserialize, Serialize};
+
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    FromPrimitive,
+    IntoPrimitive,
+    Serialize,
+    Deserialize,
+)]
+#[repr(u32)]
+pub enum LightType {
+    Ambient = 0x0,
+    Point = 0x1,
+    Directional = 0x2,
+    Spot = 0x3,
+    /// Any type not listed above, kept so the light still round trips.
+    #[num_enum(catch_all)]
+    Other(u32),
+}
+
+impl Field for LightType {
+    const MIN_SIZE: usize = 4;
+
+    fn read(bytes: &mut Bytes) -> Result<LightType> {
+        Ok(bytes.safe_get_u32()?.into())
+    }
+
+    fn write(value: &LightType, bytes: &mut Vec<u8>) {
+        bytes.put_u32_le((*value).into());
+    }
+}
+
+chunk_struct! {
+    pub struct Light {
+        pub light_type: LightType,
+        pub colour: Colour,
+        /// Attenuation is `1 / (constant + linear * d + squared * d^2)`
+        pub constant: f32,
+        pub linear: f32,
+        pub squared: f32,
+        pub enabled: u32,
+    }
+}
+
+chunk_struct! {
+    pub struct LightDirection {
+        pub direction: Vector3,
+    }
+}
+
+chunk_struct! {
+    pub struct LightPosition {
+        pub position: Vector3,
+    }
+}
+
+chunk_struct! {
+    pub struct LightConeParam {
+        /// Outer cone angle, in radians
+        pub phi: f32,
+        /// Inner cone angle, in radians
+        pub theta: f32,
+        pub falloff: f32,
+        pub range: f32,
+    }
+}
+
+chunk_struct! {
+    #[derive(Eq)]
+    pub struct LightShadow {
+        pub shadow: u32,
+    }
+}
+
+chunk_struct! {
+    pub struct LightDecayRange {
+        pub decay_type: u32,
+        pub inner_range: Vector3,
+        pub outer_range: Vector3,
+    }
+}
+
+chunk_struct! {
+    pub struct LightDecayRangeRotationY {
+        pub rotation: f32,
+    }
+}
+
+chunk_struct! {
+    #[derive(Eq)]
+    pub struct LightIlluminationType {
+        pub illumination_type: u32,
+    }
+}
+
+chunk_struct! {
+    #[derive(Eq)]
+    pub struct LightGroup {
+        /// Names of [`Light`] chunks
+        pub lights: Vec<Pure3DString>,
+    }
+}
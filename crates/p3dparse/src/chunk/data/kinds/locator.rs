@@ -2,15 +2,17 @@ use crate::{
     bytes_ext::BufResult,
     chunk::{
         data::{
-            helpers::{self, read_vec3},
+            helpers::{read_vec3, write_vec3},
             kinds::shared::{Matrix, Vector3},
             parse_trait::Parse,
+            schema::chunk_struct,
+            write_trait::Write,
         },
         type_identifiers::ChunkType,
     },
     Result,
 };
-use bytes::Bytes;
+use bytes::{BufMut, Bytes};
 use num_enum::{IntoPrimitive, TryFromPrimitive};
 use serde::{Deserialize, Serialize};
 
@@ -26,11 +28,11 @@ pub struct WBLocator {
 
 impl Parse for WBLocator {
     fn parse(bytes: &mut Bytes, _: ChunkType) -> Result<Self> {
-        let typ = WBLocatorType::try_from(bytes.safe_get_u32_le()?)?;
-        let num_data_elements = bytes.safe_get_u32_le()?;
-        let mut data = Vec::with_capacity(num_data_elements as usize);
+        let typ = WBLocatorType::try_from(bytes.safe_get_u32()?)?;
+        let num_data_elements = bytes.safe_get_u32()?;
+        let mut data = Vec::with_capacity(bytes.safe_count(num_data_elements as usize, 4)?);
         for _ in 0..num_data_elements {
-            data.push(bytes.safe_get_u32_le()?);
+            data.push(bytes.safe_get_u32()?);
         }
 
         Ok(WBLocator {
@@ -38,11 +40,23 @@ impl Parse for WBLocator {
             num_data_elements,
             data,
             position: read_vec3(bytes)?,
-            num_triggers: bytes.safe_get_u32_le()?,
+            num_triggers: bytes.safe_get_u32()?,
         })
     }
 }
 
+impl Write for WBLocator {
+    fn write(&self, bytes: &mut Vec<u8>, _: ChunkType) {
+        bytes.put_u32_le(self.typ.clone().into());
+        bytes.put_u32_le(self.data.len() as u32);
+        for x in &self.data {
+            bytes.put_u32_le(*x);
+        }
+        write_vec3(bytes, &self.position);
+        bytes.put_u32_le(self.num_triggers);
+    }
+}
+
 #[derive(
     Clone,
     Debug,
@@ -76,33 +90,17 @@ pub enum WBLocatorType {
     SpawnPoint = 15,
 }
 
-#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
-pub struct WBTriggerVolume {
-    pub typ: u32,
-    pub scale: Vector3,
-    pub matrix: Matrix,
-}
-
-impl Parse for WBTriggerVolume {
-    fn parse(bytes: &mut Bytes, _typ: ChunkType) -> Result<Self> {
-        Ok(WBTriggerVolume {
-            typ: bytes.safe_get_u32_le()?,
-            scale: read_vec3(bytes)?,
-            matrix: helpers::read_matrix(bytes)?,
-        })
+chunk_struct! {
+    pub struct WBTriggerVolume {
+        pub typ: u32,
+        pub scale: Vector3,
+        pub matrix: Matrix,
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
-pub struct WBMatrix {
-    pub matrix: Matrix,
-}
-
-impl Parse for WBMatrix {
-    fn parse(bytes: &mut Bytes, _typ: ChunkType) -> Result<Self> {
-        Ok(WBMatrix {
-            matrix: helpers::read_matrix(bytes)?,
-        })
+chunk_struct! {
+    pub struct WBMatrix {
+        pub matrix: Matrix,
     }
 }
 
@@ -116,8 +114,8 @@ pub struct WBSpline {
 impl Parse for WBSpline {
     #[allow(non_snake_case)]
     fn parse(bytes: &mut Bytes, _: ChunkType) -> Result<Self> {
-        let num_CVs = bytes.safe_get_u32_le()?;
-        let mut CVs = Vec::with_capacity(num_CVs as usize);
+        let num_CVs = bytes.safe_get_u32()?;
+        let mut CVs = Vec::with_capacity(bytes.safe_count(num_CVs as usize, 12)?);
         for _ in 0..num_CVs {
             CVs.push(read_vec3(bytes)?);
         }
@@ -125,6 +123,15 @@ impl Parse for WBSpline {
     }
 }
 
+impl Write for WBSpline {
+    fn write(&self, bytes: &mut Vec<u8>, _: ChunkType) {
+        bytes.put_u32_le(self.CVs.len() as u32);
+        for x in &self.CVs {
+            write_vec3(bytes, x);
+        }
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 pub struct WBRail {
@@ -145,17 +152,33 @@ impl Parse for WBRail {
     #[allow(non_snake_case)]
     fn parse(bytes: &mut Bytes, _: ChunkType) -> Result<Self> {
         Ok(WBRail {
-            behavior: bytes.safe_get_u32_le()?,
-            min_radius: bytes.safe_get_f32_le()?,
-            max_radius: bytes.safe_get_f32_le()?,
-            track_rail: bytes.safe_get_u32_le()?,
-            track_dist: bytes.safe_get_f32_le()?,
-            reverse_sense: bytes.safe_get_u32_le()?,
-            fov: bytes.safe_get_f32_le()?,
+            behavior: bytes.safe_get_u32()?,
+            min_radius: bytes.safe_get_f32()?,
+            max_radius: bytes.safe_get_f32()?,
+            track_rail: bytes.safe_get_u32()?,
+            track_dist: bytes.safe_get_f32()?,
+            reverse_sense: bytes.safe_get_u32()?,
+            fov: bytes.safe_get_f32()?,
             target_offset: read_vec3(bytes)?,
             axis_play: read_vec3(bytes)?,
-            position_lag: bytes.safe_get_f32_le()?,
-            target_lag: bytes.safe_get_f32_le()?,
+            position_lag: bytes.safe_get_f32()?,
+            target_lag: bytes.safe_get_f32()?,
         })
     }
 }
+
+impl Write for WBRail {
+    fn write(&self, bytes: &mut Vec<u8>, _: ChunkType) {
+        bytes.put_u32_le(self.behavior);
+        bytes.put_f32_le(self.min_radius);
+        bytes.put_f32_le(self.max_radius);
+        bytes.put_u32_le(self.track_rail);
+        bytes.put_f32_le(self.track_dist);
+        bytes.put_u32_le(self.reverse_sense);
+        bytes.put_f32_le(self.fov);
+        write_vec3(bytes, &self.target_offset);
+        write_vec3(bytes, &self.axis_play);
+        bytes.put_f32_le(self.position_lag);
+        bytes.put_f32_le(self.target_lag);
+    }
+}
@@ -1,45 +1,39 @@
+// modular-bitfield expands to parenthesized types, which newer compilers complain about
+#![allow(unused_parens)]
 use crate::{
-    bytes_ext::BufResult,
+    bytes_ext::{BufResult, BytesExt},
     chunk::{
         data::{
-            helpers::{self, pure3d_read_string, read_colour, read_vec2, read_vec3},
-            kinds::shared::{Colour, Vector2, Vector3},
+            helpers::{pure3d_read_string, pure3d_write_string, read_vec2, write_vec2},
+            kinds::{
+                pure3d_string::Pure3DString,
+                shared::{Colour, Vector2, Vector3},
+            },
             parse_trait::Parse,
+            schema::chunk_struct,
+            write_trait::Write,
         },
         type_identifiers::ChunkType,
     },
     Result,
 };
-use bytes::Bytes;
+use bytes::{BufMut, Bytes};
 use modular_bitfield::prelude::*;
 use num_enum::{IntoPrimitive, TryFromPrimitive};
 use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
-pub struct Mesh {
-    pub num_prim_groups: u32,
-}
-
-impl Parse for Mesh {
-    fn parse(bytes: &mut Bytes, _: ChunkType) -> Result<Self> {
-        Ok(Mesh {
-            num_prim_groups: bytes.safe_get_u32_le()?,
-        })
+chunk_struct! {
+    #[derive(Eq)]
+    pub struct Mesh {
+        pub num_prim_groups: u32,
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
-pub struct Skin {
-    pub skeleton_name: String,
-    pub num_prim_groups: u32,
-}
-
-impl Parse for Skin {
-    fn parse(bytes: &mut Bytes, _: ChunkType) -> Result<Self> {
-        Ok(Skin {
-            skeleton_name: pure3d_read_string(bytes)?,
-            num_prim_groups: bytes.safe_get_u32_le()?,
-        })
+chunk_struct! {
+    #[derive(Eq)]
+    pub struct Skin {
+        pub skeleton_name: Pure3DString,
+        pub num_prim_groups: u32,
     }
 }
 
@@ -91,6 +85,12 @@ pub struct VertexType {
     unused: B14,
 }
 
+impl Default for VertexType {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Serialize for VertexType {
     fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
     where
@@ -128,7 +128,7 @@ impl<'de> Deserialize<'de> for VertexType {
 
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct OldPrimGroup {
-    pub shader_name: String,
+    pub shader_name: Pure3DString,
     pub primitive_type: PrimitiveType,
     /// Bitfield of [`VertexType`]
     pub vertex_types: VertexType,
@@ -141,30 +141,29 @@ impl Parse for OldPrimGroup {
     fn parse(bytes: &mut Bytes, _: ChunkType) -> Result<Self> {
         Ok(OldPrimGroup {
             shader_name: pure3d_read_string(bytes)?,
-            primitive_type: bytes.safe_get_u32_le()?.try_into()?,
-            vertex_types: bytes.safe_get_u32_le()?.into(),
-            num_vertices: bytes.safe_get_u32_le()?,
-            num_indices: bytes.safe_get_u32_le()?,
-            num_matrices: bytes.safe_get_u32_le()?,
+            primitive_type: bytes.safe_get_u32()?.try_into()?,
+            vertex_types: bytes.safe_get_u32()?.into(),
+            num_vertices: bytes.safe_get_u32()?,
+            num_indices: bytes.safe_get_u32()?,
+            num_matrices: bytes.safe_get_u32()?,
         })
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
-pub struct PositionList {
-    pub positions: Vec<Vector3>,
+impl Write for OldPrimGroup {
+    fn write(&self, bytes: &mut Vec<u8>, _: ChunkType) {
+        pure3d_write_string(bytes, &self.shader_name);
+        bytes.put_u32_le(self.primitive_type.into());
+        bytes.put_u32_le(self.vertex_types.clone().into());
+        bytes.put_u32_le(self.num_vertices);
+        bytes.put_u32_le(self.num_indices);
+        bytes.put_u32_le(self.num_matrices);
+    }
 }
 
-impl Parse for PositionList {
-    fn parse(bytes: &mut Bytes, _: ChunkType) -> Result<Self> {
-        let capacity = bytes.safe_get_u32_le()? as usize;
-
-        let mut positions = Vec::with_capacity(capacity);
-        for _ in 0..capacity {
-            positions.push(read_vec3(bytes)?);
-        }
-
-        Ok(PositionList { positions })
+chunk_struct! {
+    pub struct PositionList {
+        pub positions: Vec<Vector3>,
     }
 }
 
@@ -177,11 +176,11 @@ pub struct UVList {
 
 impl Parse for UVList {
     fn parse(bytes: &mut Bytes, _: ChunkType) -> Result<Self> {
-        let capacity = bytes.safe_get_u32_le()? as usize;
-        let channel = bytes.safe_get_u32_le()?;
+        let capacity = bytes.safe_get_u32()? as usize;
+        let channel = bytes.safe_get_u32()?;
 
         #[allow(non_snake_case)]
-        let mut UVs = Vec::with_capacity(capacity);
+        let mut UVs = Vec::with_capacity(bytes.safe_count(capacity, 8)?);
         for _ in 0..capacity {
             UVs.push(read_vec2(bytes)?);
         }
@@ -190,288 +189,153 @@ impl Parse for UVList {
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
-#[allow(non_snake_case)]
-pub struct NormalList {
-    pub normals: Vec<Vector3>,
-}
-
-impl Parse for NormalList {
-    fn parse(bytes: &mut Bytes, _: ChunkType) -> Result<Self> {
-        let capacity = bytes.safe_get_u32_le()? as usize;
-
-        let mut normals = Vec::with_capacity(capacity);
-        for _ in 0..capacity {
-            normals.push(read_vec3(bytes)?);
+impl Write for UVList {
+    fn write(&self, bytes: &mut Vec<u8>, _: ChunkType) {
+        bytes.put_u32_le(self.UVs.len() as u32);
+        bytes.put_u32_le(self.channel);
+        for x in &self.UVs {
+            write_vec2(bytes, x);
         }
-
-        Ok(NormalList { normals })
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
-#[allow(non_snake_case)]
-pub struct TangentList {
-    pub tangents: Vec<Vector3>,
-}
-
-impl Parse for TangentList {
-    fn parse(bytes: &mut Bytes, _: ChunkType) -> Result<Self> {
-        let capacity = bytes.safe_get_u32_le()? as usize;
-
-        let mut tangents = Vec::with_capacity(capacity);
-        for _ in 0..capacity {
-            tangents.push(read_vec3(bytes)?);
-        }
-
-        Ok(TangentList { tangents })
+chunk_struct! {
+    #[allow(non_snake_case)]
+    pub struct NormalList {
+        pub normals: Vec<Vector3>,
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
-#[allow(non_snake_case)]
-pub struct BinormalList {
-    pub binormals: Vec<Vector3>,
+chunk_struct! {
+    #[allow(non_snake_case)]
+    pub struct TangentList {
+        pub tangents: Vec<Vector3>,
+    }
 }
 
-impl Parse for BinormalList {
-    fn parse(bytes: &mut Bytes, _: ChunkType) -> Result<Self> {
-        let capacity = bytes.safe_get_u32_le()? as usize;
-
-        let mut binormals = Vec::with_capacity(capacity);
-        for _ in 0..capacity {
-            binormals.push(read_vec3(bytes)?);
-        }
-
-        Ok(BinormalList { binormals })
+chunk_struct! {
+    #[allow(non_snake_case)]
+    pub struct BinormalList {
+        pub binormals: Vec<Vector3>,
     }
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 pub struct PackedNormalList {
-    pub normals: Vec<u8>,
+    pub normals: Bytes,
 }
 
 impl Parse for PackedNormalList {
     fn parse(bytes: &mut Bytes, _: ChunkType) -> Result<Self> {
-        let capacity = bytes.safe_get_u32_le()? as usize;
-
-        let mut normals = Vec::with_capacity(capacity);
-        for _ in 0..capacity {
-            normals.push(bytes.safe_get_u8()?);
-        }
+        let capacity = bytes.safe_get_u32()? as usize;
 
-        Ok(PackedNormalList { normals })
+        Ok(PackedNormalList {
+            normals: bytes.safe_split_to(capacity)?,
+        })
     }
 }
-#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
-pub struct ColourList {
-    pub colours: Vec<Colour>,
-}
 
-impl Parse for ColourList {
-    fn parse(bytes: &mut Bytes, _: ChunkType) -> Result<Self> {
-        let capacity = bytes.safe_get_u32_le()? as usize;
-
-        let mut colours = Vec::with_capacity(capacity);
-        for _ in 0..capacity {
-            colours.push(read_colour(bytes)?);
-        }
-
-        Ok(ColourList { colours })
+impl Write for PackedNormalList {
+    fn write(&self, bytes: &mut Vec<u8>, _: ChunkType) {
+        bytes.put_u32_le(self.normals.len() as u32);
+        bytes.put_slice(&self.normals);
     }
 }
-
-#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
-pub struct IndexList {
-    pub indices: Vec<u32>,
-}
-
-impl Parse for IndexList {
-    fn parse(bytes: &mut Bytes, _: ChunkType) -> Result<Self> {
-        let capacity = bytes.safe_get_u32_le()? as usize;
-
-        let mut indices = Vec::with_capacity(capacity);
-        for _ in 0..capacity {
-            indices.push(bytes.safe_get_u32_le()?);
-        }
-
-        Ok(IndexList { indices })
+chunk_struct! {
+    #[derive(Eq)]
+    pub struct ColourList {
+        pub colours: Vec<Colour>,
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
-pub struct MatrixList {
-    pub matrices: Vec<Colour>,
-}
-
-impl Parse for MatrixList {
-    fn parse(bytes: &mut Bytes, _: ChunkType) -> Result<Self> {
-        let capacity = bytes.safe_get_u32_le()? as usize;
-
-        let mut matrices = Vec::with_capacity(capacity);
-        for _ in 0..capacity {
-            matrices.push(helpers::read_colour(bytes)?);
-        }
-
-        Ok(MatrixList { matrices })
+chunk_struct! {
+    #[derive(Eq)]
+    pub struct IndexList {
+        pub indices: Vec<u32>,
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
-pub struct MatrixPalette {
-    pub matrices: Vec<u32>,
-}
-
-impl Parse for MatrixPalette {
-    fn parse(bytes: &mut Bytes, _: ChunkType) -> Result<Self> {
-        let capacity = bytes.safe_get_u32_le()? as usize;
-
-        let mut matrices = Vec::with_capacity(capacity);
-        for _ in 0..capacity {
-            matrices.push(bytes.safe_get_u32_le()?);
-        }
-
-        Ok(MatrixPalette { matrices })
+chunk_struct! {
+    #[derive(Eq)]
+    pub struct MatrixList {
+        pub matrices: Vec<Colour>,
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
-pub struct WeightList {
-    pub weights: Vec<Vector3>,
-}
-
-impl Parse for WeightList {
-    fn parse(bytes: &mut Bytes, _: ChunkType) -> Result<Self> {
-        let capacity = bytes.safe_get_u32_le()? as usize;
-
-        let mut weights = Vec::with_capacity(capacity);
-        for _ in 0..capacity {
-            weights.push(read_vec3(bytes)?);
-        }
-
-        Ok(WeightList { weights })
+chunk_struct! {
+    #[derive(Eq)]
+    pub struct MatrixPalette {
+        pub matrices: Vec<u32>,
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
-pub struct RenderStatus {
-    pub cast_shadow: u32,
-}
-
-impl Parse for RenderStatus {
-    fn parse(bytes: &mut Bytes, _: ChunkType) -> Result<Self> {
-        Ok(RenderStatus {
-            cast_shadow: bytes.safe_get_u32_le()?,
-        })
+chunk_struct! {
+    pub struct WeightList {
+        pub weights: Vec<Vector3>,
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
-pub struct CompositeDrawable {
-    pub skeleton_name: String,
-}
-
-impl Parse for CompositeDrawable {
-    fn parse(bytes: &mut Bytes, _: ChunkType) -> Result<Self> {
-        Ok(CompositeDrawable {
-            skeleton_name: pure3d_read_string(bytes)?,
-        })
+chunk_struct! {
+    #[derive(Eq)]
+    pub struct RenderStatus {
+        pub cast_shadow: u32,
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
-pub struct CompositeDrawableSkinList {
-    pub num_elements: u32,
-}
-
-impl Parse for CompositeDrawableSkinList {
-    fn parse(bytes: &mut Bytes, _: ChunkType) -> Result<Self> {
-        Ok(CompositeDrawableSkinList {
-            num_elements: bytes.safe_get_u32_le()?,
-        })
+chunk_struct! {
+    #[derive(Eq)]
+    pub struct CompositeDrawable {
+        pub skeleton_name: Pure3DString,
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
-pub struct CompositeDrawableSkin {
-    pub is_translucent: u32,
-}
-
-impl Parse for CompositeDrawableSkin {
-    fn parse(bytes: &mut Bytes, _: ChunkType) -> Result<Self> {
-        Ok(CompositeDrawableSkin {
-            is_translucent: bytes.safe_get_u32_le()?,
-        })
+chunk_struct! {
+    #[derive(Eq)]
+    pub struct CompositeDrawableSkinList {
+        pub num_elements: u32,
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
-pub struct CompositeDrawablePropList {
-    pub num_elements: u32,
-}
-
-impl Parse for CompositeDrawablePropList {
-    fn parse(bytes: &mut Bytes, _: ChunkType) -> Result<Self> {
-        Ok(CompositeDrawablePropList {
-            num_elements: bytes.safe_get_u32_le()?,
-        })
+chunk_struct! {
+    #[derive(Eq)]
+    pub struct CompositeDrawableSkin {
+        pub is_translucent: u32,
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
-pub struct CompositeDrawableProp {
-    pub is_translucent: u32,
-    pub skeleton_joint_id: u32,
-}
-
-impl Parse for CompositeDrawableProp {
-    fn parse(bytes: &mut Bytes, _: ChunkType) -> Result<Self> {
-        Ok(CompositeDrawableProp {
-            is_translucent: bytes.safe_get_u32_le()?,
-            skeleton_joint_id: bytes.safe_get_u32_le()?,
-        })
+chunk_struct! {
+    #[derive(Eq)]
+    pub struct CompositeDrawablePropList {
+        pub num_elements: u32,
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
-pub struct CompositeDrawableEffectList {
-    pub num_elements: u32,
-}
-
-impl Parse for CompositeDrawableEffectList {
-    fn parse(bytes: &mut Bytes, _: ChunkType) -> Result<Self> {
-        Ok(CompositeDrawableEffectList {
-            num_elements: bytes.safe_get_u32_le()?,
-        })
+chunk_struct! {
+    #[derive(Eq)]
+    pub struct CompositeDrawableProp {
+        pub is_translucent: u32,
+        pub skeleton_joint_id: u32,
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
-pub struct CompositeDrawableEffect {
-    pub is_translucent: u32,
-    pub skeleton_joint_id: u32,
-}
-
-impl Parse for CompositeDrawableEffect {
-    fn parse(bytes: &mut Bytes, _: ChunkType) -> Result<Self> {
-        Ok(CompositeDrawableEffect {
-            is_translucent: bytes.safe_get_u32_le()?,
-            skeleton_joint_id: bytes.safe_get_u32_le()?,
-        })
+chunk_struct! {
+    #[derive(Eq)]
+    pub struct CompositeDrawableEffectList {
+        pub num_elements: u32,
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
-pub struct CompositeDrawableSortOrder {
-    pub sort_order: f32,
+chunk_struct! {
+    #[derive(Eq)]
+    pub struct CompositeDrawableEffect {
+        pub is_translucent: u32,
+        pub skeleton_joint_id: u32,
+    }
 }
 
-impl Parse for CompositeDrawableSortOrder {
-    fn parse(bytes: &mut Bytes, _: ChunkType) -> Result<Self> {
-        Ok(CompositeDrawableSortOrder {
-            sort_order: bytes.safe_get_f32_le()?,
-        })
+chunk_struct! {
+    pub struct CompositeDrawableSortOrder {
+        pub sort_order: f32,
     }
 }
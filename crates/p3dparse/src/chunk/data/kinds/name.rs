@@ -1,6 +1,11 @@
 use crate::{
     chunk::{
-        data::{helpers::pure3d_read_string, parse_trait::Parse},
+        data::{
+            helpers::{pure3d_read_string, pure3d_write_string},
+            kinds::pure3d_string::Pure3DString,
+            parse_trait::Parse,
+            write_trait::Write,
+        },
         type_identifiers::ChunkType,
     },
     Result,
@@ -9,7 +14,7 @@ use bytes::Bytes;
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
-pub struct Name(pub String);
+pub struct Name(pub Pure3DString);
 
 impl Parse for Name {
     fn parse(bytes: &mut Bytes, _: ChunkType) -> Result<Self> {
@@ -17,3 +22,9 @@ impl Parse for Name {
         Ok(Name(name))
     }
 }
+
+impl Write for Name {
+    fn write(&self, bytes: &mut Vec<u8>, _: ChunkType) {
+        pure3d_write_string(bytes, &self.0);
+    }
+}
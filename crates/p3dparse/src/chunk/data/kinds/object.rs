@@ -2,94 +2,58 @@ use crate::{
     bytes_ext::BufResult,
     chunk::{
         data::{
-            helpers::{pure3d_read_fourcc, pure3d_read_string},
+            kinds::pure3d_string::Pure3DString,
             parse_trait::Parse,
+            schema::{chunk_struct, FourCC},
+            write_trait::Write,
         },
         type_identifiers::ChunkType,
     },
     Result,
 };
-use bytes::Bytes;
+use bytes::{BufMut, Bytes};
 use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
-pub struct AnimatedObjectFactory {
-    pub factory_name: String,
-    pub num_animations: u32,
-}
-
-impl Parse for AnimatedObjectFactory {
-    fn parse(bytes: &mut Bytes, _: ChunkType) -> Result<Self> {
-        Ok(AnimatedObjectFactory {
-            factory_name: pure3d_read_string(bytes)?,
-            num_animations: bytes.safe_get_u32_le()?,
-        })
+chunk_struct! {
+    #[derive(Eq, PartialOrd, Ord)]
+    pub struct AnimatedObjectFactory {
+        pub factory_name: Pure3DString,
+        pub num_animations: u32,
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
-pub struct AnimatedObject {
-    pub factory_name: String,
-    pub starting_animation: u32,
-}
-
-impl Parse for AnimatedObject {
-    fn parse(bytes: &mut Bytes, _: ChunkType) -> Result<Self> {
-        Ok(AnimatedObject {
-            factory_name: pure3d_read_string(bytes)?,
-            starting_animation: bytes.safe_get_u32_le()?,
-        })
+chunk_struct! {
+    #[derive(Eq, PartialOrd, Ord)]
+    pub struct AnimatedObject {
+        pub factory_name: Pure3DString,
+        pub starting_animation: u32,
     }
 }
 
-#[derive(Clone, Debug, PartialEq, PartialOrd, Serialize, Deserialize)]
-pub struct AnimatedObjectAnimation {
-    pub frame_rate: f32,
-    pub num_old_frame_controllers: u32,
-}
-
-impl Parse for AnimatedObjectAnimation {
-    fn parse(bytes: &mut Bytes, _: ChunkType) -> Result<Self> {
-        Ok(AnimatedObjectAnimation {
-            frame_rate: bytes.safe_get_f32_le()?,
-            num_old_frame_controllers: bytes.safe_get_u32_le()?,
-        })
+chunk_struct! {
+    #[derive(PartialOrd)]
+    pub struct AnimatedObjectAnimation {
+        pub frame_rate: f32,
+        pub num_old_frame_controllers: u32,
     }
 }
 
-#[derive(Clone, Debug, PartialEq, PartialOrd, Serialize, Deserialize)]
-pub struct OldFrameController {
-    pub type2: String,
-    pub frame_offset: f32,
-    pub hierarchy_name: String,
-    pub animation_name: String,
-}
-
-impl Parse for OldFrameController {
-    fn parse(bytes: &mut Bytes, _: ChunkType) -> Result<Self> {
-        Ok(OldFrameController {
-            type2: pure3d_read_fourcc(bytes)?,
-            frame_offset: bytes.safe_get_f32_le()?,
-            hierarchy_name: pure3d_read_string(bytes)?,
-            animation_name: pure3d_read_string(bytes)?,
-        })
+chunk_struct! {
+    #[derive(PartialOrd)]
+    pub struct OldFrameController {
+        pub type2: String as FourCC,
+        pub frame_offset: f32,
+        pub hierarchy_name: Pure3DString,
+        pub animation_name: Pure3DString,
     }
 }
 
-#[derive(Clone, Debug, PartialEq, PartialOrd, Serialize, Deserialize)]
-pub struct MultiController {
-    pub length: f32,
-    pub frame_rate: f32,
-    pub num_tracks: u32,
-}
-
-impl Parse for MultiController {
-    fn parse(bytes: &mut Bytes, _: ChunkType) -> Result<Self> {
-        Ok(MultiController {
-            length: bytes.safe_get_f32_le()?,
-            frame_rate: bytes.safe_get_f32_le()?,
-            num_tracks: bytes.safe_get_u32_le()?,
-        })
+chunk_struct! {
+    #[derive(PartialOrd)]
+    pub struct MultiController {
+        pub length: f32,
+        pub frame_rate: f32,
+        pub num_tracks: u32,
     }
 }
 
@@ -100,9 +64,9 @@ pub struct MultiControllerTracks {
 
 impl Parse for MultiControllerTracks {
     fn parse(bytes: &mut Bytes, typ: ChunkType) -> Result<Self> {
-        let capacity = bytes.safe_get_u32_le()? as usize;
+        let capacity = bytes.safe_get_u32()? as usize;
 
-        let mut tracks = Vec::with_capacity(capacity);
+        let mut tracks = Vec::with_capacity(bytes.safe_count(capacity, 13)?);
         for _ in 0..capacity {
             tracks.push(MultiControllerTrack::parse(bytes, typ)?);
         }
@@ -111,49 +75,36 @@ impl Parse for MultiControllerTracks {
     }
 }
 
-#[derive(Clone, Debug, PartialEq, PartialOrd, Serialize, Deserialize)]
-pub struct MultiControllerTrack {
-    pub name: String,
-    pub start_time: f32,
-    pub end_time: f32,
-    pub scale: f32,
-}
-
-impl Parse for MultiControllerTrack {
-    fn parse(bytes: &mut Bytes, _: ChunkType) -> Result<Self> {
-        Ok(MultiControllerTrack {
-            name: pure3d_read_string(bytes)?,
-            start_time: bytes.safe_get_f32_le()?,
-            end_time: bytes.safe_get_f32_le()?,
-            scale: bytes.safe_get_f32_le()?,
-        })
+impl Write for MultiControllerTracks {
+    fn write(&self, bytes: &mut Vec<u8>, typ: ChunkType) {
+        bytes.put_u32_le(self.tracks.len() as u32);
+        for track in &self.tracks {
+            track.write(bytes, typ);
+        }
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
-pub struct ObjectDSG {
-    pub render_order: u32,
-}
-
-impl Parse for ObjectDSG {
-    fn parse(bytes: &mut Bytes, _: ChunkType) -> Result<Self> {
-        Ok(ObjectDSG {
-            render_order: bytes.safe_get_u32_le()?,
-        })
+chunk_struct! {
+    #[derive(PartialOrd)]
+    pub struct MultiControllerTrack {
+        pub name: Pure3DString,
+        pub start_time: f32,
+        pub end_time: f32,
+        pub scale: f32,
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
-pub struct AnimatedObjectDSGWrapper {
-    pub version: u8,
-    pub has_alpha: u8,
+chunk_struct! {
+    #[derive(Eq, PartialOrd, Ord)]
+    pub struct ObjectDSG {
+        pub render_order: u32,
+    }
 }
 
-impl Parse for AnimatedObjectDSGWrapper {
-    fn parse(bytes: &mut Bytes, _: ChunkType) -> Result<Self> {
-        Ok(AnimatedObjectDSGWrapper {
-            version: bytes.safe_get_u8()?,
-            has_alpha: bytes.safe_get_u8()?,
-        })
+chunk_struct! {
+    #[derive(Eq, PartialOrd, Ord)]
+    pub struct AnimatedObjectDSGWrapper {
+        pub version: u8,
+        pub has_alpha: u8,
     }
 }
@@ -1,36 +1,14 @@
-use crate::{
-    bytes_ext::BufResult,
-    chunk::{
-        data::{helpers::read_vec3, kinds::shared::Vector3, parse_trait::Parse},
-        type_identifiers::ChunkType,
-    },
-    Result,
-};
-use bytes::Bytes;
-use serde::{Deserialize, Serialize};
+use crate::chunk::data::{kinds::shared::Vector3, schema::chunk_struct};
 
-#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
-#[allow(non_snake_case)]
-pub struct Camera {
-    pub FOV: f32,
-    pub aspect_ratio: f32,
-    pub near_clip: f32,
-    pub far_clip: f32,
-    pub position: Vector3,
-    pub look: Vector3,
-    pub up: Vector3,
-}
-
-impl Parse for Camera {
-    fn parse(bytes: &mut Bytes, _: ChunkType) -> Result<Self> {
-        Ok(Camera {
-            FOV: bytes.safe_get_f32_le()?,
-            aspect_ratio: bytes.safe_get_f32_le()?,
-            near_clip: bytes.safe_get_f32_le()?,
-            far_clip: bytes.safe_get_f32_le()?,
-            position: read_vec3(bytes)?,
-            look: read_vec3(bytes)?,
-            up: read_vec3(bytes)?,
-        })
+chunk_struct! {
+    #[allow(non_snake_case)]
+    pub struct Camera {
+        pub FOV: f32,
+        pub aspect_ratio: f32,
+        pub near_clip: f32,
+        pub far_clip: f32,
+        pub position: Vector3,
+        pub look: Vector3,
+        pub up: Vector3,
     }
 }
@@ -0,0 +1,186 @@
+use std::{
+    borrow::Borrow,
+    cmp::Ordering,
+    fmt::{Debug, Display},
+    hash::{Hash, Hasher},
+    ops::Deref,
+};
+
+use bytes::Bytes;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// A length prefixed, null padded string, as names and paths are stored.
+///
+/// Files don't agree on how much padding there is, and some strings have Latin-1 in them, so the
+/// bytes that were read are kept and written back as they were. A string that's built instead of
+/// read is written as Latin-1, with `?` for anything Latin-1 can't hold, padded out to a multiple
+/// of 4 bytes. Either way it compares, hashes and serializes as just the string.
+#[derive(Clone, Default)]
+pub struct Pure3DString {
+    string: String,
+    /// Everything after the length prefix, padding included
+    raw: Option<Bytes>,
+}
+
+impl Pure3DString {
+    /// Decodes the bytes after the length prefix. Nulls are padding wherever they are.
+    pub fn from_raw(raw: Bytes) -> Self {
+        Pure3DString {
+            string: raw
+                .iter()
+                .filter(|byte| **byte != 0)
+                .map(|byte| *byte as char)
+                .collect(),
+            raw: Some(raw),
+        }
+    }
+
+    /// The bytes that will be written after the length prefix.
+    pub fn raw(&self) -> Bytes {
+        if let Some(raw) = &self.raw {
+            return raw.clone();
+        }
+
+        let mut raw: Vec<u8> = self
+            .string
+            .chars()
+            .map(|c| u8::try_from(c).unwrap_or(b'?'))
+            .take(u8::MAX as usize)
+            .collect();
+        let padded_len = ((raw.len() + 3) & !3).min(u8::MAX as usize);
+        raw.resize(padded_len, 0);
+        raw.into()
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.string
+    }
+
+    pub fn into_string(self) -> String {
+        self.string
+    }
+}
+
+impl From<String> for Pure3DString {
+    fn from(string: String) -> Self {
+        Pure3DString { string, raw: None }
+    }
+}
+
+impl From<&str> for Pure3DString {
+    fn from(string: &str) -> Self {
+        string.to_owned().into()
+    }
+}
+
+impl From<Pure3DString> for String {
+    fn from(string: Pure3DString) -> Self {
+        string.string
+    }
+}
+
+impl Deref for Pure3DString {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.string
+    }
+}
+
+impl AsRef<str> for Pure3DString {
+    fn as_ref(&self) -> &str {
+        &self.string
+    }
+}
+
+impl Borrow<str> for Pure3DString {
+    fn borrow(&self) -> &str {
+        &self.string
+    }
+}
+
+impl Display for Pure3DString {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(&self.string, f)
+    }
+}
+
+impl Debug for Pure3DString {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Debug::fmt(&self.string, f)
+    }
+}
+
+impl PartialEq for Pure3DString {
+    fn eq(&self, other: &Self) -> bool {
+        self.string == other.string
+    }
+}
+
+impl Eq for Pure3DString {}
+
+impl PartialEq<str> for Pure3DString {
+    fn eq(&self, other: &str) -> bool {
+        self.string == other
+    }
+}
+
+impl PartialEq<&str> for Pure3DString {
+    fn eq(&self, other: &&str) -> bool {
+        self.string == *other
+    }
+}
+
+impl PartialEq<String> for Pure3DString {
+    fn eq(&self, other: &String) -> bool {
+        self.string == *other
+    }
+}
+
+impl PartialEq<Pure3DString> for str {
+    fn eq(&self, other: &Pure3DString) -> bool {
+        self == other.string
+    }
+}
+
+impl PartialEq<Pure3DString> for &str {
+    fn eq(&self, other: &Pure3DString) -> bool {
+        *self == other.string
+    }
+}
+
+impl PartialEq<Pure3DString> for String {
+    fn eq(&self, other: &Pure3DString) -> bool {
+        *self == other.string
+    }
+}
+
+impl PartialOrd for Pure3DString {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Pure3DString {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.string.cmp(&other.string)
+    }
+}
+
+impl Hash for Pure3DString {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.string.hash(state);
+    }
+}
+
+impl Serialize for Pure3DString {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.string.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Pure3DString {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer).map(Pure3DString::from)
+    }
+}
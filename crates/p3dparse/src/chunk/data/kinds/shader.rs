@@ -1,17 +1,23 @@
 use crate::{
     bytes_ext::BufResult,
     chunk::{
-        data::{helpers, kinds::mesh::VertexType, parse_trait::Parse},
+        data::{
+            helpers,
+            kinds::{mesh::VertexType, pure3d_string::Pure3DString},
+            parse_trait::Parse,
+            schema::chunk_struct,
+            write_trait::Write,
+        },
         type_identifiers::ChunkType,
     },
     Result,
 };
-use bytes::Bytes;
+use bytes::{BufMut, Bytes};
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Shader {
-    pub pddi_shader_name: String,
+    pub pddi_shader_name: Pure3DString,
     pub has_translucency: u32,
     pub vertex_needs: VertexType,
     pub vertex_mask: VertexType,
@@ -22,23 +28,27 @@ impl Parse for Shader {
     fn parse(bytes: &mut Bytes, _: ChunkType) -> Result<Self> {
         Ok(Shader {
             pddi_shader_name: helpers::pure3d_read_string(bytes)?,
-            has_translucency: bytes.safe_get_u32_le()?,
-            vertex_needs: bytes.safe_get_u32_le()?.try_into()?,
-            vertex_mask: bytes.safe_get_u32_le()?.try_into()?,
-            num_params: bytes.safe_get_u32_le()?,
+            has_translucency: bytes.safe_get_u32()?,
+            vertex_needs: bytes.safe_get_u32()?.into(),
+            vertex_mask: bytes.safe_get_u32()?.into(),
+            num_params: bytes.safe_get_u32()?,
         })
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
-pub struct VertexShader {
-    pub vertex_shader_name: String,
+impl Write for Shader {
+    fn write(&self, bytes: &mut Vec<u8>, _: ChunkType) {
+        helpers::pure3d_write_string(bytes, &self.pddi_shader_name);
+        bytes.put_u32_le(self.has_translucency);
+        bytes.put_u32_le(self.vertex_needs.clone().into());
+        bytes.put_u32_le(self.vertex_mask.clone().into());
+        bytes.put_u32_le(self.num_params);
+    }
 }
 
-impl Parse for VertexShader {
-    fn parse(bytes: &mut Bytes, _: ChunkType) -> Result<Self> {
-        Ok(VertexShader {
-            vertex_shader_name: helpers::pure3d_read_string(bytes)?,
-        })
+chunk_struct! {
+    #[derive(Eq, PartialOrd, Ord)]
+    pub struct VertexShader {
+        pub vertex_shader_name: Pure3DString,
     }
 }
@@ -1,13 +1,22 @@
 use crate::{
     bytes_ext::BufResult,
     chunk::{
-        data::{helpers, kinds::shared::Colour, parse_trait::Parse},
+        data::{
+            helpers,
+            kinds::{
+                pure3d_string::Pure3DString,
+                shared::{Colour, Matrix, Vector3},
+            },
+            parse_trait::Parse,
+            write_trait::Write,
+        },
         type_identifiers::ChunkType,
     },
     Result,
 };
-use bytes::Bytes;
+use bytes::{BufMut, Bytes};
 use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
 
 #[derive(Clone, Debug, PartialEq, PartialOrd, Serialize, Deserialize)]
 pub struct ShaderParam {
@@ -19,27 +28,88 @@ impl Parse for ShaderParam {
     fn parse(bytes: &mut Bytes, typ: ChunkType) -> Result<Self> {
         Ok(ShaderParam {
             param: helpers::pure3d_read_fourcc(bytes)?,
+            // No V14 file has turned up to check against, so those chunks are assumed to be laid
+            // out the same as the current ones. Any bytes past that are kept in unparsed_data.
             value: match typ {
-                ChunkType::ShaderTextureParam => {
+                ChunkType::ShaderTextureParam | ChunkType::P3DV14ShaderTextureParam => {
                     ShaderParamValue::Texture(helpers::pure3d_read_string(bytes)?)
                 }
-                ChunkType::ShaderIntParam => ShaderParamValue::Int(bytes.safe_get_u32_le()?),
-                ChunkType::ShaderFloatParam => ShaderParamValue::Float(bytes.safe_get_f32_le()?),
-                ChunkType::ShaderColourParam => {
+                ChunkType::ShaderIntParam | ChunkType::P3DV14ShaderIntParam => {
+                    ShaderParamValue::Int(bytes.safe_get_u32()?)
+                }
+                ChunkType::ShaderFloatParam | ChunkType::P3DV14ShaderFloatParam => {
+                    ShaderParamValue::Float(bytes.safe_get_f32()?)
+                }
+                ChunkType::ShaderColourParam | ChunkType::P3DV14ShaderColourParam => {
                     ShaderParamValue::Colour(helpers::read_colour(bytes)?)
                 }
+                ChunkType::ShaderVectorParam | ChunkType::P3DV14ShaderVectorParam => {
+                    ShaderParamValue::Vector(helpers::read_vec3(bytes)?)
+                }
+                ChunkType::ShaderMatrixParam | ChunkType::P3DV14ShaderMatrixParam => {
+                    ShaderParamValue::Matrix(helpers::read_matrix(bytes)?)
+                }
                 _ => ShaderParamValue::None,
             },
         })
     }
 }
 
-#[derive(Clone, Debug, PartialEq, PartialOrd, Serialize, Deserialize)]
+impl Write for ShaderParam {
+    fn write(&self, bytes: &mut Vec<u8>, _: ChunkType) {
+        helpers::pure3d_write_fourcc(bytes, &self.param);
+        match &self.value {
+            ShaderParamValue::Texture(texture) => helpers::pure3d_write_string(bytes, texture),
+            ShaderParamValue::Int(int) => bytes.put_u32_le(*int),
+            ShaderParamValue::Float(float) => bytes.put_f32_le(*float),
+            ShaderParamValue::Colour(colour) => helpers::write_colour(bytes, colour),
+            ShaderParamValue::Vector(vector) => helpers::write_vec3(bytes, vector),
+            ShaderParamValue::Matrix(matrix) => helpers::write_matrix(bytes, matrix),
+            ShaderParamValue::None => {}
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub enum ShaderParamValue {
-    Texture(String),
+    Texture(Pure3DString),
     Int(u32),
     Float(f32),
     /// ARGB
     Colour(Colour),
+    Vector(Vector3),
+    Matrix(Matrix),
     None,
 }
+
+impl ShaderParamValue {
+    /// Position of the variant, so different kinds of value order like a derived `PartialOrd`
+    fn rank(&self) -> u8 {
+        match self {
+            ShaderParamValue::Texture(_) => 0,
+            ShaderParamValue::Int(_) => 1,
+            ShaderParamValue::Float(_) => 2,
+            ShaderParamValue::Colour(_) => 3,
+            ShaderParamValue::Vector(_) => 4,
+            ShaderParamValue::Matrix(_) => 5,
+            ShaderParamValue::None => 6,
+        }
+    }
+}
+
+/// Ordered like a derived `PartialOrd`, except that vectors and matrices, which nalgebra doesn't
+/// order, compare their elements in turn.
+impl PartialOrd for ShaderParamValue {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        use ShaderParamValue::*;
+        match (self, other) {
+            (Texture(a), Texture(b)) => a.partial_cmp(b),
+            (Int(a), Int(b)) => a.partial_cmp(b),
+            (Float(a), Float(b)) => a.partial_cmp(b),
+            (Colour(a), Colour(b)) => a.partial_cmp(b),
+            (Vector(a), Vector(b)) => a.vector.iter().partial_cmp(b.vector.iter()),
+            (Matrix(a), Matrix(b)) => a.matrix().iter().partial_cmp(b.matrix().iter()),
+            _ => self.rank().partial_cmp(&other.rank()),
+        }
+    }
+}
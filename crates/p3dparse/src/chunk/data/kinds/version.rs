@@ -1,9 +1,12 @@
 use crate::{
     bytes_ext::BufResult,
-    chunk::{data::parse_trait::Parse, type_identifiers::ChunkType},
+    chunk::{
+        data::{parse_trait::Parse, write_trait::Write},
+        type_identifiers::ChunkType,
+    },
     Result,
 };
-use bytes::Bytes;
+use bytes::{BufMut, Bytes};
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Deserialize, Serialize)]
@@ -11,6 +14,12 @@ pub struct Version(pub u32);
 
 impl Parse for Version {
     fn parse(bytes: &mut Bytes, _: ChunkType) -> Result<Self> {
-        Ok(Version(bytes.safe_get_u32_le()?))
+        Ok(Version(bytes.safe_get_u32()?))
+    }
+}
+
+impl Write for Version {
+    fn write(&self, bytes: &mut Vec<u8>, _: ChunkType) {
+        bytes.put_u32_le(self.0);
     }
 }
@@ -1,8 +1,29 @@
-use crate::{chunk::type_identifiers::ChunkType, Result};
+use crate::{chunk::type_identifiers::ChunkType, error::Field, Result};
 use bytes::Bytes;
 
 pub trait Parse {
     fn parse(bytes: &mut Bytes, typ: ChunkType) -> Result<Self>
     where
         Self: Sized;
+
+    /// Same as [`Parse::parse`], but any error remembers it happened while reading `field`.
+    fn parse_field(bytes: &mut Bytes, typ: ChunkType, field: &'static str) -> Result<Self>
+    where
+        Self: Sized,
+    {
+        in_field(field, Self::parse(bytes, typ))
+    }
+}
+
+/// Names the field `result` was read for, so a [`ParseError`](crate::error::ParseError) can say
+/// which field failed. Fields read inside other fields end up as a dotted path, like
+/// `data.element.colour`.
+pub fn in_field<T>(field: &'static str, result: Result<T>) -> Result<T> {
+    result.map_err(|mut report| match report.downcast_mut::<Field>() {
+        Some(path) => {
+            path.0.insert(0, field);
+            report
+        }
+        None => report.wrap_err(Field(vec![field])),
+    })
 }
@@ -0,0 +1,273 @@
+//! [`chunk_struct!`], for chunk data that's just a list of fields read one after another.
+
+use bytes::{BufMut, Bytes};
+
+use crate::{
+    bytes_ext::BufResult,
+    chunk::data::{
+        helpers,
+        kinds::{
+            pure3d_string::Pure3DString,
+            shared::{Colour, Matrix, Quaternion, Vector2, Vector3},
+        },
+    },
+    Result,
+};
+
+/// How one field of a [`chunk_struct!`] is stored. `T` is the type the field has in Rust, which is
+/// usually the implementing type itself. Marker types like [`FourCC`] pick a different encoding.
+pub trait Field<T = Self> {
+    /// The fewest bytes one value can take up, used to check list counts before allocating.
+    const MIN_SIZE: usize;
+
+    fn read(bytes: &mut Bytes) -> Result<T>;
+    fn write(value: &T, bytes: &mut Vec<u8>);
+}
+
+macro_rules! primitive_field {
+    ($($typ:ident),*) => {
+        paste::paste! {
+            $(
+                impl Field for $typ {
+                    const MIN_SIZE: usize = std::mem::size_of::<$typ>();
+
+                    fn read(bytes: &mut Bytes) -> Result<$typ> {
+                        bytes.[<safe_get_ $typ>]()
+                    }
+
+                    fn write(value: &$typ, bytes: &mut Vec<u8>) {
+                        bytes.[<put_ $typ _le>](*value);
+                    }
+                }
+            )*
+        }
+    };
+}
+
+primitive_field!(u16, u32, i32, f32);
+
+impl Field for u8 {
+    const MIN_SIZE: usize = 1;
+
+    fn read(bytes: &mut Bytes) -> Result<u8> {
+        bytes.safe_get_u8()
+    }
+
+    fn write(value: &u8, bytes: &mut Vec<u8>) {
+        bytes.put_u8(*value);
+    }
+}
+
+/// A length prefixed, null padded string.
+impl Field for Pure3DString {
+    const MIN_SIZE: usize = 1;
+
+    fn read(bytes: &mut Bytes) -> Result<Pure3DString> {
+        helpers::pure3d_read_string(bytes)
+    }
+
+    fn write(value: &Pure3DString, bytes: &mut Vec<u8>) {
+        helpers::pure3d_write_string(bytes, value);
+    }
+}
+
+/// The same as [`Pure3DString`], for when the exact bytes don't matter.
+impl Field for String {
+    const MIN_SIZE: usize = 1;
+
+    fn read(bytes: &mut Bytes) -> Result<String> {
+        Ok(helpers::pure3d_read_string(bytes)?.into_string())
+    }
+
+    fn write(value: &String, bytes: &mut Vec<u8>) {
+        helpers::pure3d_write_string(bytes, &value.as_str().into());
+    }
+}
+
+/// A string that always takes up 4 bytes, like shader param and texture format names.
+pub struct FourCC;
+
+impl Field<String> for FourCC {
+    const MIN_SIZE: usize = 4;
+
+    fn read(bytes: &mut Bytes) -> Result<String> {
+        helpers::pure3d_read_fourcc(bytes)
+    }
+
+    fn write(value: &String, bytes: &mut Vec<u8>) {
+        helpers::pure3d_write_fourcc(bytes, value);
+    }
+}
+
+impl Field for Vector2 {
+    const MIN_SIZE: usize = 8;
+
+    fn read(bytes: &mut Bytes) -> Result<Vector2> {
+        helpers::read_vec2(bytes)
+    }
+
+    fn write(value: &Vector2, bytes: &mut Vec<u8>) {
+        helpers::write_vec2(bytes, value);
+    }
+}
+
+impl Field for Vector3 {
+    const MIN_SIZE: usize = 12;
+
+    fn read(bytes: &mut Bytes) -> Result<Vector3> {
+        helpers::read_vec3(bytes)
+    }
+
+    fn write(value: &Vector3, bytes: &mut Vec<u8>) {
+        helpers::write_vec3(bytes, value);
+    }
+}
+
+impl Field for Quaternion {
+    const MIN_SIZE: usize = 16;
+
+    fn read(bytes: &mut Bytes) -> Result<Quaternion> {
+        helpers::read_quaternion(bytes)
+    }
+
+    fn write(value: &Quaternion, bytes: &mut Vec<u8>) {
+        helpers::write_quaternion(bytes, value);
+    }
+}
+
+impl Field for Colour {
+    const MIN_SIZE: usize = 4;
+
+    fn read(bytes: &mut Bytes) -> Result<Colour> {
+        helpers::read_colour(bytes)
+    }
+
+    fn write(value: &Colour, bytes: &mut Vec<u8>) {
+        helpers::write_colour(bytes, value);
+    }
+}
+
+impl Field for Matrix {
+    const MIN_SIZE: usize = 64;
+
+    fn read(bytes: &mut Bytes) -> Result<Matrix> {
+        helpers::read_matrix(bytes)
+    }
+
+    fn write(value: &Matrix, bytes: &mut Vec<u8>) {
+        helpers::write_matrix(bytes, value);
+    }
+}
+
+/// A u32 count followed by that many elements.
+impl<T: Field> Field for Vec<T> {
+    const MIN_SIZE: usize = 4;
+
+    fn read(bytes: &mut Bytes) -> Result<Vec<T>> {
+        let count = bytes.safe_get_u32()? as usize;
+
+        let mut list = Vec::with_capacity(bytes.safe_count(count, T::MIN_SIZE)?);
+        for _ in 0..count {
+            list.push(T::read(bytes)?);
+        }
+        Ok(list)
+    }
+
+    fn write(value: &Vec<T>, bytes: &mut Vec<u8>) {
+        bytes.put_u32_le(value.len() as u32);
+        for element in value {
+            T::write(element, bytes);
+        }
+    }
+}
+
+/// Declares a struct along with its [`Parse`](crate::chunk::data::parse_trait::Parse) and
+/// [`Write`](crate::chunk::data::write_trait::Write) impls, reading and writing the fields in the
+/// order they're listed. `Clone`, `Debug`, `PartialEq` and serde are always derived, any other
+/// attributes are passed through.
+///
+/// Every field type needs a [`Field`] impl. `field: Type as Marker` stores it the way `Marker`
+/// says instead, e.g. `name: String as FourCC`. The struct gets a [`Field`] impl of its own, so it
+/// can be nested in another one.
+///
+/// Crates outside p3dparse can use it for their own
+/// [`CustomPayload`](crate::chunk::data::custom::CustomPayload)s, they need `serde` as a dependency
+/// for the derives.
+///
+/// ```ignore
+/// chunk_struct! {
+///     #[derive(Eq)]
+///     pub struct Skin {
+///         pub skeleton_name: String,
+///         pub num_prim_groups: u32,
+///     }
+/// }
+/// ```
+#[macro_export]
+macro_rules! chunk_struct {
+    (@codec $typ:ty) => { $typ };
+    (@codec $typ:ty, $codec:ty) => { $codec };
+
+    (
+        $(#[$meta:meta])*
+        pub struct $name:ident {
+            $(
+                $(#[$field_meta:meta])*
+                pub $field:ident: $typ:ty $(as $codec:ty)?
+            ),* $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Clone, Debug, PartialEq, ::serde::Serialize, ::serde::Deserialize)]
+        pub struct $name {
+            $(
+                $(#[$field_meta])*
+                pub $field: $typ,
+            )*
+        }
+
+        impl $crate::chunk::data::schema::Field for $name {
+            const MIN_SIZE: usize = 0 $(
+                + <$crate::chunk_struct!(@codec $typ $(, $codec)?) as $crate::chunk::data::schema::Field<$typ>>::MIN_SIZE
+            )*;
+
+            fn read(bytes: &mut $crate::Bytes) -> $crate::Result<Self> {
+                Ok($name {
+                    $(
+                        $field: $crate::chunk::data::parse_trait::in_field(
+                            stringify!($field),
+                            <$crate::chunk_struct!(@codec $typ $(, $codec)?) as $crate::chunk::data::schema::Field<$typ>>::read(bytes),
+                        )?,
+                    )*
+                })
+            }
+
+            fn write(value: &Self, bytes: &mut Vec<u8>) {
+                $(
+                    <$crate::chunk_struct!(@codec $typ $(, $codec)?) as $crate::chunk::data::schema::Field<$typ>>::write(&value.$field, bytes);
+                )*
+            }
+        }
+
+        impl $crate::chunk::data::parse_trait::Parse for $name {
+            fn parse(
+                bytes: &mut $crate::Bytes,
+                _: $crate::chunk::type_identifiers::ChunkType,
+            ) -> $crate::Result<Self> {
+                <$name as $crate::chunk::data::schema::Field>::read(bytes)
+            }
+        }
+
+        impl $crate::chunk::data::write_trait::Write for $name {
+            fn write(
+                &self,
+                bytes: &mut Vec<u8>,
+                _: $crate::chunk::type_identifiers::ChunkType,
+            ) {
+                <$name as $crate::chunk::data::schema::Field>::write(self, bytes)
+            }
+        }
+    };
+}
+
+pub use crate::chunk_struct;
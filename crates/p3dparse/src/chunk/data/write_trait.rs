@@ -0,0 +1,5 @@
+use crate::chunk::type_identifiers::ChunkType;
+
+pub trait Write {
+    fn write(&self, bytes: &mut Vec<u8>, typ: ChunkType);
+}
@@ -0,0 +1,291 @@
+use std::mem;
+
+use bytes::Bytes;
+use eyre::eyre;
+
+use crate::{
+    chunk::{data::data_enum::ChunkData, type_identifiers::ChunkType, Chunk, FileSpan, Span},
+    Result,
+};
+
+/// Structural edits on a parsed tree.
+///
+/// After every edit the tree is put back in the same depth-first order the parser produces, with
+/// `parent`, `children` and [`Span`]s rewritten to match, so it can be written straight back out.
+/// That means every edit can move chunks around the arena: indices held from before an edit,
+/// including a [`ChunkIndex`](crate::chunk::index::ChunkIndex), are stale afterwards. Edits that
+/// create or move a chunk return its new index.
+pub trait TreeEdit {
+    /// Adds an empty chunk as child number `position` of `parent`, returns its index.
+    fn insert_chunk(
+        &mut self,
+        parent: usize,
+        position: usize,
+        typ: ChunkType,
+        data: ChunkData,
+    ) -> Result<usize>;
+    /// Adds a whole tree as child number `position` of `parent`, returns the index of its root.
+    fn insert_subtree(
+        &mut self,
+        parent: usize,
+        position: usize,
+        subtree: Vec<Chunk>,
+    ) -> Result<usize>;
+    /// Takes the chunk at `index` and everything under it out of the tree.
+    /// They're returned as a tree of their own, with the removed chunk as the root.
+    fn remove_subtree(&mut self, index: usize) -> Result<Vec<Chunk>>;
+    /// Moves the chunk at `index`, with everything under it, to child number `position` of
+    /// `new_parent`. Returns its new index.
+    fn move_subtree(&mut self, index: usize, new_parent: usize, position: usize) -> Result<usize>;
+    /// Renames a chunk that has a [`Name`](crate::chunk::data::kinds::name::Name).
+    fn rename(&mut self, index: usize, name: &str) -> Result<()>;
+    /// Swaps out a chunk's data, returning the old data. Its `unparsed_data` belonged to the old
+    /// data, so it's dropped.
+    fn replace_data(&mut self, index: usize, data: ChunkData) -> Result<ChunkData>;
+}
+
+impl TreeEdit for Vec<Chunk> {
+    fn insert_chunk(
+        &mut self,
+        parent: usize,
+        position: usize,
+        typ: ChunkType,
+        data: ChunkData,
+    ) -> Result<usize> {
+        let chunk = Chunk {
+            typ,
+            data,
+            span: Span {
+                absolute_index: 0,
+                relative_index: 0,
+            },
+            parent: None,
+            children: Vec::new(),
+            unparsed_data: Bytes::new(),
+            file_span: FileSpan::default(),
+        };
+        self.insert_subtree(parent, position, vec![chunk])
+    }
+
+    fn insert_subtree(
+        &mut self,
+        parent: usize,
+        position: usize,
+        subtree: Vec<Chunk>,
+    ) -> Result<usize> {
+        check_index(self, parent)?;
+        check_position(&self[parent], position)?;
+        check_subtree(&subtree)?;
+
+        // Append the subtree after the existing chunks, shifting its links to match
+        let offset = self.len();
+        for mut chunk in subtree {
+            chunk.parent = Some(chunk.parent.map_or(parent, |p| p + offset));
+            for child in &mut chunk.children {
+                *child += offset;
+            }
+            self.push(chunk);
+        }
+        self[parent].children.insert(position, offset);
+
+        let mapping = renumber(self);
+        Ok(mapping[offset])
+    }
+
+    fn remove_subtree(&mut self, index: usize) -> Result<Vec<Chunk>> {
+        check_index(self, index)?;
+        let Some(parent) = self[index].parent else {
+            return Err(eyre!("Can't remove the root chunk"));
+        };
+
+        self[parent].children.retain(|child| *child != index);
+
+        let mut slots: Vec<_> = mem::take(self).into_iter().map(Some).collect();
+        let (removed, _) = extract(&mut slots, index);
+        *self = extract(&mut slots, 0).0;
+        Ok(removed)
+    }
+
+    fn move_subtree(&mut self, index: usize, new_parent: usize, position: usize) -> Result<usize> {
+        check_index(self, index)?;
+        check_index(self, new_parent)?;
+        let Some(old_parent) = self[index].parent else {
+            return Err(eyre!("Can't move the root chunk"));
+        };
+
+        // The new parent can't be inside the subtree being moved
+        let mut ancestor = Some(new_parent);
+        while let Some(a) = ancestor {
+            if a == index {
+                return Err(eyre!(
+                    "Can't move {} under itself",
+                    self[index].get_lineage(self)
+                ));
+            }
+            ancestor = self[a].parent;
+        }
+
+        self[old_parent].children.retain(|child| *child != index);
+        if let Err(e) = check_position(&self[new_parent], position) {
+            // Put it back where it was, the renumber below would otherwise drop it
+            let relative_index = self[index].span.relative_index;
+            self[old_parent].children.insert(relative_index, index);
+            return Err(e);
+        }
+        self[new_parent].children.insert(position, index);
+        self[index].parent = Some(new_parent);
+
+        let mapping = renumber(self);
+        Ok(mapping[index])
+    }
+
+    fn rename(&mut self, index: usize, name: &str) -> Result<()> {
+        check_index(self, index)?;
+        let chunk = &mut self[index];
+        match chunk.data.get_name_mut() {
+            Some(old) => {
+                old.0 = name.into();
+                Ok(())
+            }
+            None => Err(eyre!("{:?} chunks don't have a name", chunk.typ)),
+        }
+    }
+
+    fn replace_data(&mut self, index: usize, data: ChunkData) -> Result<ChunkData> {
+        check_index(self, index)?;
+        let chunk = &mut self[index];
+        chunk.unparsed_data = Bytes::new();
+        Ok(mem::replace(&mut chunk.data, data))
+    }
+}
+
+fn check_index(tree: &[Chunk], index: usize) -> Result<()> {
+    if index >= tree.len() {
+        return Err(eyre!(
+            "Chunk index {} is out of range, the tree has {} chunks",
+            index,
+            tree.len()
+        ));
+    }
+    Ok(())
+}
+
+fn check_position(parent: &Chunk, position: usize) -> Result<()> {
+    if position > parent.children.len() {
+        return Err(eyre!(
+            "Child position {} is out of range, {:?} has {} children",
+            position,
+            parent.typ,
+            parent.children.len()
+        ));
+    }
+    Ok(())
+}
+
+/// Makes sure a tree from outside is rooted at its first chunk, and that following children from
+/// there reaches every chunk exactly once with matching parents.
+fn check_subtree(subtree: &[Chunk]) -> Result<()> {
+    match subtree.first() {
+        None => return Err(eyre!("Can't insert an empty subtree")),
+        Some(root) if root.parent.is_some() => {
+            return Err(eyre!(
+                "The first chunk of a subtree is its root, it can't have a parent"
+            ))
+        }
+        Some(_) => {}
+    }
+
+    let mut seen = vec![false; subtree.len()];
+    seen[0] = true;
+    let mut stack = vec![0];
+    while let Some(index) = stack.pop() {
+        for &child in &subtree[index].children {
+            if child >= subtree.len() {
+                return Err(eyre!(
+                    "Chunk {} of the subtree has child {}, but the subtree only has {} chunks",
+                    index,
+                    child,
+                    subtree.len()
+                ));
+            }
+            if seen[child] {
+                return Err(eyre!(
+                    "Chunk {} of the subtree is reached more than once",
+                    child
+                ));
+            }
+            if subtree[child].parent != Some(index) {
+                return Err(eyre!(
+                    "Chunk {} of the subtree is a child of {}, but its parent is {:?}",
+                    child,
+                    index,
+                    subtree[child].parent
+                ));
+            }
+            seen[child] = true;
+            stack.push(child);
+        }
+    }
+
+    match seen.iter().position(|seen| !seen) {
+        Some(index) => Err(eyre!(
+            "Chunk {} of the subtree can't be reached from its root",
+            index
+        )),
+        None => Ok(()),
+    }
+}
+
+/// Puts the tree back in depth-first order from the root, dropping anything no longer attached.
+/// Returns where each old index ended up, `usize::MAX` for dropped chunks.
+fn renumber(tree: &mut Vec<Chunk>) -> Vec<usize> {
+    let mut slots: Vec<_> = mem::take(tree).into_iter().map(Some).collect();
+    let (renumbered, mapping) = extract(&mut slots, 0);
+    *tree = renumbered;
+    mapping
+}
+
+/// Takes the subtree under `root` out of `slots` as a new depth-first tree.
+fn extract(slots: &mut [Option<Chunk>], root: usize) -> (Vec<Chunk>, Vec<usize>) {
+    let mut order = Vec::new();
+    let mut stack = vec![root];
+    while let Some(index) = stack.pop() {
+        order.push(index);
+        if let Some(chunk) = &slots[index] {
+            stack.extend(chunk.children.iter().rev());
+        }
+    }
+
+    let mut mapping = vec![usize::MAX; slots.len()];
+    for (new, old) in order.iter().enumerate() {
+        mapping[*old] = new;
+    }
+
+    let mut tree = Vec::with_capacity(order.len());
+    for (new, old) in order.iter().enumerate() {
+        let mut chunk = slots[*old]
+            .take()
+            .expect("Invariant violated: Chunk is reachable from more than one parent");
+        chunk.parent = if new == 0 {
+            None
+        } else {
+            chunk.parent.map(|parent| mapping[parent])
+        };
+        for child in &mut chunk.children {
+            *child = mapping[*child];
+        }
+        chunk.span.absolute_index = new;
+        tree.push(chunk);
+    }
+
+    for i in 0..tree.len() {
+        for (relative_index, child) in tree[i].children.clone().into_iter().enumerate() {
+            tree[child].span.relative_index = relative_index;
+        }
+    }
+    if let Some(root) = tree.first_mut() {
+        root.span.relative_index = 0;
+    }
+
+    (tree, mapping)
+}
@@ -0,0 +1,62 @@
+use std::collections::HashMap;
+
+use crate::chunk::{type_identifiers::ChunkType, Chunk};
+
+/// Lookup tables over a parsed tree, so finding "the Skeleton named X" or "all Shaders"
+/// doesn't mean scanning the whole tree every time.
+///
+/// Built once with [`ChunkIndex::new`], it stores arena indices so it has to be rebuilt if the tree changes.
+/// Names come from [`ChunkData::get_name`](crate::chunk::data::data_enum::ChunkData::get_name).
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ChunkIndex {
+    by_type: HashMap<ChunkType, Vec<usize>>,
+    by_name: HashMap<ChunkType, HashMap<String, Vec<usize>>>,
+}
+
+impl ChunkIndex {
+    pub fn new(tree: &[Chunk]) -> Self {
+        let mut index = ChunkIndex::default();
+
+        for (i, chunk) in tree.iter().enumerate() {
+            index.by_type.entry(chunk.typ).or_default().push(i);
+            if let Some(name) = chunk.data.get_name() {
+                index
+                    .by_name
+                    .entry(chunk.typ)
+                    .or_default()
+                    .entry(name.0.into_string())
+                    .or_default()
+                    .push(i);
+            }
+        }
+
+        index
+    }
+
+    /// Indices of every chunk of this type, in tree order.
+    pub fn of_type(&self, typ: ChunkType) -> &[usize] {
+        self.by_type.get(&typ).map_or(&[], Vec::as_slice)
+    }
+
+    /// Indices of every chunk of this type with this name, in tree order.
+    pub fn named(&self, typ: ChunkType, name: &str) -> &[usize] {
+        self.by_name
+            .get(&typ)
+            .and_then(|names| names.get(name))
+            .map_or(&[], Vec::as_slice)
+    }
+
+    /// Every chunk of this type, in tree order.
+    pub fn all<'s, 'a: 's>(
+        &'s self,
+        tree: &'a [Chunk],
+        typ: ChunkType,
+    ) -> impl Iterator<Item = &'a Chunk> + 's {
+        self.of_type(typ).iter().map(|i| &tree[*i])
+    }
+
+    /// The first chunk of this type with this name.
+    pub fn get<'a>(&self, tree: &'a [Chunk], typ: ChunkType, name: &str) -> Option<&'a Chunk> {
+        self.named(typ, name).first().map(|i| &tree[*i])
+    }
+}
@@ -0,0 +1,236 @@
+//! Path queries over a parsed tree, so finding chunks doesn't need a hand written loop every time.
+//!
+//! A query is a list of steps separated by `/` (children) or `//` (any descendant), starting from
+//! the root chunk. Each step is a [`ChunkType`] name, a hex chunk ID like `0x10000`, or `*` for any
+//! type, optionally followed by a name predicate. Names may use `*` as a wildcard.
+//!
+//! ```text
+//! Skin[name=homer_m]/OldPrimGroup/UVList   UV lists of one skin
+//! //Shader[name=char_*]                    every shader starting with char_, at any depth
+//! Mesh/*                                   every child of every top level mesh
+//! ```
+
+use std::str::FromStr;
+
+use serde::{de::IntoDeserializer, Deserialize};
+
+use crate::{
+    chunk::{type_identifiers::ChunkType, Chunk},
+    Result,
+};
+use eyre::eyre;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Axis {
+    Child,
+    Descendant,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Step {
+    axis: Axis,
+    /// `None` matches any type.
+    typ: Option<ChunkType>,
+    name: Option<String>,
+}
+
+impl Step {
+    fn matches(&self, chunk: &Chunk) -> bool {
+        if self.typ.is_some_and(|typ| typ != chunk.typ) {
+            return false;
+        }
+
+        match &self.name {
+            Some(pattern) => chunk
+                .data
+                .get_name()
+                .is_some_and(|name| wildcard_match(pattern, &name.0)),
+            None => true,
+        }
+    }
+}
+
+/// A parsed query, reusable across trees.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Query {
+    steps: Vec<Step>,
+}
+
+impl Query {
+    pub fn new(path: &str) -> Result<Self> {
+        let mut steps = Vec::new();
+        let mut rest = path.trim();
+
+        // A leading single slash is allowed, it means the same thing as none
+        if !rest.starts_with("//") {
+            rest = rest.strip_prefix('/').unwrap_or(rest);
+        }
+
+        while !rest.is_empty() {
+            let axis = if let Some(stripped) = rest.strip_prefix("//") {
+                rest = stripped;
+                Axis::Descendant
+            } else {
+                if !steps.is_empty() {
+                    rest = rest
+                        .strip_prefix('/')
+                        .ok_or_else(|| eyre!("Expected / before {:?} in query {:?}", rest, path))?;
+                }
+                Axis::Child
+            };
+
+            let end = step_end(rest)?;
+            steps.push(parse_step(axis, &rest[..end], path)?);
+            rest = &rest[end..];
+        }
+
+        if steps.is_empty() {
+            return Err(eyre!("Empty query"));
+        }
+
+        Ok(Query { steps })
+    }
+
+    /// Arena indices of every matching chunk, in tree order without duplicates.
+    pub fn run_indices(&self, tree: &[Chunk]) -> Vec<usize> {
+        if tree.is_empty() {
+            return Vec::new();
+        }
+
+        let mut context = vec![0];
+        for step in &self.steps {
+            let mut next = Vec::new();
+            for index in &context {
+                match step.axis {
+                    Axis::Child => next.extend(
+                        tree[*index]
+                            .children
+                            .iter()
+                            .filter(|child| step.matches(&tree[**child])),
+                    ),
+                    Axis::Descendant => collect_descendants(tree, *index, step, &mut next),
+                }
+            }
+            // Overlapping descendant steps can find the same chunk more than once
+            next.sort_unstable();
+            next.dedup();
+            context = next;
+        }
+
+        context
+    }
+
+    /// Every matching chunk, in tree order without duplicates.
+    pub fn run<'a>(&self, tree: &'a [Chunk]) -> Vec<&'a Chunk> {
+        self.run_indices(tree)
+            .into_iter()
+            .map(|index| &tree[index])
+            .collect()
+    }
+}
+
+impl FromStr for Query {
+    type Err = eyre::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Query::new(s)
+    }
+}
+
+/// Parses `path` and runs it against `tree`, see the [module docs](self) for the syntax.
+pub fn query<'a>(tree: &'a [Chunk], path: &str) -> Result<Vec<&'a Chunk>> {
+    Ok(Query::new(path)?.run(tree))
+}
+
+fn collect_descendants(tree: &[Chunk], index: usize, step: &Step, out: &mut Vec<usize>) {
+    for child in &tree[index].children {
+        if step.matches(&tree[*child]) {
+            out.push(*child);
+        }
+        collect_descendants(tree, *child, step, out);
+    }
+}
+
+/// Finds where the step at the start of `rest` ends, skipping over slashes inside predicates.
+fn step_end(rest: &str) -> Result<usize> {
+    let mut depth = 0;
+    for (i, c) in rest.char_indices() {
+        match c {
+            '[' => depth += 1,
+            ']' if depth == 0 => return Err(eyre!("Unmatched ] in query step {:?}", rest)),
+            ']' => depth -= 1,
+            '/' if depth == 0 => return Ok(i),
+            _ => {}
+        }
+    }
+
+    if depth != 0 {
+        return Err(eyre!("Unclosed [ in query step {:?}", rest));
+    }
+    Ok(rest.len())
+}
+
+fn parse_step(axis: Axis, step: &str, path: &str) -> Result<Step> {
+    let (typ, predicate) = match step.split_once('[') {
+        Some((typ, predicate)) => {
+            let predicate = predicate
+                .strip_suffix(']')
+                .ok_or_else(|| eyre!("Expected ] at the end of {:?}", step))?;
+            (typ, Some(predicate))
+        }
+        None => (step, None),
+    };
+
+    let typ = match typ.trim() {
+        "" => return Err(eyre!("Empty step in query {:?}", path)),
+        "*" => None,
+        typ => Some(parse_type(typ)?),
+    };
+
+    let name = match predicate {
+        Some(predicate) => match predicate.split_once('=') {
+            Some((key, value)) if key.trim() == "name" => Some(value.trim().to_owned()),
+            _ => {
+                return Err(eyre!(
+                    "Unsupported predicate [{}], only [name=...] is supported",
+                    predicate
+                ))
+            }
+        },
+        None => None,
+    };
+
+    Ok(Step { axis, typ, name })
+}
+
+fn parse_type(typ: &str) -> Result<ChunkType> {
+    if let Some(hex) = typ.strip_prefix("0x").or_else(|| typ.strip_prefix("0X")) {
+        return u32::from_str_radix(hex, 16)
+            .map(ChunkType::from)
+            .map_err(|e| eyre!("Invalid chunk ID {:?}: {}", typ, e));
+    }
+
+    ChunkType::deserialize(typ.into_deserializer())
+        .map_err(|_: serde::de::value::Error| eyre!("Unknown chunk type {:?}", typ))
+}
+
+/// Matches `text` against a pattern where `*` stands for any run of characters.
+fn wildcard_match(pattern: &str, text: &str) -> bool {
+    let Some((first, rest)) = pattern.split_once('*') else {
+        return pattern == text;
+    };
+    let Some(mut text) = text.strip_prefix(first) else {
+        return false;
+    };
+
+    let mut parts: Vec<&str> = rest.split('*').collect();
+    let last = parts.pop().unwrap_or_default();
+    for part in parts {
+        match text.find(part) {
+            Some(i) => text = &text[i + part.len()..],
+            None => return false,
+        }
+    }
+
+    text.len() >= last.len() && text.ends_with(last)
+}
@@ -0,0 +1,105 @@
+//! The table [`ChunkData::from_chunk_type`] uses to pick a parser for each [`ChunkType`].
+//!
+//! Every built-in parser is registered here when the table is first used. Downstream crates can
+//! add parsers for chunk types p3dparse doesn't know, including raw IDs through
+//! [`ChunkType::Other`], or replace a built-in one. The registry is global, so register before
+//! parsing.
+//!
+//! Custom parsers should read through [`BufResult`](crate::bytes_ext::BufResult), the string and
+//! vector [`helpers`](crate::chunk::data::helpers) or [`chunk_struct!`](crate::chunk_struct), which
+//! all follow the byte order of the file being parsed.
+//!
+//! ```ignore
+//! registry::register_custom::<RoadSegment>(&[ChunkType::Other(0x3000003)]);
+//! let tree = p3dparse::parse_file(file)?;
+//! let road = match &tree[1].data {
+//!     ChunkData::Custom(data) => data.downcast_ref::<RoadSegment>(),
+//!     _ => None,
+//! };
+//! ```
+
+use std::{
+    collections::HashMap,
+    sync::{OnceLock, RwLock},
+};
+
+use bytes::Bytes;
+
+use crate::{
+    chunk::{
+        data::{
+            custom::{deserialize_payload, CustomData, CustomPayload, DeserializeFn},
+            data_enum::ChunkData,
+            data_enum_impl::register_builtins,
+        },
+        type_identifiers::ChunkType,
+    },
+    Result,
+};
+
+/// Parses the payload of a chunk, not including its header.
+pub type ParseFn = fn(&mut Bytes, ChunkType) -> Result<ChunkData>;
+
+#[derive(Default)]
+pub(crate) struct ParserRegistry {
+    parsers: HashMap<ChunkType, ParseFn>,
+    payloads: HashMap<&'static str, DeserializeFn>,
+}
+
+impl ParserRegistry {
+    pub(crate) fn register(&mut self, typ: ChunkType, parser: ParseFn) -> Option<ParseFn> {
+        self.parsers.insert(typ, parser)
+    }
+}
+
+fn registry() -> &'static RwLock<ParserRegistry> {
+    static REGISTRY: OnceLock<RwLock<ParserRegistry>> = OnceLock::new();
+    REGISTRY.get_or_init(|| {
+        let mut registry = ParserRegistry::default();
+        register_builtins(&mut registry);
+        RwLock::new(registry)
+    })
+}
+
+/// Uses `parser` for chunks of type `typ` from now on. Returns the parser it replaced, if any.
+pub fn register_parser(typ: ChunkType, parser: ParseFn) -> Option<ParseFn> {
+    registry()
+        .write()
+        .unwrap_or_else(|e| e.into_inner())
+        .register(typ, parser)
+}
+
+/// Parses chunks of every type in `types` as `T`, stored in [`ChunkData::Custom`].
+/// Also lets trees containing `T` be deserialized.
+pub fn register_custom<T: CustomPayload>(types: &[ChunkType]) {
+    let mut registry = registry().write().unwrap_or_else(|e| e.into_inner());
+    registry.payloads.insert(T::NAME, deserialize_payload::<T>);
+    for typ in types {
+        registry.register(*typ, parse_custom::<T>);
+    }
+}
+
+/// The parser currently registered for `typ`.
+pub fn parser(typ: ChunkType) -> Option<ParseFn> {
+    registry()
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .parsers
+        .get(&typ)
+        .copied()
+}
+
+pub(crate) fn payload_deserializer(name: &str) -> Option<DeserializeFn> {
+    registry()
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .payloads
+        .get(name)
+        .copied()
+}
+
+fn parse_custom<T: CustomPayload>(bytes: &mut Bytes, typ: ChunkType) -> Result<ChunkData> {
+    Ok(ChunkData::Custom(CustomData::new(T::parse_field(
+        bytes, typ, "data",
+    )?)))
+}
@@ -1,4 +1,4 @@
-use num_enum::{IntoPrimitive, TryFromPrimitive};
+use num_enum::{FromPrimitive, IntoPrimitive};
 use serde::{Deserialize, Serialize};
 
 /// Directly stolen from LucasStuff.Radical
@@ -6,12 +6,13 @@ use serde::{Deserialize, Serialize};
 #[repr(u32)]
 #[derive(
     IntoPrimitive,
-    TryFromPrimitive,
+    FromPrimitive,
     Debug,
     PartialEq,
     Eq,
     PartialOrd,
     Ord,
+    Hash,
     Clone,
     Copy,
     Deserialize,
@@ -730,4 +731,8 @@ pub enum ChunkType {
     AnimatedDSGWrapper = 0x03F0000F,
     /// Originally BreakableDrawable
     AnimatedObjectDSGWrapper = 0x03F00010,
+    /// Any ID not listed above, e.g. chunks from other games or custom tools.
+    /// The header sizes still let us skip over these safely.
+    #[num_enum(catch_all)]
+    Other(u32),
 }
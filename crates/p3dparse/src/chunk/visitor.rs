@@ -0,0 +1,222 @@
+//! Depth-first traversal of a parsed tree, so passes over it don't each need their own recursion.
+//!
+//! Implement [`ChunkVisitor`], overriding only the hooks you care about, and drive it with [`walk`].
+//! For every chunk the walker calls [`enter`](ChunkVisitor::enter), then the typed callback for its
+//! [`ChunkData`] variant through [`visit_data`](ChunkVisitor::visit_data), then walks the children,
+//! and finally calls [`leave`](ChunkVisitor::leave).
+
+use crate::chunk::data::custom::CustomData;
+use crate::chunk::data::kinds::{
+    animation::{Animation, AnimationGroup, AnimationGroupList, AnimationSize},
+    channel::{Channel, ChannelInterpolation},
+    collision::{
+        CollisionBoundingBox, CollisionCylinder, CollisionObject, CollisionObjectAttribute,
+        CollisionOblongBox, CollisionSphere, CollisionVector, CollisionVolume,
+        CollisionVolumeOwner, IntersectDSG, TerrainTypeList,
+    },
+    explosion::BreakableObject,
+    file_metadata::{ExportInfoNamedInt, ExportInfoNamedString, History},
+    frontend::{
+        FrontendGroup, FrontendImageResource, FrontendLanguage, FrontendLayer, FrontendMovie,
+        FrontendMultiSprite, FrontendMultiText, FrontendPage, FrontendPolygon, FrontendProject,
+        FrontendPure3DObject, FrontendResource, FrontendScreen, FrontendStringHardCoded,
+        FrontendStringTextBible, FrontendTextBible,
+    },
+    game_metadata::{FollowCameraData, Locator},
+    gameattr::{GameAttr, GameAttrParam},
+    image::{Image, ImageRaw},
+    light::{
+        Light, LightConeParam, LightDecayRange, LightDecayRangeRotationY, LightDirection,
+        LightGroup, LightIlluminationType, LightPosition, LightShadow,
+    },
+    locator::{WBLocator, WBMatrix, WBRail, WBSpline, WBTriggerVolume},
+    mesh::{
+        BinormalList, ColourList, CompositeDrawable, CompositeDrawableEffect,
+        CompositeDrawableEffectList, CompositeDrawableProp, CompositeDrawablePropList,
+        CompositeDrawableSkin, CompositeDrawableSkinList, CompositeDrawableSortOrder, IndexList,
+        MatrixList, MatrixPalette, Mesh, NormalList, OldPrimGroup, PackedNormalList, PositionList,
+        RenderStatus, Skin, TangentList, UVList, WeightList,
+    },
+    name::Name,
+    object::{
+        AnimatedObject, AnimatedObjectAnimation, AnimatedObjectDSGWrapper, AnimatedObjectFactory,
+        MultiController, MultiControllerTracks, ObjectDSG, OldFrameController,
+    },
+    old_billboard::{
+        OldBillboardDisplayInfo, OldBillboardPerspectiveInfo, OldBillboardQuad,
+        OldBillboardQuadGroup,
+    },
+    old_particle_system::{
+        InstanceableParticleSystem, OldBaseEmitter, OldParticleSystem, OldParticleSystemFactory,
+        OldParticleSystemInstancingInfo, OldSpriteEmitter,
+    },
+    physics::{
+        BoundingBox, BoundingSphere, PhysicsInertiaMatrix, PhysicsJoint, PhysicsObject,
+        PhysicsVector,
+    },
+    prop_state::{
+        ObjectAttributes, StatePropCallbackData, StatePropDataV1, StatePropEventData,
+        StatePropFrameControllerData, StatePropStateDataV1, StatePropVisibilitiesData,
+    },
+    pure3d_other::Camera,
+    scenegraph::{
+        ScenegraphAttachment, ScenegraphAttachmentPoint, ScenegraphBranch, ScenegraphCamera,
+        ScenegraphDrawable, ScenegraphLightGroup, ScenegraphSortOrder, ScenegraphTransform,
+        ScenegraphVisibility,
+    },
+    shader::{Shader, VertexShader},
+    shader_param::ShaderParam,
+    skeleton::{Skeleton, SkeletonJoint, SkeletonJointBonePreserve, SkeletonJointMirrorMap},
+    texture::Texture,
+    version::Version,
+};
+
+use crate::{
+    chunk::{
+        data::data_enum::{chunk_data_variants, ChunkData},
+        Chunk,
+    },
+    Result,
+};
+
+/// Where the walker currently is.
+#[derive(Clone, Copy, Debug)]
+pub struct VisitContext<'a, 's> {
+    pub tree: &'a [Chunk],
+    pub chunk: &'a Chunk,
+    /// Arena index of [`VisitContext::chunk`].
+    pub index: usize,
+    /// 0 for the chunk the walk started at.
+    pub depth: usize,
+    /// Arena indices of the chunks above this one, outermost first.
+    pub ancestors: &'s [usize],
+}
+
+impl<'a, 's> VisitContext<'a, 's> {
+    /// The parent of this chunk, even if the walk started below it.
+    pub fn parent(&self) -> Option<&'a Chunk> {
+        self.chunk.get_parent(self.tree)
+    }
+
+    /// Same format as [`Chunk::get_lineage`], built on demand since most visitors only need it for errors.
+    pub fn lineage(&self) -> String {
+        self.chunk.get_lineage(self.tree)
+    }
+}
+
+/// What the walker should do after [`ChunkVisitor::enter`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Walk {
+    Continue,
+    /// Don't visit this chunk's data or children, [`ChunkVisitor::leave`] is still called.
+    SkipChildren,
+}
+
+macro_rules! chunk_visitor {
+    ($($variant:ident($($field:ident: $typ:ty),*) = [$($chunk:ident),* $(,)?] => $method:ident,)*) => {
+        /// Hooks called by [`walk`], every one of them does nothing by default.
+        /// An error from any hook stops the walk and is returned from [`walk`].
+        pub trait ChunkVisitor<'a> {
+            #[allow(unused_variables)]
+            fn enter(&mut self, ctx: &VisitContext<'a, '_>) -> Result<Walk> {
+                Ok(Walk::Continue)
+            }
+
+            /// Called once all children have been walked.
+            #[allow(unused_variables)]
+            fn leave(&mut self, ctx: &VisitContext<'a, '_>) -> Result<()> {
+                Ok(())
+            }
+
+            /// Dispatches to the typed callback for this variant, override it to see every chunk's data.
+            fn visit_data(&mut self, ctx: &VisitContext<'a, '_>, data: &'a ChunkData) -> Result<()> {
+                match data {
+                    ChunkData::None => self.visit_none(ctx),
+                    ChunkData::Unknown => self.visit_unknown(ctx),
+                    ChunkData::Custom(data) => self.visit_custom(ctx, data),
+                    $(ChunkData::$variant($($field),*) => self.$method(ctx, $($field),*),)*
+                }
+            }
+
+            /// Chunks that hold no data, like the root.
+            #[allow(unused_variables)]
+            fn visit_none(&mut self, ctx: &VisitContext<'a, '_>) -> Result<()> {
+                Ok(())
+            }
+
+            /// Chunks without a parser, their payload is in [`Chunk::unparsed_data`].
+            #[allow(unused_variables)]
+            fn visit_unknown(&mut self, ctx: &VisitContext<'a, '_>) -> Result<()> {
+                Ok(())
+            }
+
+            /// Chunks parsed by a downstream parser, see [`registry`](crate::chunk::registry).
+            #[allow(unused_variables)]
+            fn visit_custom(&mut self, ctx: &VisitContext<'a, '_>, data: &'a CustomData) -> Result<()> {
+                Ok(())
+            }
+
+            $(
+                #[allow(unused_variables)]
+                fn $method(&mut self, ctx: &VisitContext<'a, '_>, $($field: &'a $typ),*) -> Result<()> {
+                    Ok(())
+                }
+            )*
+        }
+    };
+}
+
+chunk_data_variants!(chunk_visitor);
+
+/// Walks the whole tree depth-first, starting at the root.
+pub fn walk<'a, V: ChunkVisitor<'a>>(tree: &'a [Chunk], visitor: &mut V) -> Result<()> {
+    if tree.is_empty() {
+        return Ok(());
+    }
+    walk_from(tree, 0, visitor)
+}
+
+/// Walks the subtree under `index` depth-first, depths are relative to it.
+pub fn walk_from<'a, V: ChunkVisitor<'a>>(
+    tree: &'a [Chunk],
+    index: usize,
+    visitor: &mut V,
+) -> Result<()> {
+    let mut ancestors = Vec::new();
+    walk_chunk(tree, index, &mut ancestors, visitor)
+}
+
+fn walk_chunk<'a, V: ChunkVisitor<'a>>(
+    tree: &'a [Chunk],
+    index: usize,
+    ancestors: &mut Vec<usize>,
+    visitor: &mut V,
+) -> Result<()> {
+    let chunk = &tree[index];
+    let ctx = VisitContext {
+        tree,
+        chunk,
+        index,
+        depth: ancestors.len(),
+        ancestors,
+    };
+
+    if visitor.enter(&ctx)? == Walk::Continue {
+        visitor.visit_data(&ctx, &chunk.data)?;
+
+        ancestors.push(index);
+        for child in &chunk.children {
+            walk_chunk(tree, *child, ancestors, visitor)?;
+        }
+        ancestors.pop();
+    }
+
+    let ctx = VisitContext {
+        tree,
+        chunk,
+        index,
+        depth: ancestors.len(),
+        ancestors,
+    };
+    visitor.leave(&ctx)
+}
@@ -0,0 +1,252 @@
+use std::{fmt::Display, path::PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{chunk::type_identifiers::ChunkType, FileTypes, ParseOptions};
+
+/// Why a file couldn't be parsed.
+///
+/// Offsets are absolute byte offsets into the (decompressed) file, and lineages are the same
+/// `name:Type:abs:rel@offset+data/total` strings as
+/// [`Chunk::get_lineage`](crate::chunk::Chunk::get_lineage) for the parent of the chunk that
+/// failed, or `Unknown` for the root. Fields inside a chunk's data are dotted paths like
+/// `data.element.colour`, as deep as the parser named them.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ParseError {
+    /// The file couldn't be opened or mapped.
+    Io { path: PathBuf, message: String },
+    /// The file doesn't start with any magic we know of.
+    UnrecognisedFile { magic: u32 },
+    /// We know what this file is, but can't read it yet.
+    UnsupportedFile { file_type: FileTypes },
+    /// The LZR stream of a compressed file is broken.
+    Decompression { message: String },
+    /// A chunk header has sizes that can't possibly be right.
+    CorruptHeader {
+        offset: usize,
+        typ: ChunkType,
+        data_size: u32,
+        total_size: u32,
+        lineage: String,
+    },
+    /// The file ended, or a chunk's data ran out, in the middle of a field.
+    /// `typ` is `None` if the chunk header itself was cut off.
+    UnexpectedEof {
+        offset: usize,
+        typ: Option<ChunkType>,
+        field: String,
+        needed: usize,
+        lineage: String,
+    },
+    /// A field was read but holds a value that isn't allowed, like an out of range enum.
+    InvalidValue {
+        offset: usize,
+        typ: ChunkType,
+        field: String,
+        message: String,
+        lineage: String,
+    },
+    /// Something [`ParseOptions::lenient`] would have recovered from, in strict mode.
+    Strict(Diagnostic),
+}
+
+impl ParseError {
+    /// The absolute byte offset the error happened at, if it happened inside the chunk tree.
+    pub fn offset(&self) -> Option<usize> {
+        match self {
+            ParseError::CorruptHeader { offset, .. }
+            | ParseError::UnexpectedEof { offset, .. }
+            | ParseError::InvalidValue { offset, .. } => Some(*offset),
+            ParseError::Strict(diagnostic) => Some(diagnostic.offset),
+            _ => None,
+        }
+    }
+
+    /// The type of the chunk that failed to parse, if it got far enough to know.
+    pub fn chunk_type(&self) -> Option<ChunkType> {
+        match self {
+            ParseError::CorruptHeader { typ, .. } | ParseError::InvalidValue { typ, .. } => {
+                Some(*typ)
+            }
+            ParseError::UnexpectedEof { typ, .. } => *typ,
+            ParseError::Strict(diagnostic) => Some(diagnostic.typ),
+            _ => None,
+        }
+    }
+
+    /// Turns an error from a chunk's data parser into a `ParseError`.
+    pub(crate) fn from_report(
+        report: eyre::Report,
+        offset: usize,
+        typ: ChunkType,
+        lineage: String,
+    ) -> ParseError {
+        let field = report
+            .downcast_ref::<Field>()
+            .map_or_else(|| "unknown".to_owned(), |f| f.0.join("."));
+
+        match report.downcast_ref::<Overrun>() {
+            Some(overrun) => ParseError::UnexpectedEof {
+                offset,
+                typ: Some(typ),
+                field,
+                needed: overrun.0,
+                lineage,
+            },
+            None => ParseError::InvalidValue {
+                offset,
+                typ,
+                field,
+                message: report.root_cause().to_string(),
+                lineage,
+            },
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseError::Io { path, message } => {
+                write!(f, "Could not read {}: {}", path.display(), message)
+            }
+            ParseError::UnrecognisedFile { magic } => {
+                write!(f, "Unrecognized file format (magic {:#010X})", magic)
+            }
+            ParseError::UnsupportedFile { file_type } => {
+                write!(f, "{:?} files aren't currently supported.", file_type)
+            }
+            ParseError::Decompression { message } => {
+                write!(f, "Could not decompress file: {}", message)
+            }
+            ParseError::CorruptHeader {
+                offset,
+                typ,
+                data_size,
+                total_size,
+                lineage,
+            } => write!(
+                f,
+                "File is corrupted. {:?} at {:#X} has data size {} and total size {}. Lineage Info: {}",
+                typ, offset, data_size, total_size, lineage
+            ),
+            ParseError::UnexpectedEof {
+                offset,
+                typ,
+                field,
+                needed,
+                lineage,
+            } => write!(
+                f,
+                "Unexpected end of data at {:#X} reading {} of {:?}, {} more bytes needed. Lineage Info: {}",
+                offset, field, typ, needed, lineage
+            ),
+            ParseError::InvalidValue {
+                offset,
+                typ,
+                field,
+                message,
+                lineage,
+            } => write!(
+                f,
+                "Invalid value at {:#X} reading {} of {:?}: {}. Lineage Info: {}",
+                offset, field, typ, message, lineage
+            ),
+            ParseError::Strict(diagnostic) => write!(f, "Strict mode: {}", diagnostic),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Something odd in a file that lenient parsing recovered from.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Diagnostic {
+    /// Absolute byte offset of the chunk header.
+    pub offset: usize,
+    pub typ: ChunkType,
+    /// Lineage of the chunk itself, it's in the tree even if it couldn't be fully parsed.
+    pub lineage: String,
+    pub kind: DiagnosticKind,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub enum DiagnosticKind {
+    /// The chunk ID isn't in [`ChunkType`], its payload is kept in `unparsed_data`.
+    UnrecognisedChunk,
+    /// We know the chunk ID but have no parser for it, its payload is kept in `unparsed_data`.
+    UnparsedChunk,
+    /// The parser stopped short of the data size, but the rest parsed as children. Anything after
+    /// the last of them is kept in `unparsed_data`.
+    MisalignedChildren {
+        consumed: usize,
+        expected: usize,
+        children: usize,
+    },
+    /// The parser stopped short of the data size and the rest isn't children, it's kept in `unparsed_data`.
+    LeftoverBytes { count: usize },
+    /// The chunk parsed, but what's in it doesn't add up, e.g. a light group naming a light that
+    /// isn't in the file.
+    Invalid { message: String },
+}
+
+impl Diagnostic {
+    /// Collects this diagnostic, or fails the parse with it in strict mode.
+    pub(crate) fn report(
+        self,
+        options: &ParseOptions,
+        diagnostics: &mut Vec<Diagnostic>,
+    ) -> Result<(), ParseError> {
+        if options.strict {
+            return Err(ParseError::Strict(self));
+        }
+        diagnostics.push(self);
+        Ok(())
+    }
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.kind {
+            DiagnosticKind::UnrecognisedChunk => write!(f, "Unrecognised chunk ID"),
+            DiagnosticKind::UnparsedChunk => write!(f, "No parser for {:?}", self.typ),
+            DiagnosticKind::MisalignedChildren {
+                consumed,
+                expected,
+                children,
+            } => write!(
+                f,
+                "Parsed {} of {} data bytes, found {} misaligned children in the rest",
+                consumed, expected, children
+            ),
+            DiagnosticKind::LeftoverBytes { count } => {
+                write!(f, "{} data bytes left over", count)
+            }
+            DiagnosticKind::Invalid { message } => write!(f, "Invalid {:?}: {}", self.typ, message),
+        }?;
+        write!(f, " at {:#X}. Lineage Info: {}", self.offset, self.lineage)
+    }
+}
+
+/// Raised by [`BufResult`](crate::bytes_ext::BufResult) when there aren't enough bytes left.
+#[derive(Debug)]
+pub(crate) struct Overrun(pub usize);
+
+impl Display for Overrun {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Overrun by {} bytes", self.0)
+    }
+}
+
+impl std::error::Error for Overrun {}
+
+/// Context attached by [`in_field`](crate::chunk::data::parse_trait::in_field) so errors know what
+/// was being read. Outermost field first.
+#[derive(Debug)]
+pub(crate) struct Field(pub Vec<&'static str>);
+
+impl Display for Field {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "While reading {}", self.0.join("."))
+    }
+}
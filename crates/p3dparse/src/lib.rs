@@ -1,17 +1,29 @@
+use std::{fs::File, path::Path};
+
 pub use bytes::Bytes;
+use bytes_ext::{BufResult, EndianGuard};
+use eyre::eyre;
+use memmap2::Mmap;
 use num_enum::{IntoPrimitive, TryFromPrimitive};
-use result::Result;
+pub use result::Result;
 
-mod bytes_ext;
+pub mod bytes_ext;
 pub mod chunk;
+pub mod error;
+pub mod lzr;
 mod result;
-use crate::chunk::Chunk;
+pub mod scan;
+pub mod text;
+use crate::{
+    chunk::Chunk,
+    error::{Diagnostic, ParseError},
+};
 
 #[cfg(test)]
 mod tests;
 
 #[repr(u32)]
-#[derive(PartialEq, Eq, Debug, IntoPrimitive, TryFromPrimitive)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug, IntoPrimitive, TryFromPrimitive)]
 pub enum FileTypes {
     RZ = 0x0000_5A52,
     CompressedPure3DBigEndian = 0x5033_445A,
@@ -20,10 +32,109 @@ pub enum FileTypes {
     Pure3D = 0xFF44_3350,
 }
 
-pub fn parse_file(mut file: Bytes) -> Result<Vec<Chunk>> {
-    // let mut file_clone = file.clone();
-    // let file_type =
-    //     FileTypes::try_from(file_clone.safe_get_u32_le()?).context("Unrecognized file format")?;
+impl FileTypes {
+    /// Console builds (GameCube, Xbox 360) store everything big endian, which shows up as a byte swapped magic.
+    pub fn is_big_endian(&self) -> bool {
+        matches!(
+            self,
+            FileTypes::CompressedPure3DBigEndian | FileTypes::Pure3DBigEndian
+        )
+    }
+}
+
+/// Parses a P3D file, decompressing it first if it's LZR compressed.
+/// The byte order is picked from the file magic, big endian files produce the same [`ChunkData`](chunk::data::data_enum::ChunkData).
+pub fn parse_file(file: Bytes) -> Result<Vec<Chunk>, ParseError> {
+    parse_file_with_options(file, &ParseOptions::lenient()).map(|(tree, _)| tree)
+}
+
+/// Memory maps a file, so it can be parsed without reading it all into memory first.
+///
+/// Parsed chunks slice into the mapping, so it stays open until they're all dropped.
+/// The file must not be modified while it's mapped.
+pub fn map_file(path: &Path) -> Result<Bytes, ParseError> {
+    let io_error = |e: std::io::Error| ParseError::Io {
+        path: path.to_owned(),
+        message: e.to_string(),
+    };
+
+    let file = File::open(path).map_err(io_error)?;
+    // Safety: Nothing in this crate writes to the file, and callers are told not to either.
+    let map = unsafe { Mmap::map(&file) }.map_err(io_error)?;
+    Ok(Bytes::from_owner(map))
+}
+
+/// Same as [`parse_file`], for a file on disk. The file is memory mapped, see [`map_file`].
+pub fn parse_path(path: &Path) -> Result<Vec<Chunk>, ParseError> {
+    parse_file(map_file(path)?)
+}
+
+/// How [`parse_file_with_options`] should deal with anything it has to recover from.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ParseOptions {
+    /// Fail with [`ParseError::Strict`] instead of collecting diagnostics.
+    pub strict: bool,
+}
+
+impl ParseOptions {
+    pub fn strict() -> Self {
+        ParseOptions { strict: true }
+    }
+
+    pub fn lenient() -> Self {
+        ParseOptions { strict: false }
+    }
+}
+
+/// Same as [`parse_file`], but also returns every [`Diagnostic`] for unknown chunks and recovered data.
+pub fn parse_file_with_options(
+    mut file: Bytes,
+    options: &ParseOptions,
+) -> Result<(Vec<Chunk>, Vec<Diagnostic>), ParseError> {
+    let magic = file
+        .clone()
+        .safe_get_u32_le()
+        .map_err(|_| ParseError::UnrecognisedFile { magic: 0 })?;
+    let file_type =
+        FileTypes::try_from(magic).map_err(|_| ParseError::UnrecognisedFile { magic })?;
+
+    let _endian = EndianGuard::new(file_type.is_big_endian());
+    match file_type {
+        FileTypes::Pure3D | FileTypes::Pure3DBigEndian => Chunk::parse_root(&mut file, options),
+        FileTypes::CompressedPure3D | FileTypes::CompressedPure3DBigEndian => {
+            let mut file = lzr::decompress_file(file).map_err(|e| ParseError::Decompression {
+                message: e.to_string(),
+            })?;
+            Chunk::parse_root(&mut file, options)
+        }
+        FileTypes::RZ => Err(ParseError::UnsupportedFile { file_type }),
+    }
+}
+
+/// Serializes a parsed tree back into a P3D file, starting from the root chunk.
+///
+/// The output is always little endian. Parsed data is converted, but [`Chunk::unparsed_data`] is
+/// only ever copied, so a tree from a big endian file is an error if any chunk still has some.
+pub fn write_file(tree: &[Chunk]) -> Result<Vec<u8>> {
+    if let Some(chunk) = tree
+        .iter()
+        .find(|chunk| chunk.file_span.big_endian && !chunk.unparsed_data.is_empty())
+    {
+        return Err(eyre!(
+            "{} has {} big endian bytes that weren't parsed, they can't be written little endian",
+            chunk.get_lineage(tree),
+            chunk.unparsed_data.len()
+        ));
+    }
+
+    let mut bytes = Vec::new();
+    if let Some(root) = tree.first() {
+        root.write(tree, &mut bytes);
+    }
+    Ok(bytes)
+}
 
-    Chunk::parse_root(&mut file)
+/// Serializes a parsed tree into an LZR compressed P3D file, see [`write_file`].
+pub fn write_file_compressed(tree: &[Chunk]) -> Result<Vec<u8>> {
+    Ok(lzr::compress_file(&write_file(tree)?))
 }
@@ -0,0 +1,255 @@
+//! Radical's LZR compression, used by retail `P3DZ` files.
+//!
+//! A compressed file is the `P3DZ` magic, the u32 size of the decompressed file, and then a
+//! series of blocks until that size is reached. Each block is a u32 compressed size, a u32
+//! decompressed size, and the compressed bytes.
+//!
+//! Inside a block, every token starts with a code byte:
+//! - `1..=15` is a run of that many literal bytes.
+//! - `16..=255` is a back-reference. The low nibble is the length and the high nibble plus the
+//!   next byte shifted left by 4 is the distance back into the output.
+//!
+//! A length nibble of 0 means the length is extended, starting at 15: every following zero byte
+//! adds 255 and the first non-zero byte is added last. Literal runs use the same scheme with a
+//! code of 0.
+
+use crate::{
+    bytes_ext::{BufResult, EndianGuard},
+    FileTypes, Result,
+};
+use bytes::{Buf, BufMut, Bytes};
+use eyre::eyre;
+
+/// How much uncompressed data goes into each block when compressing.
+const BLOCK_SIZE: usize = 0x1_0000;
+/// Back-references can't reach further than 12 bits.
+const MAX_DISTANCE: usize = 0xFFF;
+/// A back-reference costs at least two bytes, so shorter matches aren't worth it.
+const MIN_MATCH: usize = 4;
+/// How many previous positions are tried when looking for a match.
+const MAX_CHAIN: usize = 32;
+
+/// Decompresses an entire `P3DZ` file, magic included, into a plain P3D file.
+/// Big endian files have their sizes byte swapped, but the compressed data itself is the same.
+pub fn decompress_file(mut file: Bytes) -> Result<Bytes> {
+    let magic = file.safe_get_u32_le()?;
+    let _endian = match FileTypes::try_from(magic) {
+        Ok(FileTypes::CompressedPure3D) => EndianGuard::new(false),
+        Ok(FileTypes::CompressedPure3DBigEndian) => EndianGuard::new(true),
+        _ => {
+            return Err(eyre!(
+                "Not an LZR compressed P3D file (magic {:#010X})",
+                magic
+            ))
+        }
+    };
+
+    let decompressed_size = file.safe_get_u32()? as usize;
+    // Every output byte needs at least a bit of input, so don't trust huge sizes up front
+    let mut output = Vec::with_capacity(decompressed_size.min(file.remaining() * 8));
+
+    while output.len() < decompressed_size {
+        let compressed_size = file.safe_get_u32()? as usize;
+        let block_size = file.safe_get_u32()? as usize;
+        if file.remaining() < compressed_size {
+            return Err(eyre!(
+                "LZR block claims {} compressed bytes but only {} remain",
+                compressed_size,
+                file.remaining()
+            ));
+        }
+
+        let block = file.split_to(compressed_size);
+        decompress_block(&block, block_size, &mut output)?;
+    }
+
+    if output.len() != decompressed_size {
+        return Err(eyre!(
+            "LZR data decompressed to {} bytes, expected {}",
+            output.len(),
+            decompressed_size
+        ));
+    }
+
+    Ok(output.into())
+}
+
+/// Compresses a plain P3D file into a `P3DZ` file.
+pub fn compress_file(data: &[u8]) -> Vec<u8> {
+    let mut output = Vec::with_capacity(8 + data.len() / 2);
+    output.put_u32_le(FileTypes::CompressedPure3D.into());
+    output.put_u32_le(data.len() as u32);
+
+    for block in data.chunks(BLOCK_SIZE) {
+        let compressed = compress_block(block);
+        output.put_u32_le(compressed.len() as u32);
+        output.put_u32_le(block.len() as u32);
+        output.put_slice(&compressed);
+    }
+
+    output
+}
+
+fn read_extended_length(block: &mut &[u8]) -> Result<usize> {
+    let mut length = 15;
+    loop {
+        let byte = block.safe_get_u8()?;
+        if byte == 0 {
+            length += 255;
+        } else {
+            return Ok(length + byte as usize);
+        }
+    }
+}
+
+fn decompress_block(mut block: &[u8], block_size: usize, output: &mut Vec<u8>) -> Result<()> {
+    let end = output.len() + block_size;
+
+    while output.len() < end {
+        let code = block.safe_get_u8()?;
+        if code > 15 {
+            let length = match code & 0xF {
+                0 => read_extended_length(&mut block)?,
+                length => length as usize,
+            };
+            let distance = (code >> 4) as usize | (block.safe_get_u8()? as usize) << 4;
+            if distance > output.len() {
+                return Err(eyre!(
+                    "LZR back-reference of {} bytes reaches before the start of the output ({} bytes)",
+                    distance,
+                    output.len()
+                ));
+            }
+            if output.len() + length > end {
+                return Err(eyre!("LZR back-reference overruns its block"));
+            }
+
+            // The source and destination can overlap, so this has to go byte by byte
+            let start = output.len() - distance;
+            for i in start..start + length {
+                output.push(output[i]);
+            }
+        } else {
+            let length = match code {
+                0 => read_extended_length(&mut block)?,
+                length => length as usize,
+            };
+            if block.remaining() < length {
+                return Err(eyre!(
+                    "LZR literal run overrun by {} bytes",
+                    length - block.remaining()
+                ));
+            }
+            if output.len() + length > end {
+                return Err(eyre!("LZR literal run overruns its block"));
+            }
+
+            output.extend_from_slice(&block[..length]);
+            block.advance(length);
+        }
+    }
+
+    Ok(())
+}
+
+fn write_extended_length(output: &mut Vec<u8>, length: usize) {
+    let mut remaining = length - 15;
+    while remaining > 255 {
+        output.put_u8(0);
+        remaining -= 255;
+    }
+    output.put_u8(remaining as u8);
+}
+
+fn write_literals(output: &mut Vec<u8>, literals: &[u8]) {
+    if literals.is_empty() {
+        return;
+    }
+
+    if literals.len() < 16 {
+        output.put_u8(literals.len() as u8);
+    } else {
+        output.put_u8(0);
+        write_extended_length(output, literals.len());
+    }
+    output.put_slice(literals);
+}
+
+fn write_match(output: &mut Vec<u8>, length: usize, distance: usize) {
+    let nibble = if length < 16 { length as u8 } else { 0 };
+    output.put_u8(((distance & 0xF) as u8) << 4 | nibble);
+    if nibble == 0 {
+        write_extended_length(output, length);
+    }
+    output.put_u8((distance >> 4) as u8);
+}
+
+fn hash(bytes: &[u8]) -> usize {
+    let value = u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+    (value.wrapping_mul(0x9E37_79B1) >> 20) as usize
+}
+
+/// Greedy hash chain compressor. Distances with a low nibble of 0 can't be encoded, because the
+/// code byte would then look like a literal run, so those candidates are skipped.
+fn compress_block(block: &[u8]) -> Vec<u8> {
+    let mut output = Vec::with_capacity(block.len() / 2);
+    let mut head = vec![usize::MAX; 1 << 12];
+    let mut previous = vec![usize::MAX; block.len()];
+
+    let insert = |position: usize, head: &mut [usize], previous: &mut [usize]| {
+        if position + MIN_MATCH <= block.len() {
+            let h = hash(&block[position..]);
+            previous[position] = head[h];
+            head[h] = position;
+        }
+    };
+
+    let mut literal_start = 0;
+    let mut position = 0;
+    while position < block.len() {
+        let mut best_length = 0;
+        let mut best_distance = 0;
+
+        if position + MIN_MATCH <= block.len() {
+            let mut candidate = head[hash(&block[position..])];
+            let mut chain = 0;
+            while candidate != usize::MAX && chain < MAX_CHAIN {
+                let distance = position - candidate;
+                if distance > MAX_DISTANCE {
+                    break;
+                }
+
+                if distance & 0xF != 0 {
+                    let length = block[position..]
+                        .iter()
+                        .zip(&block[candidate..])
+                        .take_while(|(a, b)| a == b)
+                        .count();
+                    if length > best_length {
+                        best_length = length;
+                        best_distance = distance;
+                    }
+                }
+
+                candidate = previous[candidate];
+                chain += 1;
+            }
+        }
+
+        if best_length >= MIN_MATCH {
+            write_literals(&mut output, &block[literal_start..position]);
+            write_match(&mut output, best_length, best_distance);
+            for p in position..position + best_length {
+                insert(p, &mut head, &mut previous);
+            }
+            position += best_length;
+            literal_start = position;
+        } else {
+            insert(position, &mut head, &mut previous);
+            position += 1;
+        }
+    }
+    write_literals(&mut output, &block[literal_start..]);
+
+    output
+}
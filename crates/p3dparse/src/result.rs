@@ -1 +1 @@
-pub type Result<T> = std::result::Result<T, eyre::Error>;
+pub type Result<T, E = eyre::Error> = std::result::Result<T, E>;
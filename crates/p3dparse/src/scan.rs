@@ -0,0 +1,236 @@
+//! Header-only scanning, for when you only need to know what's in a file.
+//!
+//! [`scan_file`] walks the 12 byte chunk headers to build the tree without decoding any
+//! [`ChunkData`], which is most of the cost of [`parse_file`](crate::parse_file) on big files.
+//! Individual chunks, or whole subtrees, can then be decoded on demand from their byte ranges.
+
+use bytes::{Buf, Bytes};
+
+use crate::{
+    bytes_ext::{is_big_endian, BufResult, EndianGuard},
+    chunk::{data::data_enum::ChunkData, type_identifiers::ChunkType, Chunk, FileSpan, Span},
+    error::ParseError,
+    lzr, FileTypes, ParseOptions,
+};
+
+/// A chunk as seen by [`scan_file`], everything but its data.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ScannedChunk {
+    pub typ: ChunkType,
+    pub span: Span,
+    pub parent: Option<usize>,
+    pub children: Vec<usize>,
+    pub file_span: FileSpan,
+}
+
+/// The result of [`scan_file`]. Holds on to the (decompressed) file so chunks can be decoded later.
+#[derive(Clone, Debug)]
+pub struct ScannedFile {
+    file: Bytes,
+    big_endian: bool,
+    chunks: Vec<ScannedChunk>,
+}
+
+impl ScannedFile {
+    /// Every chunk header, in the same depth-first order [`parse_file`](crate::parse_file) produces.
+    pub fn chunks(&self) -> &[ScannedChunk] {
+        &self.chunks
+    }
+
+    /// The raw data of a chunk, without its header or children.
+    pub fn payload(&self, index: usize) -> Bytes {
+        let span = self.chunks[index].file_span;
+        self.file
+            .slice(span.offset + 12..span.offset + span.data_size as usize)
+    }
+
+    /// Decodes just the data of one chunk, leaving its children alone.
+    pub fn decode(&self, index: usize) -> Result<ChunkData, ParseError> {
+        let _endian = EndianGuard::new(self.big_endian);
+        let chunk = &self.chunks[index];
+        let mut payload = self.payload(index);
+        let size = payload.remaining();
+
+        ChunkData::from_chunk_type(chunk.typ, &mut payload).map_err(|e| {
+            ParseError::from_report(
+                e,
+                chunk.file_span.offset + 12 + size - payload.remaining(),
+                chunk.typ,
+                self.parent_lineage(index),
+            )
+        })
+    }
+
+    /// Fully parses a chunk and everything under it, the same way [`parse_file`](crate::parse_file)
+    /// would. The chunk is the root of the returned tree, but file spans are still absolute.
+    pub fn decode_subtree(&self, index: usize) -> Result<Vec<Chunk>, ParseError> {
+        let _endian = EndianGuard::new(self.big_endian);
+        let span = self.chunks[index].file_span;
+        let mut bytes = self
+            .file
+            .slice(span.offset..span.offset + span.total_size as usize);
+
+        let mut tree = Vec::new();
+        Chunk::parse(
+            &mut bytes,
+            &mut tree,
+            None,
+            0,
+            span.offset,
+            &ParseOptions::lenient(),
+            &mut Vec::new(),
+        )?;
+        Ok(tree)
+    }
+
+    /// Same format as [`Chunk::get_lineage`], but names are never known without decoding.
+    pub fn get_lineage(&self, index: usize) -> String {
+        let mut lineage = Vec::new();
+        let mut target = Some(index);
+        while let Some(index) = target {
+            let chunk = &self.chunks[index];
+            lineage.push(format!(
+                "<no name>:{:?}:{}@{}",
+                chunk.typ, chunk.span, chunk.file_span
+            ));
+            target = chunk.parent;
+        }
+        lineage.join(" -> ")
+    }
+
+    fn parent_lineage(&self, index: usize) -> String {
+        match self.chunks[index].parent {
+            Some(parent) => self.get_lineage(parent),
+            None => "Unknown".to_owned(),
+        }
+    }
+}
+
+/// Builds the chunk tree from headers alone. Compressed files still have to be decompressed first.
+///
+/// Unlike [`parse_file`](crate::parse_file) this can't find children hidden in a chunk's data
+/// after a parser stops short, since nothing gets parsed. Children are exactly what the headers say.
+pub fn scan_file(mut file: Bytes) -> Result<ScannedFile, ParseError> {
+    let magic = file
+        .clone()
+        .safe_get_u32_le()
+        .map_err(|_| ParseError::UnrecognisedFile { magic: 0 })?;
+    let file_type =
+        FileTypes::try_from(magic).map_err(|_| ParseError::UnrecognisedFile { magic })?;
+    let big_endian = file_type.is_big_endian();
+
+    let _endian = EndianGuard::new(big_endian);
+    if matches!(
+        file_type,
+        FileTypes::CompressedPure3D | FileTypes::CompressedPure3DBigEndian
+    ) {
+        file = lzr::decompress_file(file).map_err(|e| ParseError::Decompression {
+            message: e.to_string(),
+        })?;
+    } else if file_type == FileTypes::RZ {
+        return Err(ParseError::UnsupportedFile { file_type });
+    }
+
+    // Same check as Chunk::parse_root, compressed files only show what they are once decompressed
+    let magic = file
+        .clone()
+        .safe_get_u32()
+        .map_err(|_| ParseError::UnrecognisedFile { magic: 0 })?;
+    if ChunkType::from(magic) != ChunkType::DataFile {
+        return Err(ParseError::UnrecognisedFile { magic });
+    }
+
+    let mut scanned = ScannedFile {
+        file,
+        big_endian,
+        chunks: Vec::new(),
+    };
+    scan_chunk(&mut scanned, None, 0, 0)?;
+    Ok(scanned)
+}
+
+/// Reads the header at `offset` and then its children's, returns the size of the whole chunk.
+fn scan_chunk(
+    scanned: &mut ScannedFile,
+    parent: Option<usize>,
+    relative_index: usize,
+    offset: usize,
+) -> Result<usize, ParseError> {
+    let parent_lineage = |scanned: &ScannedFile| match parent {
+        Some(parent) => scanned.get_lineage(parent),
+        None => "Unknown".to_owned(),
+    };
+
+    let mut header = scanned.file.slice(offset.min(scanned.file.len())..);
+    let mut field = |typ: Option<ChunkType>, name: &str| {
+        header
+            .safe_get_u32()
+            .map_err(|_| ParseError::UnexpectedEof {
+                offset,
+                typ,
+                field: name.to_owned(),
+                needed: 4 - header.remaining(),
+                lineage: parent_lineage(scanned),
+            })
+    };
+    let typ = ChunkType::from(field(None, "chunk type")?);
+    let data_size = field(Some(typ), "data size")?;
+    let total_size = field(Some(typ), "total size")?;
+
+    if data_size < 12 || data_size > total_size {
+        return Err(ParseError::CorruptHeader {
+            offset,
+            typ,
+            data_size,
+            total_size,
+            lineage: parent_lineage(scanned),
+        });
+    }
+    if offset + total_size as usize > scanned.file.len() {
+        return Err(ParseError::UnexpectedEof {
+            offset: offset + 12,
+            typ: Some(typ),
+            field: "data".to_owned(),
+            needed: offset + total_size as usize - scanned.file.len(),
+            lineage: parent_lineage(scanned),
+        });
+    }
+
+    let index = scanned.chunks.len();
+    scanned.chunks.push(ScannedChunk {
+        typ,
+        span: Span {
+            absolute_index: index,
+            relative_index,
+        },
+        parent,
+        children: Vec::new(),
+        file_span: FileSpan {
+            offset,
+            data_size,
+            total_size,
+            big_endian: is_big_endian(),
+        },
+    });
+
+    let mut children = Vec::new();
+    let mut child_offset = offset + data_size as usize;
+    let end = offset + total_size as usize;
+    while child_offset < end {
+        children.push(scanned.chunks.len());
+        let size = scan_chunk(scanned, Some(index), children.len() - 1, child_offset)?;
+        child_offset += size;
+    }
+    if child_offset != end {
+        return Err(ParseError::CorruptHeader {
+            offset,
+            typ,
+            data_size,
+            total_size,
+            lineage: scanned.get_lineage(index),
+        });
+    }
+
+    scanned.chunks[index].children = children;
+    Ok(total_size as usize)
+}
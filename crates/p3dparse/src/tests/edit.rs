@@ -0,0 +1,190 @@
+use super::write::test_tree;
+use crate::{
+    chunk::{
+        data::{
+            data_enum::ChunkData,
+            kinds::{mesh::IndexList, name::Name},
+        },
+        edit::TreeEdit,
+        type_identifiers::ChunkType,
+        Chunk,
+    },
+    parse_file, write_file,
+};
+use bytes::Bytes;
+
+/// Checks that every link agrees with the spans, and that the arena is in depth-first order.
+fn assert_consistent(tree: &[Chunk]) {
+    let mut expected = 0;
+    fn visit(tree: &[Chunk], index: usize, expected: &mut usize) {
+        assert_eq!(index, *expected, "Tree isn't in depth-first order");
+        *expected += 1;
+
+        let chunk = &tree[index];
+        assert_eq!(chunk.span.absolute_index, index);
+        for (relative_index, child) in chunk.children.iter().enumerate() {
+            assert_eq!(tree[*child].parent, Some(index));
+            assert_eq!(tree[*child].span.relative_index, relative_index);
+            visit(tree, *child, expected);
+        }
+    }
+
+    assert_eq!(tree[0].parent, None);
+    visit(tree, 0, &mut expected);
+    assert_eq!(expected, tree.len(), "Tree has unreachable chunks");
+}
+
+/// Writing and parsing back gives the same tree, so edits are still valid files.
+fn assert_round_trips(tree: &[Chunk]) {
+    let parsed = parse_file(Bytes::from(write_file(tree).unwrap())).unwrap();
+    assert_eq!(super::write::clear_file_spans(parsed), tree);
+}
+
+fn types(tree: &[Chunk]) -> Vec<ChunkType> {
+    tree.iter().map(|c| c.typ).collect()
+}
+
+#[test]
+fn test_insert_chunk() {
+    let mut tree = test_tree();
+
+    // As the first child of the shader, pushing its params and everything after down by one
+    let index = tree
+        .insert_chunk(
+            1,
+            0,
+            ChunkType::IndexList,
+            ChunkData::IndexList(IndexList { indices: vec![3] }),
+        )
+        .unwrap();
+    assert_eq!(index, 2);
+    assert_eq!(tree[2].typ, ChunkType::IndexList);
+    assert_eq!(tree[1].children, &[2, 3, 4]);
+    assert_eq!(tree[5].data.get_name().unwrap().0, "mesh1");
+    assert_consistent(&tree);
+    assert_round_trips(&tree);
+
+    assert!(tree
+        .insert_chunk(1, 4, ChunkType::IndexList, ChunkData::None)
+        .is_err());
+    assert!(tree
+        .insert_chunk(100, 0, ChunkType::IndexList, ChunkData::None)
+        .is_err());
+}
+
+#[test]
+fn test_remove_and_insert_subtree() {
+    let mut tree = test_tree();
+
+    let mesh = tree.remove_subtree(4).unwrap();
+    assert_eq!(
+        types(&mesh),
+        [
+            ChunkType::Mesh,
+            ChunkType::OldPrimGroup,
+            ChunkType::PositionList,
+            ChunkType::UVList,
+            ChunkType::IndexList
+        ]
+    );
+    assert_consistent(&mesh);
+    assert_eq!(tree.len(), 5);
+    assert_eq!(tree[4].typ, ChunkType::CompressedQuaternionChannel);
+    assert_consistent(&tree);
+    assert_round_trips(&tree);
+
+    // Putting it back where it was gives the original tree
+    assert_eq!(tree.insert_subtree(0, 1, mesh).unwrap(), 4);
+    assert_eq!(tree, test_tree());
+
+    assert!(tree.remove_subtree(0).is_err());
+}
+
+#[test]
+fn test_insert_broken_subtree() {
+    let mut tree = test_tree();
+    let mesh = tree.remove_subtree(4).unwrap();
+    let before = tree.clone();
+
+    let broken = |edit: fn(&mut Vec<Chunk>)| {
+        let mut subtree = mesh.clone();
+        edit(&mut subtree);
+        subtree
+    };
+    for subtree in [
+        Vec::new(),
+        // Root isn't first
+        broken(|subtree| subtree[0].parent = Some(1)),
+        // Child out of range
+        broken(|subtree| subtree[1].children.push(10)),
+        // Shared child
+        broken(|subtree| subtree[0].children.push(2)),
+        // Cycle back to the root
+        broken(|subtree| subtree[2].children.push(0)),
+        // Parent doesn't match
+        broken(|subtree| subtree[2].parent = Some(0)),
+        // Chunks nothing points to
+        broken(|subtree| subtree[1].children.clear()),
+    ] {
+        assert!(tree.insert_subtree(0, 1, subtree).is_err());
+        assert_eq!(tree, before);
+    }
+
+    assert_eq!(tree.insert_subtree(0, 1, mesh).unwrap(), 4);
+    assert_consistent(&tree);
+}
+
+#[test]
+fn test_move_subtree() {
+    let mut tree = test_tree();
+
+    // The prim group moves from the mesh to the end of the shader
+    let index = tree.move_subtree(5, 1, 2).unwrap();
+    assert_eq!(index, 4);
+    assert_eq!(tree[4].typ, ChunkType::OldPrimGroup);
+    assert_eq!(tree[4].parent, Some(1));
+    assert_eq!(tree[4].children, &[5, 6, 7]);
+    assert!(tree[8].children.is_empty());
+    assert_consistent(&tree);
+    assert_round_trips(&tree);
+
+    // Moving within the same parent, the channel becomes the first child of the root
+    let index = tree.move_subtree(9, 0, 0).unwrap();
+    assert_eq!(index, 1);
+    assert_consistent(&tree);
+
+    // A failed move leaves the tree alone
+    let before = tree.clone();
+    assert!(tree.move_subtree(2, 5, 0).is_err());
+    assert!(tree.move_subtree(2, 1, 10).is_err());
+    assert!(tree.move_subtree(0, 1, 0).is_err());
+    assert_eq!(tree, before);
+}
+
+#[test]
+fn test_rename_and_replace_data() {
+    let mut tree = test_tree();
+
+    tree.rename(4, "mesh2").unwrap();
+    assert_eq!(tree[4].data.get_name(), Some(Name("mesh2".into())));
+    assert!(tree.rename(6, "positions").is_err());
+    assert_round_trips(&tree);
+
+    tree[8].unparsed_data = Bytes::from_static(&[1, 2, 3, 4]);
+    let old = tree
+        .replace_data(
+            8,
+            ChunkData::IndexList(IndexList {
+                indices: vec![2, 1, 0],
+            }),
+        )
+        .unwrap();
+    assert_eq!(
+        old,
+        ChunkData::IndexList(IndexList {
+            indices: vec![0, 1, 2]
+        })
+    );
+    assert!(tree[8].unparsed_data.is_empty());
+    assert_round_trips(&tree);
+}
@@ -0,0 +1,149 @@
+use crate::{
+    chunk::{
+        data::{
+            data_enum::ChunkData,
+            kinds::shader_param::{ShaderParam, ShaderParamValue},
+        },
+        type_identifiers::ChunkType,
+        Chunk,
+    },
+    lzr::compress_file,
+    parse_file, write_file,
+};
+
+/// Same as `raw_chunk` in the write tests, but with the header in either byte order.
+fn raw_chunk(big_endian: bool, typ: ChunkType, data: &[u8], children: &[u8]) -> Vec<u8> {
+    let u32_bytes = |value: u32| {
+        if big_endian {
+            value.to_be_bytes()
+        } else {
+            value.to_le_bytes()
+        }
+    };
+
+    let mut bytes = Vec::new();
+    bytes.extend_from_slice(&u32_bytes(typ.into()));
+    bytes.extend_from_slice(&u32_bytes(12 + data.len() as u32));
+    bytes.extend_from_slice(&u32_bytes(12 + (data.len() + children.len()) as u32));
+    bytes.extend_from_slice(data);
+    bytes.extend_from_slice(children);
+    bytes
+}
+
+/// Checks every chunk is marked as coming from a big endian file, then clears that so the tree can
+/// be compared with a little endian one.
+fn clear_big_endian(mut tree: Vec<Chunk>) -> Vec<Chunk> {
+    for chunk in &mut tree {
+        assert!(chunk.file_span.big_endian);
+        chunk.file_span.big_endian = false;
+    }
+    tree
+}
+
+fn shader_params(big_endian: bool) -> Vec<u8> {
+    let int = if big_endian {
+        0x1234_5678u32.to_be_bytes()
+    } else {
+        0x1234_5678u32.to_le_bytes()
+    };
+    let float = if big_endian {
+        1.5f32.to_be_bytes()
+    } else {
+        1.5f32.to_le_bytes()
+    };
+    // 0xAARRGGBB
+    let colour = if big_endian {
+        [0xFF, 0x10, 0x20, 0x30]
+    } else {
+        [0x30, 0x20, 0x10, 0xFF]
+    };
+
+    let children = [
+        raw_chunk(
+            big_endian,
+            ChunkType::ShaderIntParam,
+            &[b"TEST".as_slice(), &int].concat(),
+            &[],
+        ),
+        raw_chunk(
+            big_endian,
+            ChunkType::ShaderFloatParam,
+            &[b"TEST".as_slice(), &float].concat(),
+            &[],
+        ),
+        raw_chunk(
+            big_endian,
+            ChunkType::ShaderColourParam,
+            &[b"TEST".as_slice(), &colour].concat(),
+            &[],
+        ),
+    ]
+    .concat();
+
+    raw_chunk(big_endian, ChunkType::DataFile, &[], &children)
+}
+
+#[test]
+fn test_big_endian_matches_little_endian() {
+    let big = shader_params(true);
+    // The magic is byte swapped, which is how the byte order is picked
+    assert_eq!(&big[..4], &[0xFF, 0x44, 0x33, 0x50]);
+
+    let little = parse_file(shader_params(false).into()).expect("Failed to parse LE file");
+    let big = parse_file(big.into()).expect("Failed to parse BE file");
+
+    assert_eq!(big.len(), 4);
+    assert_eq!(
+        big[1].data,
+        ChunkData::ShaderParam(ShaderParam {
+            param: "TEST".to_string(),
+            value: ShaderParamValue::Int(0x1234_5678),
+        })
+    );
+    assert_eq!(
+        big[3].data,
+        ChunkData::ShaderParam(ShaderParam {
+            param: "TEST".to_string(),
+            value: ShaderParamValue::Colour([0xFF, 0x10, 0x20, 0x30].into()),
+        })
+    );
+    assert_eq!(clear_big_endian(big), little);
+}
+
+#[test]
+fn test_big_endian_compressed() {
+    let file = shader_params(true);
+
+    // Swap the magic and the block sizes, the compressed data itself doesn't change
+    let mut compressed = compress_file(&file);
+    for word in compressed[..16].chunks_mut(4) {
+        word.reverse();
+    }
+    assert_eq!(&compressed[..4], b"ZD3P");
+
+    let little = parse_file(shader_params(false).into()).unwrap();
+    let big = parse_file(compressed.into()).expect("Failed to parse compressed BE file");
+    assert_eq!(clear_big_endian(big), little);
+
+    // The byte order doesn't leak into the next parse on this thread
+    assert_eq!(parse_file(shader_params(false).into()).unwrap(), little);
+}
+
+#[test]
+fn test_big_endian_write() {
+    // Everything was parsed, so it's all converted
+    let big = parse_file(shader_params(true).into()).unwrap();
+    assert_eq!(write_file(&big).unwrap(), shader_params(false));
+
+    // Nobody knows what's in a photon map, or which parts of it would need swapping
+    let unknown = |big_endian| {
+        let child = raw_chunk(big_endian, ChunkType::LightPhotonMap, &[1, 2, 3, 4], &[]);
+        raw_chunk(big_endian, ChunkType::DataFile, &[], &child)
+    };
+    let little = parse_file(unknown(false).into()).unwrap();
+    assert_eq!(write_file(&little).unwrap(), unknown(false));
+
+    let big = parse_file(unknown(true).into()).unwrap();
+    let error = write_file(&big).unwrap_err().to_string();
+    assert!(error.contains("LightPhotonMap"), "{}", error);
+}
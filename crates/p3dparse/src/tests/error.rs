@@ -0,0 +1,178 @@
+use super::write::raw_chunk;
+use crate::{
+    chunk::type_identifiers::ChunkType,
+    error::{DiagnosticKind, ParseError},
+    parse_file, parse_file_with_options, ParseOptions,
+};
+
+#[test]
+fn test_error_truncated_data() {
+    // Render status needs a u32 but has no data at all
+    let child = raw_chunk(ChunkType::RenderStatus, &[], &[]);
+    let file = raw_chunk(ChunkType::DataFile, &[], &child);
+
+    match parse_file(file.into()) {
+        Err(ParseError::UnexpectedEof {
+            offset,
+            typ,
+            field,
+            needed,
+            lineage,
+        }) => {
+            assert_eq!(offset, 24);
+            assert_eq!(typ, Some(ChunkType::RenderStatus));
+            assert_eq!(field, "data.cast_shadow");
+            assert_eq!(needed, 4);
+            assert!(lineage.contains("DataFile"));
+        }
+        r => panic!("Expected UnexpectedEof, got {:?}", r),
+    }
+}
+
+#[test]
+fn test_error_nested_field() {
+    // A movie's name, version and the first six fields of its placement, cut off at the colour
+    let mut movie = vec![4, b'm', b'o', b'v', b'i'];
+    for value in [1u32, 0, 0, 64, 64, 0, 0] {
+        movie.extend_from_slice(&value.to_le_bytes());
+    }
+    let child = raw_chunk(ChunkType::FrontendMovie, &movie, &[]);
+    let file = raw_chunk(ChunkType::DataFile, &[], &child);
+
+    match parse_file(file.into()) {
+        Err(ParseError::UnexpectedEof { field, .. }) => {
+            assert_eq!(field, "data.element.colour")
+        }
+        r => panic!("Expected UnexpectedEof, got {:?}", r),
+    }
+}
+
+#[test]
+fn test_error_truncated_file() {
+    let child = raw_chunk(ChunkType::RenderStatus, &[1, 0, 0, 0], &[]);
+    let mut file = raw_chunk(ChunkType::DataFile, &[], &child);
+    // Cut the child's header in half
+    file.truncate(18);
+
+    let error = parse_file(file.into()).unwrap_err();
+    assert_eq!(error.offset(), Some(12));
+    assert_eq!(error.chunk_type(), Some(ChunkType::RenderStatus));
+    assert!(matches!(error, ParseError::UnexpectedEof { ref field, .. } if field == "data size"));
+}
+
+#[test]
+fn test_error_invalid_value() {
+    let mut image = vec![4, b'i', b'm', b'g', 0];
+    // Version, width, height, bpp, palettized, has_alpha
+    for value in [14000u32, 8, 8, 32, 0, 1] {
+        image.extend_from_slice(&value.to_le_bytes());
+    }
+    // Not an image format
+    image.extend_from_slice(&0xFFFFu32.to_le_bytes());
+
+    let child = raw_chunk(ChunkType::Image, &image, &[]);
+    let file = raw_chunk(ChunkType::DataFile, &[], &child);
+
+    match parse_file(file.into()) {
+        Err(ParseError::InvalidValue {
+            offset, typ, field, ..
+        }) => {
+            assert_eq!(offset, 24 + image.len());
+            assert_eq!(typ, ChunkType::Image);
+            assert_eq!(field, "data.image_format");
+        }
+        r => panic!("Expected InvalidValue, got {:?}", r),
+    }
+}
+
+#[test]
+fn test_error_file_level() {
+    assert_eq!(
+        parse_file(vec![1, 2, 3, 4].into()),
+        Err(ParseError::UnrecognisedFile { magic: 0x04030201 })
+    );
+
+    let header = raw_chunk(ChunkType::DataFile, &[], &[]);
+    let mut corrupt = header.clone();
+    // Data size bigger than total size
+    corrupt[4] = 16;
+    assert!(matches!(
+        parse_file(corrupt.into()),
+        Err(ParseError::CorruptHeader {
+            offset: 0,
+            typ: ChunkType::DataFile,
+            data_size: 16,
+            total_size: 12,
+            ..
+        })
+    ));
+
+    let mut compressed = header;
+    compressed[..4].copy_from_slice(&0x5A44_3350u32.to_le_bytes());
+    assert!(matches!(
+        parse_file(compressed.into()),
+        Err(ParseError::Decompression { .. })
+    ));
+}
+
+#[test]
+fn test_diagnostics() {
+    // Nobody knows what's in a photon map
+    let unparsed = raw_chunk(ChunkType::LightPhotonMap, &[1, 2, 3, 4], &[]);
+    let unrecognised = raw_chunk(ChunkType::Other(0x7FFF0001), &[], &[]);
+    // Render status only reads a single u32, the rest is left over
+    let leftover = raw_chunk(ChunkType::RenderStatus, &[1, 0, 0, 0, 0xAA, 0xBB], &[]);
+    // Same again, but the rest is a child that should have come after the data
+    let child = raw_chunk(ChunkType::RenderStatus, &[2, 0, 0, 0], &[]);
+    let misaligned = raw_chunk(
+        ChunkType::RenderStatus,
+        &[[1, 0, 0, 0].as_slice(), &child].concat(),
+        &[],
+    );
+    let children = [unparsed, unrecognised, leftover, misaligned].concat();
+    let file = raw_chunk(ChunkType::DataFile, &[], &children);
+
+    let (tree, diagnostics) =
+        parse_file_with_options(file.clone().into(), &ParseOptions::lenient()).unwrap();
+    assert_eq!(tree.len(), 6);
+    assert_eq!(tree[4].children, vec![5]);
+
+    let kinds: Vec<_> = diagnostics.iter().map(|d| d.kind.clone()).collect();
+    assert_eq!(
+        kinds,
+        vec![
+            DiagnosticKind::UnparsedChunk,
+            DiagnosticKind::UnrecognisedChunk,
+            DiagnosticKind::LeftoverBytes { count: 2 },
+            DiagnosticKind::MisalignedChildren {
+                consumed: 4,
+                expected: 20,
+                children: 1
+            },
+        ]
+    );
+    assert_eq!(diagnostics[0].offset, 12);
+    assert_eq!(diagnostics[1].typ, ChunkType::Other(0x7FFF0001));
+    assert!(diagnostics[2]
+        .lineage
+        .starts_with("<no name>:RenderStatus:3:2"));
+
+    // Strict mode stops at the first one
+    match parse_file_with_options(file.into(), &ParseOptions::strict()) {
+        Err(ParseError::Strict(diagnostic)) => {
+            assert_eq!(diagnostic, diagnostics[0]);
+        }
+        r => panic!("Expected a strict mode error, got {:?}", r),
+    }
+}
+
+#[test]
+fn test_strict_accepts_clean_files() {
+    let child = raw_chunk(ChunkType::RenderStatus, &[1, 0, 0, 0], &[]);
+    let file = raw_chunk(ChunkType::DataFile, &[], &child);
+
+    let (tree, diagnostics) =
+        parse_file_with_options(file.into(), &ParseOptions::strict()).unwrap();
+    assert_eq!(tree.len(), 2);
+    assert!(diagnostics.is_empty());
+}
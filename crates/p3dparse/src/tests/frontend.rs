@@ -0,0 +1,436 @@
+use super::write::{clear_file_spans, push, raw_chunk};
+use crate::{
+    chunk::{
+        data::{
+            data_enum::ChunkData,
+            kinds::{
+                frontend::{
+                    FrontendElement, FrontendGroup, FrontendImageResource, FrontendLayer,
+                    FrontendMultiSprite, FrontendMultiText, FrontendPage, FrontendPolygon,
+                    FrontendProject, FrontendResource, FrontendScreen, FrontendStringHardCoded,
+                    FrontendStringTextBible,
+                },
+                name::Name,
+                shared::Vector3,
+                version::Version,
+            },
+            write_trait::Write,
+        },
+        type_identifiers::ChunkType,
+        Chunk,
+    },
+    error::ParseError,
+    parse_file, write_file,
+};
+use bytes::Bytes;
+
+fn named(name: &str) -> (Name, Version) {
+    (Name(name.into()), Version(1))
+}
+
+fn element(x: i32, y: i32) -> FrontendElement {
+    FrontendElement {
+        position_x: x,
+        position_y: y,
+        dimension_x: 64,
+        dimension_y: 32,
+        justification_x: 4,
+        justification_y: 2,
+        colour: [255, 128, 64, 32].into(),
+        translucency: 255,
+        rotation: 0.5,
+    }
+}
+
+/// A project with one screen showing one page, covering every frontend chunk type that's parsed
+fn frontend_tree() -> Vec<Chunk> {
+    let mut tree = Vec::new();
+    let root = push(&mut tree, None, ChunkType::DataFile, ChunkData::None);
+
+    let (name, version) = named("project");
+    let project = push(
+        &mut tree,
+        Some(root),
+        ChunkType::FrontendProject,
+        ChunkData::FrontendProject(
+            name,
+            version,
+            FrontendProject {
+                resolution_x: 640,
+                resolution_y: 480,
+                platform: "PC".into(),
+                page_path: "pages/".into(),
+                resource_path: "art/".into(),
+                screen_path: "screens/".into(),
+            },
+        ),
+    );
+
+    let (name, version) = named("page");
+    let page = push(
+        &mut tree,
+        Some(project),
+        ChunkType::FrontendPage,
+        ChunkData::FrontendPage(
+            name,
+            version,
+            FrontendPage {
+                resolution_x: 640,
+                resolution_y: 480,
+            },
+        ),
+    );
+    let (name, version) = named("logo.png");
+    push(
+        &mut tree,
+        Some(page),
+        ChunkType::FrontendImageResource,
+        ChunkData::FrontendImageResource(
+            name,
+            version,
+            FrontendImageResource {
+                filename: "art/logo.png".into(),
+            },
+        ),
+    );
+    let (name, version) = named("style");
+    push(
+        &mut tree,
+        Some(page),
+        ChunkType::FrontendTextStyleResource,
+        ChunkData::FrontendResource(
+            name,
+            version,
+            FrontendResource {
+                filename: "fonts/style.p3d".into(),
+                inventory_name: "style".into(),
+            },
+        ),
+    );
+
+    let (name, version) = named("layer");
+    let layer = push(
+        &mut tree,
+        Some(page),
+        ChunkType::FrontendLayer,
+        ChunkData::FrontendLayer(
+            name,
+            version,
+            FrontendLayer {
+                visible: 1,
+                editable: 0,
+                alpha: 255,
+            },
+        ),
+    );
+    let (name, version) = named("group");
+    let group = push(
+        &mut tree,
+        Some(layer),
+        ChunkType::FrontendGroup,
+        ChunkData::FrontendGroup(name, version, FrontendGroup { alpha: 128 }),
+    );
+    let (name, version) = named("logo");
+    push(
+        &mut tree,
+        Some(group),
+        ChunkType::FrontendMultiSprite,
+        ChunkData::FrontendMultiSprite(
+            name,
+            version,
+            FrontendMultiSprite {
+                element: element(10, 20),
+                image_names: vec!["logo.png".into()],
+            },
+        ),
+    );
+    let (name, version) = named("title");
+    let text = push(
+        &mut tree,
+        Some(layer),
+        ChunkType::FrontendMultiText,
+        ChunkData::FrontendMultiText(
+            name,
+            version,
+            FrontendMultiText {
+                element: element(-5, 100),
+                text_style_name: "style".into(),
+                shadow_enabled: 1,
+                shadow_colour: [255, 0, 0, 0].into(),
+                shadow_offset_x: 2,
+                shadow_offset_y: -2,
+                current_text: 0,
+            },
+        ),
+    );
+    push(
+        &mut tree,
+        Some(text),
+        ChunkType::FrontendStringTextBible,
+        ChunkData::FrontendStringTextBible(FrontendStringTextBible {
+            bible_name: "srr2".into(),
+            string_id: "TITLE".into(),
+        }),
+    );
+    push(
+        &mut tree,
+        Some(text),
+        ChunkType::FrontendStringHardCoded,
+        ChunkData::FrontendStringHardCoded(FrontendStringHardCoded {
+            string: "Title".into(),
+        }),
+    );
+    let (name, version) = named("backdrop");
+    push(
+        &mut tree,
+        Some(layer),
+        ChunkType::FrontendPolygon,
+        ChunkData::FrontendPolygon(
+            name,
+            version,
+            FrontendPolygon {
+                translucency: 200,
+                points: vec![
+                    Vector3::new(0.0, 0.0, 0.0),
+                    Vector3::new(640.0, 0.0, 0.0),
+                    Vector3::new(640.0, 480.0, 0.0),
+                ],
+                colours: vec![[255, 0, 0, 0].into(); 3],
+            },
+        ),
+    );
+
+    let (name, version) = named("screen");
+    push(
+        &mut tree,
+        Some(project),
+        ChunkType::FrontendScreen,
+        ChunkData::FrontendScreen(
+            name,
+            version,
+            FrontendScreen {
+                page_names: vec!["page".into()],
+            },
+        ),
+    );
+
+    tree
+}
+
+#[test]
+fn test_frontend_round_trip() {
+    let tree = frontend_tree();
+    let bytes = write_file(&tree).unwrap();
+
+    let parsed = parse_file(Bytes::from(bytes.clone())).unwrap();
+    assert!(parsed.iter().all(|chunk| chunk.unparsed_data.is_empty()));
+    assert_eq!(clear_file_spans(parsed.clone()), tree);
+    assert_eq!(write_file(&parsed).unwrap(), bytes);
+}
+
+#[test]
+fn test_frontend_polygon_hostile_count() {
+    let mut data = Vec::new();
+    // Name, version, translucency, then a point count that doesn't fit
+    data.extend_from_slice(b"\x04poly");
+    for value in [1u32, 0, u32::MAX] {
+        data.extend_from_slice(&value.to_le_bytes());
+    }
+    let bytes = raw_chunk(
+        ChunkType::DataFile,
+        &[],
+        &raw_chunk(ChunkType::FrontendPolygon, &data, &[]),
+    );
+
+    assert!(matches!(
+        parse_file(Bytes::from(bytes)),
+        Err(ParseError::UnexpectedEof { .. })
+    ));
+}
+
+#[test]
+fn test_frontend_text_bytes() {
+    #[rustfmt::skip]
+    let project: &[u8] = &[
+        0x04, b'm', b'e', b'n', b'u', // name
+        0x01, 0x00, 0x00, 0x00, // version
+        0x80, 0x02, 0x00, 0x00, 0xE0, 0x01, 0x00, 0x00, // 640 by 480
+        0x02, b'P', b'C', // platform
+        0x04, b'p', b'a', b'g', b'e', // page path
+        0x03, b'a', b'r', b't', // resource path
+        0x00, // no screen path
+    ];
+    #[rustfmt::skip]
+    let text: &[u8] = &[
+        0x05, b't', b'i', b't', b'l', b'e', // name
+        0x01, 0x00, 0x00, 0x00, // version
+        0xFB, 0xFF, 0xFF, 0xFF, 0x64, 0x00, 0x00, 0x00, // position -5, 100
+        0x40, 0x00, 0x00, 0x00, 0x20, 0x00, 0x00, 0x00, // dimension 64, 32
+        0x04, 0x00, 0x00, 0x00, 0x02, 0x00, 0x00, 0x00, // justification centre, top
+        0x20, 0x40, 0x80, 0xFF, // colour
+        0xFF, 0x00, 0x00, 0x00, // translucency
+        0x00, 0x00, 0x00, 0x3F, // rotation 0.5
+        0x05, b's', b't', b'y', b'l', b'e', // text style
+        0x01, // shadow enabled
+        0x00, 0x00, 0x00, 0xFF, // shadow colour
+        0x02, 0x00, 0x00, 0x00, 0xFE, 0xFF, 0xFF, 0xFF, // shadow offset 2, -2
+        0x00, 0x00, 0x00, 0x00, // current text
+    ];
+    #[rustfmt::skip]
+    let bible: &[u8] = &[
+        0x04, b's', b'r', b'r', b'2', // bible name
+        0x05, b'T', b'I', b'T', b'L', b'E', // string id
+    ];
+    let hard_coded: &[u8] = &[0x05, b'T', b'i', b't', b'l', b'e'];
+    let strings = [
+        raw_chunk(ChunkType::FrontendStringTextBible, bible, &[]),
+        raw_chunk(ChunkType::FrontendStringHardCoded, hard_coded, &[]),
+    ]
+    .concat();
+    let bytes = raw_chunk(
+        ChunkType::DataFile,
+        &[],
+        &raw_chunk(
+            ChunkType::FrontendProject,
+            project,
+            &raw_chunk(ChunkType::FrontendMultiText, text, &strings),
+        ),
+    );
+
+    let file = parse_file(Bytes::from(bytes.clone())).unwrap();
+    assert_eq!(
+        file[1].data,
+        ChunkData::FrontendProject(
+            Name("menu".into()),
+            Version(1),
+            FrontendProject {
+                resolution_x: 640,
+                resolution_y: 480,
+                platform: "PC".into(),
+                page_path: "page".into(),
+                resource_path: "art".into(),
+                screen_path: "".into(),
+            },
+        )
+    );
+    assert_eq!(
+        file[2].data,
+        ChunkData::FrontendMultiText(
+            Name("title".into()),
+            Version(1),
+            FrontendMultiText {
+                element: element(-5, 100),
+                text_style_name: "style".into(),
+                shadow_enabled: 1,
+                shadow_colour: [255, 0, 0, 0].into(),
+                shadow_offset_x: 2,
+                shadow_offset_y: -2,
+                current_text: 0,
+            },
+        )
+    );
+    assert_eq!(
+        file[3].data,
+        ChunkData::FrontendStringTextBible(FrontendStringTextBible {
+            bible_name: "srr2".into(),
+            string_id: "TITLE".into(),
+        })
+    );
+    assert_eq!(
+        file[4].data,
+        ChunkData::FrontendStringHardCoded(FrontendStringHardCoded {
+            string: "Title".into(),
+        })
+    );
+    assert!(file.iter().all(|chunk| chunk.unparsed_data.is_empty()));
+    assert_eq!(write_file(&file).unwrap(), bytes);
+}
+
+#[test]
+fn test_frontend_polygon_mismatched_lengths() {
+    let polygon = FrontendPolygon {
+        translucency: 255,
+        points: vec![Vector3::new(1.0, 2.0, 3.0), Vector3::new(4.0, 5.0, 6.0)],
+        colours: vec![[255, 1, 2, 3].into()],
+    };
+    let mut bytes = Vec::new();
+    polygon.write(&mut bytes, ChunkType::FrontendPolygon);
+
+    #[rustfmt::skip]
+    let expected: &[u8] = &[
+        0xFF, 0x00, 0x00, 0x00, // translucency
+        0x01, 0x00, 0x00, 0x00, // one point
+        0x00, 0x00, 0x80, 0x3F, 0x00, 0x00, 0x00, 0x40, 0x00, 0x00, 0x40, 0x40, // 1, 2, 3
+        0x03, 0x02, 0x01, 0xFF, // colour
+    ];
+    assert_eq!(bytes, expected);
+}
+
+#[test]
+fn test_frontend_sprite_and_old_resources() {
+    #[rustfmt::skip]
+    let sprite: &[u8] = &[
+        0x04, b'l', b'o', b'g', b'o', // name
+        0x01, 0x00, 0x00, 0x00, // version
+        0x0A, 0x00, 0x00, 0x00, 0xEC, 0xFF, 0xFF, 0xFF, // position 10, -20
+        0x40, 0x00, 0x00, 0x00, 0x20, 0x00, 0x00, 0x00, // dimension 64, 32
+        0x04, 0x00, 0x00, 0x00, 0x02, 0x00, 0x00, 0x00, // justification centre, top
+        0x20, 0x40, 0x80, 0xFF, // colour
+        0xFF, 0x00, 0x00, 0x00, // translucency
+        0x00, 0x00, 0x00, 0x3F, // rotation 0.5
+        0x01, 0x00, 0x00, 0x00, // one image
+        0x08, b'l', b'o', b'g', b'o', b'.', b'p', b'n', b'g',
+    ];
+    #[rustfmt::skip]
+    let style: &[u8] = &[
+        0x04, b'f', b'o', b'n', b't', // name
+        0x01, 0x00, 0x00, 0x00, // version
+        0x08, b'f', b'o', b'n', b't', b'.', b'p', b'3', b'd', // filename
+        0x04, b's', b'a', b'n', b's', // inventory name
+    ];
+    let mut children = raw_chunk(ChunkType::FrontendSprite, sprite, &[]);
+    children.extend(raw_chunk(
+        ChunkType::FrontendOldResourceTextStyle,
+        style,
+        &[],
+    ));
+    children.extend(raw_chunk(
+        ChunkType::FrontendOldResourceTextBible,
+        style,
+        &[],
+    ));
+    let bytes = raw_chunk(ChunkType::DataFile, &[], &children);
+
+    let parsed = parse_file(Bytes::from(bytes.clone())).unwrap();
+    assert!(parsed.iter().all(|chunk| chunk.unparsed_data.is_empty()));
+    assert_eq!(
+        parsed[1].data,
+        ChunkData::FrontendMultiSprite(
+            Name("logo".into()),
+            Version(1),
+            FrontendMultiSprite {
+                element: FrontendElement {
+                    position_y: -20,
+                    ..element(10, 0)
+                },
+                image_names: vec!["logo.png".into()],
+            },
+        )
+    );
+    for chunk in &parsed[2..] {
+        assert_eq!(
+            chunk.data,
+            ChunkData::FrontendResource(
+                Name("font".into()),
+                Version(1),
+                FrontendResource {
+                    filename: "font.p3d".into(),
+                    inventory_name: "sans".into(),
+                },
+            )
+        );
+    }
+    assert_eq!(parsed[2].typ, ChunkType::FrontendOldResourceTextStyle);
+    assert_eq!(parsed[3].typ, ChunkType::FrontendOldResourceTextBible);
+    assert_eq!(write_file(&parsed).unwrap(), bytes);
+}
@@ -0,0 +1,139 @@
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+};
+
+use super::write::{raw_chunk, test_tree};
+use crate::{
+    chunk::type_identifiers::ChunkType, error::ParseError, lzr, parse_file, write_file, FileTypes,
+};
+use proptest::prelude::*;
+
+/// Remembers the largest single allocation made on each thread, so tests running in parallel
+/// don't see each other's allocations.
+struct LargestAllocation;
+
+thread_local! {
+    static LARGEST: Cell<usize> = const { Cell::new(0) };
+}
+
+fn record(size: usize) {
+    let _ = LARGEST.try_with(|largest| largest.set(largest.get().max(size)));
+}
+
+unsafe impl GlobalAlloc for LargestAllocation {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        record(layout.size());
+        System.alloc(layout)
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        record(layout.size());
+        System.alloc_zeroed(layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        record(new_size);
+        System.realloc(ptr, layout, new_size)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: LargestAllocation = LargestAllocation;
+
+/// Parses `file` and checks nothing allocated was out of proportion to its size.
+/// The chunk arena is the biggest thing that legitimately grows with the input, a few hundred
+/// bytes per 12 byte header, so anything past that means a count was trusted blindly.
+fn parse_bounded(file: Vec<u8>) -> Result<(), TestCaseError> {
+    let limit = 256 * file.len() + (1 << 20);
+    LARGEST.with(|largest| largest.set(0));
+    let _ = parse_file(file.into());
+    let largest = LARGEST.with(|largest| largest.get());
+
+    prop_assert!(
+        largest <= limit,
+        "Allocated {} bytes at once, limit was {}",
+        largest,
+        limit
+    );
+    Ok(())
+}
+
+#[test]
+fn test_hostile_counts() {
+    // Every count-prefixed list claims u32::MAX elements with nothing behind it.
+    // The prefix is whatever comes before the count: names, versions, fourccs.
+    for (typ, prefix) in [
+        (ChunkType::PositionList, &[][..]),
+        (ChunkType::UVList, &[]),
+        (ChunkType::IndexList, &[]),
+        (ChunkType::ImageData, &[]),
+        (ChunkType::IntersectDSG, &[]),
+        (ChunkType::P3DMultiControllerTracks, &[]),
+        (ChunkType::TerrainTypeList, &[0; 4]),
+        (ChunkType::WBSpline, &[0]),
+        (ChunkType::Float1Channel, &[0; 8]),
+    ] {
+        let mut data = prefix.to_vec();
+        data.extend_from_slice(&[0xFF; 4]);
+        let child = raw_chunk(typ, &data, &[]);
+        let file = raw_chunk(ChunkType::DataFile, &[], &child);
+
+        LARGEST.with(|largest| largest.set(0));
+        let result = parse_file(file.into());
+        assert!(
+            matches!(result, Err(ParseError::UnexpectedEof { .. })),
+            "{:?} gave {:?}",
+            typ,
+            result
+        );
+        assert!(LARGEST.with(|largest| largest.get()) < 1 << 20);
+    }
+}
+
+proptest! {
+    #[test]
+    fn prop_random_bytes(file in prop::collection::vec(any::<u8>(), 0..512)) {
+        parse_bounded(file)?;
+    }
+
+    #[test]
+    fn prop_random_chunks(data in prop::collection::vec(any::<u8>(), 0..512)) {
+        // A valid magic gets past the file type check and into the chunk parser
+        let mut file = u32::from(FileTypes::Pure3D).to_le_bytes().to_vec();
+        file.extend_from_slice(&data);
+        parse_bounded(file)?;
+    }
+
+    #[test]
+    fn prop_corrupted_file(
+        corruptions in prop::collection::vec(
+            (any::<prop::sample::Index>(), prop_oneof![any::<u32>(), Just(u32::MAX), 0u32..16]),
+            1..8,
+        ),
+    ) {
+        // Overwrite u32s of a real file, which hits header sizes and counts far more often than random bytes
+        let mut file = write_file(&test_tree()).unwrap();
+        for (index, value) in corruptions {
+            let offset = index.index(file.len() - 3);
+            file[offset..offset + 4].copy_from_slice(&value.to_le_bytes());
+        }
+        parse_bounded(file)?;
+    }
+
+    #[test]
+    fn prop_corrupted_compressed_file(
+        corruptions in prop::collection::vec((any::<prop::sample::Index>(), any::<u8>()), 1..8),
+    ) {
+        let mut file = lzr::compress_file(&write_file(&test_tree()).unwrap());
+        for (index, value) in corruptions {
+            let offset = index.index(file.len());
+            file[offset] = value;
+        }
+        parse_bounded(file)?;
+    }
+}
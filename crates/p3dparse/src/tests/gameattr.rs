@@ -0,0 +1,146 @@
+use super::write::raw_chunk;
+use crate::{
+    chunk::{
+        data::{
+            data_enum::ChunkData,
+            kinds::{
+                gameattr::{GameAttr, GameAttrParam, GameAttrParamValue},
+                name::Name,
+                shared::{Matrix, Vector3},
+                version::Version,
+            },
+            write_trait::Write,
+        },
+        type_identifiers::ChunkType,
+    },
+    parse_file, write_file,
+};
+use bytes::Bytes;
+
+#[test]
+fn test_game_attr_params() {
+    let params = [
+        (ChunkType::GameAttrIntParam, GameAttrParamValue::Int(3)),
+        (
+            ChunkType::GameAttrFloatParam,
+            GameAttrParamValue::Float(0.25),
+        ),
+        (
+            ChunkType::GameAttrColourParam,
+            GameAttrParamValue::Colour([1, 2, 3, 4].into()),
+        ),
+        (
+            ChunkType::GameAttrVectorParam,
+            GameAttrParamValue::Vector(Vector3::new(1.0, 2.0, 3.0)),
+        ),
+        (
+            ChunkType::GameAttrMatrixParam,
+            GameAttrParamValue::Matrix(Matrix::identity() * Vector3::new(4.0, 5.0, 6.0)),
+        ),
+    ];
+
+    let mut children = Vec::new();
+    for (i, (typ, value)) in params.iter().enumerate() {
+        let mut data = Vec::new();
+        GameAttrParam {
+            param: format!("param{}", i).into(),
+            value: value.clone(),
+        }
+        .write(&mut data, *typ);
+        children.extend(raw_chunk(*typ, &data, &[]));
+    }
+
+    let mut data = Vec::new();
+    Name("attrs".into()).write(&mut data, ChunkType::GameAttr);
+    Version(0).write(&mut data, ChunkType::GameAttr);
+    GameAttr {
+        num_params: params.len() as u32,
+    }
+    .write(&mut data, ChunkType::GameAttr);
+    let attr = raw_chunk(ChunkType::GameAttr, &data, &children);
+    let bytes = raw_chunk(ChunkType::DataFile, &[], &attr);
+
+    let file = parse_file(Bytes::from(bytes.clone())).unwrap();
+    assert_eq!(file[1].children.len(), params.len());
+    for (i, ((typ, value), child)) in params.iter().zip(&file[2..]).enumerate() {
+        assert_eq!(child.typ, *typ);
+        assert_eq!(
+            child.data,
+            ChunkData::GameAttrParam(GameAttrParam {
+                param: format!("param{}", i).into(),
+                value: value.clone(),
+            })
+        );
+        assert!(child.unparsed_data.is_empty());
+    }
+
+    assert_eq!(write_file(&file).unwrap(), bytes);
+}
+
+#[test]
+fn test_game_attr_bytes() {
+    #[rustfmt::skip]
+    let attr: &[u8] = &[
+        0x05, b'a', b't', b't', b'r', b's', // name
+        0x00, 0x00, 0x00, 0x00, // version
+        0x04, 0x00, 0x00, 0x00, // four params
+    ];
+    let params: [(ChunkType, &[u8]); 4] = [
+        (
+            ChunkType::GameAttrIntParam,
+            &[0x04, b's', b'i', b'z', b'e', 0x03, 0x00, 0x00, 0x00],
+        ),
+        (
+            ChunkType::GameAttrFloatParam,
+            &[0x04, b'r', b'a', b't', b'e', 0x00, 0x00, 0x80, 0x3E],
+        ),
+        (
+            ChunkType::GameAttrColourParam,
+            &[0x04, b't', b'i', b'n', b't', 0x04, 0x03, 0x02, 0x01],
+        ),
+        (
+            ChunkType::GameAttrVectorParam,
+            &[
+                0x03, b'd', b'i', b'r', // name
+                0x00, 0x00, 0x80, 0x3F, // 1
+                0x00, 0x00, 0x00, 0x40, // 2
+                0x00, 0x00, 0x40, 0x40, // 3
+            ],
+        ),
+    ];
+    let children: Vec<u8> = params
+        .iter()
+        .flat_map(|(typ, data)| raw_chunk(*typ, data, &[]))
+        .collect();
+    let bytes = raw_chunk(
+        ChunkType::DataFile,
+        &[],
+        &raw_chunk(ChunkType::GameAttr, attr, &children),
+    );
+
+    let file = parse_file(Bytes::from(bytes.clone())).unwrap();
+    assert_eq!(
+        file[1].data,
+        ChunkData::GameAttr(Name("attrs".into()), Version(0), GameAttr { num_params: 4 })
+    );
+    let expected = [
+        ("size", GameAttrParamValue::Int(3)),
+        ("rate", GameAttrParamValue::Float(0.25)),
+        ("tint", GameAttrParamValue::Colour([1, 2, 3, 4].into())),
+        (
+            "dir",
+            GameAttrParamValue::Vector(Vector3::new(1.0, 2.0, 3.0)),
+        ),
+    ];
+    for ((param, value), child) in expected.into_iter().zip(&file[2..]) {
+        assert_eq!(
+            child.data,
+            ChunkData::GameAttrParam(GameAttrParam {
+                param: param.into(),
+                value,
+            })
+        );
+    }
+    assert!(file.iter().all(|chunk| chunk.unparsed_data.is_empty()));
+    assert_eq!(write_file(&file).unwrap(), bytes);
+}
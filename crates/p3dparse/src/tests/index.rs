@@ -0,0 +1,30 @@
+use super::write::test_tree;
+use crate::chunk::{index::ChunkIndex, type_identifiers::ChunkType};
+
+#[test]
+fn test_chunk_index() {
+    let tree = test_tree();
+    let index = ChunkIndex::new(&tree);
+
+    assert_eq!(index.named(ChunkType::Shader, "shader1"), &[1]);
+    assert_eq!(index.named(ChunkType::Mesh, "mesh1"), &[4]);
+    assert!(index.named(ChunkType::Mesh, "shader1").is_empty());
+    assert!(index.named(ChunkType::Shader, "missing").is_empty());
+
+    let mesh = index.get(&tree, ChunkType::Mesh, "mesh1").unwrap();
+    assert_eq!(mesh.span.absolute_index, 4);
+
+    assert_eq!(
+        index.of_type(ChunkType::ShaderTextureParam).len()
+            + index.of_type(ChunkType::ShaderColourParam).len(),
+        2
+    );
+    assert_eq!(
+        index
+            .all(&tree, ChunkType::OldPrimGroup)
+            .map(|c| c.parent)
+            .collect::<Vec<_>>(),
+        vec![Some(4)]
+    );
+    assert_eq!(index.all(&tree, ChunkType::Skin).count(), 0);
+}
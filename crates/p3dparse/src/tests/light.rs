@@ -0,0 +1,199 @@
+use super::write::raw_chunk;
+use crate::{
+    chunk::{
+        data::{
+            data_enum::ChunkData,
+            kinds::{
+                light::{Light, LightConeParam, LightDirection, LightGroup, LightType},
+                name::Name,
+                shared::{Colour, Vector3},
+                version::Version,
+            },
+            write_trait::Write,
+        },
+        type_identifiers::ChunkType,
+    },
+    parse_file, write_file,
+};
+use bytes::Bytes;
+
+fn payload<T: Write>(
+    name: Option<&str>,
+    version: Option<u32>,
+    data: &T,
+    typ: ChunkType,
+) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    if let Some(name) = name {
+        Name(name.into()).write(&mut bytes, typ);
+    }
+    if let Some(version) = version {
+        Version(version).write(&mut bytes, typ);
+    }
+    data.write(&mut bytes, typ);
+    bytes
+}
+
+fn spot_light() -> Light {
+    Light {
+        light_type: LightType::Spot,
+        colour: Colour::new(255, 200, 100, 255),
+        constant: 1.0,
+        linear: 0.5,
+        squared: 0.0,
+        enabled: 1,
+    }
+}
+
+#[test]
+fn test_light_round_trip() {
+    let cone = LightConeParam {
+        phi: 1.0,
+        theta: 0.5,
+        falloff: 1.0,
+        range: 20.0,
+    };
+    let direction = LightDirection {
+        direction: Vector3::new(0.0, -1.0, 0.0),
+    };
+    let children = [
+        raw_chunk(
+            ChunkType::LightDirection,
+            &payload(None, None, &direction, ChunkType::LightDirection),
+            &[],
+        ),
+        raw_chunk(
+            ChunkType::LightConeParam,
+            &payload(None, None, &cone, ChunkType::LightConeParam),
+            &[],
+        ),
+    ]
+    .concat();
+    let light = raw_chunk(
+        ChunkType::Light,
+        &payload(Some("spot1"), Some(0), &spot_light(), ChunkType::Light),
+        &children,
+    );
+    let group = LightGroup {
+        lights: vec!["spot1".into()],
+    };
+    let group = raw_chunk(
+        ChunkType::P3DLightGroup,
+        &payload(Some("group1"), None, &group, ChunkType::P3DLightGroup),
+        &[],
+    );
+    let bytes = raw_chunk(ChunkType::DataFile, &[], &[light, group].concat());
+
+    let file = parse_file(Bytes::from(bytes.clone())).unwrap();
+    assert_eq!(
+        file[1].data,
+        ChunkData::Light(Name("spot1".into()), Version(0), spot_light())
+    );
+    assert_eq!(file[2].data, ChunkData::LightDirection(direction));
+    assert_eq!(file[3].data, ChunkData::LightConeParam(cone));
+    let ChunkData::LightGroup(name, group) = &file[4].data else {
+        panic!("Expected a light group, got {:?}", file[4].data);
+    };
+    assert_eq!(name.0, "group1");
+    assert_eq!(group.lights, ["spot1"]);
+    assert!(file.iter().all(|chunk| chunk.unparsed_data.is_empty()));
+
+    assert_eq!(write_file(&file).unwrap(), bytes);
+}
+
+#[test]
+fn test_light_bytes() {
+    #[rustfmt::skip]
+    let light: &[u8] = &[
+        0x04, b's', b'p', b'o', b't', // name
+        0x00, 0x00, 0x00, 0x00, // version
+        0x03, 0x00, 0x00, 0x00, // spot
+        0x32, 0x64, 0xC8, 0xFF, // colour
+        0x00, 0x00, 0x80, 0x3F, // constant 1
+        0x00, 0x00, 0x00, 0x3F, // linear 0.5
+        0x00, 0x00, 0x00, 0x00, // squared 0
+        0x01, 0x00, 0x00, 0x00, // enabled
+    ];
+    #[rustfmt::skip]
+    let cone: &[u8] = &[
+        0x00, 0x00, 0x80, 0x3F, // phi 1
+        0x00, 0x00, 0x00, 0x3F, // theta 0.5
+        0x00, 0x00, 0x80, 0x3F, // falloff 1
+        0x00, 0x00, 0xA0, 0x41, // range 20
+    ];
+    #[rustfmt::skip]
+    let group: &[u8] = &[
+        0x05, b'l', b'a', b'm', b'p', b's', // name
+        0x01, 0x00, 0x00, 0x00, // one light
+        0x04, b's', b'p', b'o', b't',
+    ];
+    let bytes = raw_chunk(
+        ChunkType::DataFile,
+        &[],
+        &[
+            raw_chunk(
+                ChunkType::Light,
+                light,
+                &raw_chunk(ChunkType::LightConeParam, cone, &[]),
+            ),
+            raw_chunk(ChunkType::P3DLightGroup, group, &[]),
+        ]
+        .concat(),
+    );
+
+    let file = parse_file(Bytes::from(bytes.clone())).unwrap();
+    assert_eq!(
+        file[1].data,
+        ChunkData::Light(
+            Name("spot".into()),
+            Version(0),
+            Light {
+                light_type: LightType::Spot,
+                colour: Colour::new(255, 200, 100, 50),
+                constant: 1.0,
+                linear: 0.5,
+                squared: 0.0,
+                enabled: 1,
+            }
+        )
+    );
+    assert_eq!(
+        file[2].data,
+        ChunkData::LightConeParam(LightConeParam {
+            phi: 1.0,
+            theta: 0.5,
+            falloff: 1.0,
+            range: 20.0,
+        })
+    );
+    assert_eq!(
+        file[3].data,
+        ChunkData::LightGroup(
+            Name("lamps".into()),
+            LightGroup {
+                lights: vec!["spot".into()],
+            }
+        )
+    );
+    assert!(file.iter().all(|chunk| chunk.unparsed_data.is_empty()));
+    assert_eq!(write_file(&file).unwrap(), bytes);
+}
+
+#[test]
+fn test_light_unknown_type() {
+    let mut data = payload(Some("lamp"), Some(0), &spot_light(), ChunkType::Light);
+    // Not a light type we know of, right after the name and version
+    data[9..13].copy_from_slice(&7u32.to_le_bytes());
+    let bytes = raw_chunk(
+        ChunkType::DataFile,
+        &[],
+        &raw_chunk(ChunkType::Light, &data, &[]),
+    );
+
+    let file = parse_file(Bytes::from(bytes.clone())).expect("Failed to parse file");
+    match &file[1].data {
+        ChunkData::Light(_, _, light) => assert_eq!(light.light_type, LightType::Other(7)),
+        data => panic!("Expected a light, got {:?}", data),
+    }
+    assert_eq!(write_file(&file).unwrap(), bytes);
+}
@@ -0,0 +1,83 @@
+use super::write::{clear_file_spans, raw_chunk, test_tree};
+use crate::{
+    chunk::type_identifiers::ChunkType,
+    lzr::{compress_file, decompress_file},
+    parse_file, write_file, write_file_compressed,
+};
+use bytes::Bytes;
+
+/// Builds a compressed file out of a single hand-written block.
+fn single_block(decompressed_size: u32, block: &[u8]) -> Bytes {
+    let mut file = Vec::new();
+    file.extend_from_slice(&0x5A44_3350u32.to_le_bytes());
+    file.extend_from_slice(&decompressed_size.to_le_bytes());
+    file.extend_from_slice(&(block.len() as u32).to_le_bytes());
+    file.extend_from_slice(&decompressed_size.to_le_bytes());
+    file.extend_from_slice(block);
+    file.into()
+}
+
+#[test]
+fn test_lzr_decompress_block() {
+    // Three literals, then a 7 byte back-reference 3 bytes back that overlaps itself
+    let file = single_block(10, &[3, b'a', b'b', b'c', 0x37, 0]);
+    assert_eq!(
+        decompress_file(file).unwrap(),
+        Bytes::from_static(b"abcabcabca")
+    );
+
+    // Extended literal run: 15 + 255 + 2
+    let literals = vec![0x42; 272];
+    let mut block = vec![0, 0, 2];
+    block.extend_from_slice(&literals);
+    assert_eq!(
+        decompress_file(single_block(272, &block)).unwrap(),
+        literals
+    );
+}
+
+#[test]
+fn test_lzr_rejects_bad_data() {
+    // Back-reference before the start of the output
+    assert!(decompress_file(single_block(4, &[0x14, 0])).is_err());
+    // Literal run longer than the block
+    assert!(decompress_file(single_block(4, &[4, 1, 2])).is_err());
+    // Missing block entirely
+    assert!(decompress_file(single_block(4, &[]).slice(..8)).is_err());
+}
+
+#[test]
+fn test_lzr_round_trip() {
+    let inputs: Vec<Vec<u8>> = vec![
+        Vec::new(),
+        b"a".to_vec(),
+        vec![0; 100_000],
+        (0..200_000u32).map(|i| (i * 7 % 251) as u8).collect(),
+        write_file(&test_tree()).unwrap(),
+    ];
+
+    for input in inputs {
+        let compressed = compress_file(&input);
+        assert_eq!(decompress_file(compressed.into()).unwrap(), input);
+    }
+
+    // Long runs should actually shrink
+    assert!(compress_file(&[0; 100_000]).len() < 2_000);
+}
+
+#[test]
+fn test_parse_compressed_file() {
+    let tree = test_tree();
+    let compressed = write_file_compressed(&tree).unwrap();
+    assert_eq!(
+        u32::from_le_bytes(compressed[..4].try_into().unwrap()),
+        u32::from(ChunkType::DataFileCompressed)
+    );
+
+    let file = parse_file(compressed.into()).expect("Failed to parse compressed file");
+    assert_eq!(clear_file_spans(file), tree);
+
+    // Unsupported file types are still rejected
+    let rz = raw_chunk(ChunkType::Other(0x5A52), &[], &[]);
+    assert!(parse_file(rz.into()).is_err());
+}
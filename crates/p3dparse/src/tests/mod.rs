@@ -1,6 +1,23 @@
 use crate::{chunk::data::kinds::mesh::VertexType, FileTypes};
 
+mod edit;
+mod endian;
+mod error;
+mod frontend;
+mod fuzz;
+mod gameattr;
+mod index;
+mod light;
+mod lzr;
+mod names;
+mod query;
 mod real_assets;
+mod scan;
+mod schema;
+mod shader_param;
+mod text;
+mod visitor;
+mod write;
 
 #[test]
 fn test_file_types() {
@@ -0,0 +1,128 @@
+use crate::chunk::{
+    data::{
+        data_enum::ChunkData,
+        kinds::{
+            animation::AnimationGroupList,
+            mesh::{CompositeDrawable, Skin},
+            name::Name,
+            prop_state::StatePropStateDataV1,
+            pure3d_string::Pure3DString,
+            scenegraph::ScenegraphBranch,
+            version::Version,
+        },
+    },
+    type_identifiers::ChunkType,
+    Chunk, FileSpan, Span,
+};
+use bytes::Bytes;
+
+fn name(name: &str) -> Name {
+    Name(name.into())
+}
+
+#[test]
+fn test_get_name_and_version() {
+    // One of each field layout: name first, version first, name only, name without version, version without name
+    let cases = [
+        (
+            ChunkData::Skin(
+                name("homer_m"),
+                Version(1),
+                Skin {
+                    skeleton_name: "homer".into(),
+                    num_prim_groups: 0,
+                },
+            ),
+            Some("homer_m"),
+            Some(1),
+        ),
+        (
+            ChunkData::AnimationGroupList(Version(2), AnimationGroupList { num_groups: 0 }),
+            None,
+            Some(2),
+        ),
+        (
+            ChunkData::Scenegraph(name("sg"), Version(3)),
+            Some("sg"),
+            Some(3),
+        ),
+        (
+            ChunkData::PropInstanceList(name("props")),
+            Some("props"),
+            None,
+        ),
+        (
+            ChunkData::CompositeDrawable(
+                name("homer"),
+                CompositeDrawable {
+                    skeleton_name: "homer_skel".into(),
+                },
+            ),
+            Some("homer"),
+            None,
+        ),
+        (
+            ChunkData::ScenegraphBranch(name("branch"), ScenegraphBranch { num_children: 0 }),
+            Some("branch"),
+            None,
+        ),
+        (
+            ChunkData::StatePropStateDataV1(
+                name("state"),
+                StatePropStateDataV1 {
+                    auto_transition: 0,
+                    out_state: 0,
+                    num_drawable: 0,
+                    num_frame_controllers: 0,
+                    num_events: 0,
+                    num_callbacks: 0,
+                    out_frames: 0.,
+                },
+            ),
+            Some("state"),
+            None,
+        ),
+        (ChunkData::None, None, None),
+        (ChunkData::Unknown, None, None),
+    ];
+
+    for (data, expected_name, expected_version) in cases {
+        assert_eq!(
+            data.get_name().map(|n| n.0),
+            expected_name.map(Pure3DString::from),
+            "{:?}",
+            data
+        );
+        assert_eq!(
+            data.get_version().map(|v| v.0),
+            expected_version,
+            "{:?}",
+            data
+        );
+    }
+}
+
+#[test]
+fn test_chunk_get_name() {
+    let chunk = Chunk {
+        typ: ChunkType::Skin,
+        data: ChunkData::Skin(
+            name("homer_m"),
+            Version(1),
+            Skin {
+                skeleton_name: "homer".into(),
+                num_prim_groups: 0,
+            },
+        ),
+        span: Span {
+            absolute_index: 3,
+            relative_index: 1,
+        },
+        parent: None,
+        children: Vec::new(),
+        unparsed_data: Bytes::new(),
+        file_span: FileSpan::default(),
+    };
+
+    assert_eq!(chunk.get_name(), "homer_m:Skin:3:1");
+}
@@ -0,0 +1,48 @@
+use super::write::test_tree;
+use crate::chunk::query::{query, Query};
+
+fn indices(tree: &[crate::chunk::Chunk], path: &str) -> Vec<usize> {
+    Query::new(path).unwrap().run_indices(tree)
+}
+
+#[test]
+fn test_query() {
+    let tree = test_tree();
+
+    assert_eq!(indices(&tree, "Mesh[name=mesh1]/OldPrimGroup/UVList"), &[7]);
+    assert_eq!(indices(&tree, "/Mesh/OldPrimGroup/UVList"), &[7]);
+    assert!(indices(&tree, "Mesh[name=mesh2]/OldPrimGroup/UVList").is_empty());
+    // UVList isn't a child of the root
+    assert!(indices(&tree, "UVList").is_empty());
+
+    assert_eq!(indices(&tree, "//UVList"), &[7]);
+    assert_eq!(indices(&tree, "Mesh//IndexList"), &[8]);
+    assert_eq!(indices(&tree, "Shader/*"), &[2, 3]);
+    assert_eq!(indices(&tree, "*"), &[1, 4, 9]);
+    assert_eq!(indices(&tree, "//*[name=*1]"), &[1, 4]);
+    assert_eq!(indices(&tree, "//*[name=sh*r*]"), &[1]);
+    // Overlapping descendant steps don't return duplicates
+    assert_eq!(indices(&tree, "//*//PositionList"), &[6]);
+    // Raw IDs work too, 0x11005 is ShaderColourParam
+    assert_eq!(indices(&tree, "//0x11005"), &[3]);
+
+    let shaders = query(&tree, "Shader[name=shader1]").unwrap();
+    assert_eq!(shaders.len(), 1);
+    assert_eq!(shaders[0].span.absolute_index, 1);
+    assert!(query(&[], "//Shader").unwrap().is_empty());
+}
+
+#[test]
+fn test_query_errors() {
+    for path in [
+        "",
+        "NotAChunk",
+        "Mesh[name=mesh1",
+        "Mesh]",
+        "Mesh[version=1]",
+        "Mesh//",
+        "0xZZ",
+    ] {
+        assert!(Query::new(path).is_err(), "{:?} should not parse", path);
+    }
+}
@@ -7,7 +7,7 @@ use crate::{
         type_identifiers::ChunkType,
         Chunk, VecChunkExtension,
     },
-    parse_file,
+    parse_file, write_file,
 };
 use bytes::Bytes;
 use float_eq::assert_float_eq;
@@ -94,7 +94,7 @@ fn test_mesh() {
                             assert_eq!(param.param, "TEX");
                             assert_eq!(
                                 param.value,
-                                ShaderParamValue::Texture("swatchX.bmp".to_owned())
+                                ShaderParamValue::Texture("swatchX.bmp".into())
                             );
                         }
                         _ => panic!("Invalid data for {:?}", chunk.typ),
@@ -413,7 +413,16 @@ fn test_mesh() {
                                 assert_eq!(chunk.typ, ChunkType::IndexList);
                                 // Spot check on name and lineage
                                 assert_eq!(chunk.get_name(), "<no name>:IndexList:31:5");
-                                assert_eq!(chunk.get_lineage(file), "<no name>:IndexList:31:5 -> <no name>:OldPrimGroup:25:0 -> letterAShape:Mesh:24:2 -> <no name>:DataFile:0:0");
+                                assert_eq!(
+                                    chunk.get_lineage(file),
+                                    format!(
+                                        "<no name>:IndexList:31:5@{} -> <no name>:OldPrimGroup:25:0@{} -> letterAShape:Mesh:24:2@{} -> <no name>:DataFile:0:0@{}",
+                                        file[31].file_span,
+                                        file[25].file_span,
+                                        file[24].file_span,
+                                        file[0].file_span
+                                    )
+                                );
                                 match &chunk.data {
                                     ChunkData::IndexList(indices) => {
                                         assert_eq!(indices.indices.len(), 348);
@@ -514,3 +523,19 @@ fn test_real_homer_c() {
         eprintln!("Skipping test due to inability to find assets.");
     }
 }
+
+#[test]
+fn test_real_round_trip() {
+    for name in ["simplified_letter_A.p3d", "homer_C.p3d"] {
+        if let Some(asset) = get_asset(name) {
+            let file = parse_file(Bytes::from(asset.clone())).unwrap();
+            assert!(
+                write_file(&file).unwrap() == asset,
+                "{} did not round trip",
+                name
+            );
+        } else {
+            eprintln!("Skipping test due to inability to find assets.");
+        }
+    }
+}
@@ -0,0 +1,97 @@
+use super::write::{raw_chunk, test_tree};
+use crate::{
+    chunk::{data::data_enum::ChunkData, type_identifiers::ChunkType},
+    error::ParseError,
+    lzr::compress_file,
+    parse_file,
+    scan::scan_file,
+    write_file,
+};
+use bytes::Bytes;
+
+#[test]
+fn test_scan_matches_parse() {
+    let file = Bytes::from(write_file(&test_tree()).unwrap());
+    let parsed = parse_file(file.clone()).unwrap();
+
+    for file in [file.clone(), Bytes::from(compress_file(&file))] {
+        let scanned = scan_file(file).unwrap();
+        assert_eq!(scanned.chunks().len(), parsed.len());
+        for (scanned_chunk, chunk) in scanned.chunks().iter().zip(&parsed) {
+            assert_eq!(scanned_chunk.typ, chunk.typ);
+            assert_eq!(scanned_chunk.span, chunk.span);
+            assert_eq!(scanned_chunk.parent, chunk.parent);
+            assert_eq!(scanned_chunk.children, chunk.children);
+            assert_eq!(scanned_chunk.file_span, chunk.file_span);
+        }
+
+        // Decoding one chunk doesn't touch its children
+        for (index, chunk) in parsed.iter().enumerate() {
+            assert_eq!(scanned.decode(index).unwrap(), chunk.data);
+        }
+
+        // The mesh and everything under it, rooted at the mesh
+        let mesh = scanned.decode_subtree(4).unwrap();
+        assert_eq!(mesh.len(), 5);
+        assert_eq!(mesh[0].parent, None);
+        for (decoded, chunk) in mesh.iter().zip(&parsed[4..9]) {
+            assert_eq!(decoded.data, chunk.data);
+            assert_eq!(decoded.file_span, chunk.file_span);
+        }
+    }
+}
+
+#[test]
+fn test_scan_errors() {
+    // Image data claims more pixels than it has, which only shows up when it's decoded
+    let child = raw_chunk(ChunkType::ImageData, &[0xFF, 0, 0, 0, 1, 2], &[]);
+    let file = raw_chunk(ChunkType::DataFile, &[], &child);
+
+    let scanned = scan_file(file.into()).unwrap();
+    assert_eq!(scanned.payload(1).len(), 6);
+    assert_eq!(scanned.decode(0).unwrap(), ChunkData::None);
+    match scanned.decode(1) {
+        Err(ParseError::UnexpectedEof {
+            offset, lineage, ..
+        }) => {
+            assert_eq!(offset, 28);
+            assert!(lineage.starts_with("<no name>:DataFile:0:0@"));
+        }
+        r => panic!("Expected UnexpectedEof, got {:?}", r),
+    }
+
+    // A child that runs past the end of its parent
+    let mut child = raw_chunk(ChunkType::RenderStatus, &[0; 4], &[]);
+    child[8] = 100;
+    let file = raw_chunk(ChunkType::DataFile, &[], &child);
+    assert!(matches!(
+        scan_file(file.into()),
+        Err(ParseError::UnexpectedEof { .. })
+    ));
+
+    // Children that don't add up to the parent's total size
+    let child = raw_chunk(ChunkType::RenderStatus, &[0; 4], &[]);
+    let mut file = raw_chunk(ChunkType::DataFile, &[], &child);
+    file.extend_from_slice(&[0; 8]);
+    file[8] += 8;
+    assert!(matches!(
+        scan_file(file.into()),
+        Err(ParseError::UnexpectedEof { .. } | ParseError::CorruptHeader { .. })
+    ));
+}
+
+#[test]
+fn test_scan_unrecognised_file() {
+    assert_eq!(
+        scan_file(vec![1, 2, 3, 4].into()).unwrap_err(),
+        ParseError::UnrecognisedFile { magic: 0x04030201 }
+    );
+
+    // A compressed file that doesn't hold a P3D file, which parse_file rejects as well
+    let not_p3d = compress_file(&raw_chunk(ChunkType::RenderStatus, &[0; 4], &[]));
+    let expected = ParseError::UnrecognisedFile {
+        magic: ChunkType::RenderStatus.into(),
+    };
+    assert_eq!(parse_file(not_p3d.clone().into()).unwrap_err(), expected);
+    assert_eq!(scan_file(not_p3d.into()).unwrap_err(), expected);
+}
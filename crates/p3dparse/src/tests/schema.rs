@@ -0,0 +1,80 @@
+use crate::{
+    chunk::{
+        data::{
+            kinds::shared::{Matrix, Vector3},
+            parse_trait::Parse,
+            schema::{chunk_struct, FourCC},
+            write_trait::Write,
+        },
+        type_identifiers::ChunkType,
+    },
+    error::Overrun,
+};
+use bytes::{BufMut, Bytes};
+
+chunk_struct! {
+    #[derive(Default)]
+    pub struct Everything {
+        pub count: u32,
+        pub name: String,
+        pub format: String as FourCC,
+        /// Field attributes are kept too
+        pub positions: Vec<Vector3>,
+        pub names: Vec<String>,
+        pub transform: Matrix,
+        pub scale: f32,
+    }
+}
+
+fn everything_bytes() -> Vec<u8> {
+    let mut bytes = Vec::new();
+    bytes.put_u32_le(7);
+    bytes.put_slice(b"\x04abc\0");
+    bytes.put_slice(b"DXT1");
+    bytes.put_u32_le(2);
+    for f in [1.0f32, 2.0, 3.0, 4.0, 5.0, 6.0] {
+        bytes.put_f32_le(f);
+    }
+    bytes.put_u32_le(2);
+    bytes.put_slice(b"\x04one\0\x04two\0");
+    for i in 0..16 {
+        bytes.put_f32_le(if i % 5 == 0 { 1.0 } else { 0.0 });
+    }
+    bytes.put_f32_le(0.5);
+    bytes
+}
+
+#[test]
+fn test_chunk_struct_round_trip() {
+    let raw = everything_bytes();
+    let mut bytes = Bytes::from(raw.clone());
+    let parsed = Everything::parse(&mut bytes, ChunkType::DataFile).unwrap();
+    assert!(bytes.is_empty());
+
+    assert_eq!(parsed.count, 7);
+    assert_eq!(parsed.name, "abc");
+    assert_eq!(parsed.format, "DXT1");
+    assert_eq!(parsed.positions[1], Vector3::new(4.0, 5.0, 6.0));
+    assert_eq!(parsed.names, ["one", "two"]);
+    assert_eq!(parsed.transform, Matrix::identity());
+    assert_eq!(parsed.scale, 0.5);
+
+    let mut written = Vec::new();
+    parsed.write(&mut written, ChunkType::DataFile);
+    assert_eq!(written, raw);
+
+    let json = serde_json::to_string(&parsed).unwrap();
+    assert_eq!(serde_json::from_str::<Everything>(&json).unwrap(), parsed);
+}
+
+#[test]
+fn test_chunk_struct_hostile_count() {
+    // Position count claims far more than is left, which must fail before allocating for it
+    let mut raw = Vec::new();
+    Everything::default().write(&mut raw, ChunkType::DataFile);
+    // u32, then an empty string and a fourcc before the count
+    raw[9..13].copy_from_slice(&u32::MAX.to_le_bytes());
+
+    let err = Everything::parse(&mut Bytes::from(raw), ChunkType::DataFile).unwrap_err();
+    assert!(err.downcast_ref::<Overrun>().is_some());
+}
@@ -0,0 +1,228 @@
+use super::write::raw_chunk;
+use crate::{
+    chunk::{
+        data::{
+            data_enum::ChunkData,
+            kinds::{
+                shader_param::{ShaderParam, ShaderParamValue},
+                shared::{Matrix, Vector3},
+            },
+            write_trait::Write,
+        },
+        type_identifiers::ChunkType,
+    },
+    parse_file, write_file,
+};
+use bytes::Bytes;
+use nalgebra::Matrix4;
+
+fn param_chunk(typ: ChunkType, param: &str, value: ShaderParamValue) -> Vec<u8> {
+    let mut data = Vec::new();
+    ShaderParam {
+        param: param.into(),
+        value,
+    }
+    .write(&mut data, typ);
+    raw_chunk(typ, &data, &[])
+}
+
+#[test]
+fn test_shader_param_vector_matrix() {
+    let vector = Vector3::new(1.0, 2.0, 3.0);
+    let matrix = Matrix::identity() * Vector3::new(4.0, 5.0, 6.0);
+    let params = [
+        (
+            ChunkType::ShaderVectorParam,
+            "VEC",
+            ShaderParamValue::Vector(vector),
+        ),
+        (
+            ChunkType::ShaderMatrixParam,
+            "MAT",
+            ShaderParamValue::Matrix(matrix),
+        ),
+        (
+            ChunkType::P3DV14ShaderTextureParam,
+            "TEX",
+            ShaderParamValue::Texture("old.bmp".into()),
+        ),
+        (
+            ChunkType::P3DV14ShaderIntParam,
+            "LIT",
+            ShaderParamValue::Int(1),
+        ),
+        (
+            ChunkType::P3DV14ShaderFloatParam,
+            "SHIN",
+            ShaderParamValue::Float(0.5),
+        ),
+        (
+            ChunkType::P3DV14ShaderColourParam,
+            "DIFF",
+            ShaderParamValue::Colour([1, 2, 3, 4].into()),
+        ),
+        (
+            ChunkType::P3DV14ShaderVectorParam,
+            "VEC",
+            ShaderParamValue::Vector(vector),
+        ),
+        (
+            ChunkType::P3DV14ShaderMatrixParam,
+            "MAT",
+            ShaderParamValue::Matrix(matrix),
+        ),
+    ];
+    let children: Vec<u8> = params
+        .iter()
+        .flat_map(|(typ, param, value)| param_chunk(*typ, param, value.clone()))
+        .collect();
+    let bytes = raw_chunk(ChunkType::DataFile, &[], &children);
+
+    let file = parse_file(Bytes::from(bytes.clone())).unwrap();
+    assert_eq!(file.len(), params.len() + 1);
+    for ((typ, param, value), chunk) in params.iter().zip(&file[1..]) {
+        assert_eq!(chunk.typ, *typ);
+        assert_eq!(
+            chunk.data,
+            ChunkData::ShaderParam(ShaderParam {
+                param: (*param).into(),
+                value: value.clone(),
+            })
+        );
+        assert!(chunk.unparsed_data.is_empty());
+    }
+
+    assert_eq!(write_file(&file).unwrap(), bytes);
+}
+
+#[test]
+fn test_shader_param_bytes() {
+    #[rustfmt::skip]
+    let vector: &[u8] = &[
+        0x00, 0x00, 0x80, 0x3F, 0x00, 0x00, 0x00, 0x40, 0x00, 0x00, 0x40, 0x40, // 1, 2, 3
+    ];
+    // 1 to 16, row by row
+    let matrix: Vec<u8> = (1..=16u8)
+        .flat_map(|i| f32::from(i).to_le_bytes())
+        .collect();
+    let values: [(&str, &[u8], ShaderParamValue); 6] = [
+        (
+            "TEX",
+            b"TEX\0\x08old.bmp\0",
+            ShaderParamValue::Texture("old.bmp".into()),
+        ),
+        (
+            "LIT",
+            &[b'L', b'I', b'T', 0x00, 0x01, 0x00, 0x00, 0x00],
+            ShaderParamValue::Int(1),
+        ),
+        (
+            "SHIN",
+            &[b'S', b'H', b'I', b'N', 0x00, 0x00, 0x00, 0x3F],
+            ShaderParamValue::Float(0.5),
+        ),
+        (
+            "DIFF",
+            &[b'D', b'I', b'F', b'F', 0x04, 0x03, 0x02, 0x01],
+            ShaderParamValue::Colour([1, 2, 3, 4].into()),
+        ),
+        (
+            "VEC",
+            &[b"VEC\0", vector].concat(),
+            ShaderParamValue::Vector(Vector3::new(1.0, 2.0, 3.0)),
+        ),
+        (
+            "MAT",
+            &[b"MAT\0", &matrix[..]].concat(),
+            ShaderParamValue::Matrix(Matrix::from_matrix_unchecked(Matrix4::from_row_iterator(
+                (1..=16u8).map(f32::from),
+            ))),
+        ),
+    ];
+    let types = [
+        ChunkType::ShaderTextureParam,
+        ChunkType::ShaderIntParam,
+        ChunkType::ShaderFloatParam,
+        ChunkType::ShaderColourParam,
+        ChunkType::ShaderVectorParam,
+        ChunkType::ShaderMatrixParam,
+        ChunkType::P3DV14ShaderTextureParam,
+        ChunkType::P3DV14ShaderIntParam,
+        ChunkType::P3DV14ShaderFloatParam,
+        ChunkType::P3DV14ShaderColourParam,
+        ChunkType::P3DV14ShaderVectorParam,
+        ChunkType::P3DV14ShaderMatrixParam,
+    ];
+
+    let mut children = Vec::new();
+    for (typ, (_, data, _)) in types.iter().zip(values.iter().cycle()) {
+        children.extend(raw_chunk(*typ, data, &[]));
+    }
+    let bytes = raw_chunk(ChunkType::DataFile, &[], &children);
+
+    let file = parse_file(Bytes::from(bytes.clone())).unwrap();
+    assert_eq!(file.len(), types.len() + 1);
+    for ((typ, (param, _, value)), chunk) in types.iter().zip(values.iter().cycle()).zip(&file[1..])
+    {
+        assert_eq!(chunk.typ, *typ);
+        assert_eq!(
+            chunk.data,
+            ChunkData::ShaderParam(ShaderParam {
+                param: (*param).into(),
+                value: value.clone(),
+            })
+        );
+        assert!(chunk.unparsed_data.is_empty());
+    }
+    assert_eq!(write_file(&file).unwrap(), bytes);
+}
+
+#[test]
+fn test_shader_param_v14_vector_leftover() {
+    // Should V14 vectors turn out to have a fourth component, it's kept rather than dropped
+    let mut data = b"VEC\0".to_vec();
+    for value in [1.0f32, 2.0, 3.0, 4.0] {
+        data.extend_from_slice(&value.to_le_bytes());
+    }
+    let bytes = raw_chunk(
+        ChunkType::DataFile,
+        &[],
+        &raw_chunk(ChunkType::P3DV14ShaderVectorParam, &data, &[]),
+    );
+
+    let file = parse_file(Bytes::from(bytes.clone())).unwrap();
+    assert_eq!(
+        file[1].data,
+        ChunkData::ShaderParam(ShaderParam {
+            param: "VEC".into(),
+            value: ShaderParamValue::Vector(Vector3::new(1.0, 2.0, 3.0)),
+        })
+    );
+    assert_eq!(&file[1].unparsed_data[..], 4.0f32.to_le_bytes());
+    assert_eq!(write_file(&file).unwrap(), bytes);
+}
+
+#[test]
+fn test_shader_param_order() {
+    let vector = |x| ShaderParamValue::Vector(Vector3::new(x, 0.0, 0.0));
+    let matrix = |x| ShaderParamValue::Matrix(Matrix::identity() * Vector3::new(x, 0.0, 0.0));
+
+    assert!(vector(1.0) < vector(2.0));
+    assert!(matrix(2.0) > matrix(1.0));
+    assert_eq!(
+        matrix(1.0).partial_cmp(&matrix(1.0)),
+        Some(std::cmp::Ordering::Equal)
+    );
+    assert_eq!(vector(f32::NAN).partial_cmp(&vector(f32::NAN)), None);
+    // Different kinds of value order by variant
+    assert!(ShaderParamValue::Int(9) < ShaderParamValue::Float(0.0));
+    assert!(vector(9.0) < matrix(0.0));
+    assert!(matrix(0.0) < ShaderParamValue::None);
+
+    let param = |param: &str, value| ShaderParam {
+        param: param.into(),
+        value,
+    };
+    assert!(param("A", matrix(9.0)) < param("B", vector(0.0)));
+    assert!(param("A", vector(0.0)) < param("A", vector(1.0)));
+}
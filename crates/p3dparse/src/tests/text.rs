@@ -0,0 +1,249 @@
+use super::write::{clear_file_spans, push, raw_chunk};
+use crate::{
+    chunk::{
+        data::{
+            data_enum::ChunkData,
+            kinds::{
+                frontend::{FrontendLanguage, FrontendTextBible},
+                name::Name,
+            },
+        },
+        type_identifiers::ChunkType,
+        Chunk,
+    },
+    error::ParseError,
+    parse_file,
+    text::{apply_string_tables, from_json, from_po, string_tables, to_json, to_po},
+    write_file,
+};
+use bytes::Bytes;
+
+fn language(language: u8, entries: &[(u32, &str)]) -> FrontendLanguage {
+    let mut data = FrontendLanguage {
+        language,
+        modulo: 0x1F,
+        hashes: Vec::new(),
+        offsets: Vec::new(),
+        buffer: Vec::new(),
+        trailing_byte: None,
+    };
+    data.set_entries(entries.iter().copied());
+    data
+}
+
+fn bible_tree() -> Vec<Chunk> {
+    let mut tree = Vec::new();
+    let root = push(&mut tree, None, ChunkType::DataFile, ChunkData::None);
+    let bible = push(
+        &mut tree,
+        Some(root),
+        ChunkType::FrontendTextBible,
+        ChunkData::FrontendTextBible(
+            Name("srr2".into()),
+            FrontendTextBible {
+                num_languages: 2,
+                languages: "EF".into(),
+            },
+        ),
+    );
+    push(
+        &mut tree,
+        Some(bible),
+        ChunkType::FrontendLanguage,
+        ChunkData::FrontendLanguage(
+            Name("E".into()),
+            language(b'E', &[(0x10, "Start"), (0x2A, "Quit \"now\"\n")]),
+        ),
+    );
+    push(
+        &mut tree,
+        Some(bible),
+        ChunkType::FrontendLanguage,
+        ChunkData::FrontendLanguage(
+            Name("F".into()),
+            language(b'F', &[(0x10, "Démarrer"), (0x2A, "Quitter ✓")]),
+        ),
+    );
+    tree
+}
+
+#[test]
+fn test_language_round_trip() {
+    let tree = bible_tree();
+    let bytes = write_file(&tree).unwrap();
+
+    let parsed = parse_file(Bytes::from(bytes.clone())).unwrap();
+    assert!(parsed.iter().all(|chunk| chunk.unparsed_data.is_empty()));
+    assert_eq!(clear_file_spans(parsed.clone()), tree);
+    assert_eq!(write_file(&parsed).unwrap(), bytes);
+
+    let ChunkData::FrontendLanguage(_, french) = &parsed[3].data else {
+        panic!("Expected a language, got {:?}", parsed[3].data);
+    };
+    // Wide characters survive, they're stored as UCS-2
+    assert_eq!(french.get(0x10).as_deref(), Some("Démarrer"));
+    assert_eq!(french.offsets, [0, 9]);
+    assert_eq!(french.get(0x99), None);
+}
+
+#[test]
+fn test_language_hostile_count() {
+    let mut data = Vec::new();
+    data.extend_from_slice(b"\x04E\0\0\0E");
+    // Entry count, modulo
+    for value in [u32::MAX, 0x1F] {
+        data.extend_from_slice(&value.to_le_bytes());
+    }
+    let bytes = raw_chunk(
+        ChunkType::DataFile,
+        &[],
+        &raw_chunk(ChunkType::FrontendLanguage, &data, &[]),
+    );
+
+    assert!(matches!(
+        parse_file(Bytes::from(bytes)),
+        Err(ParseError::UnexpectedEof { .. })
+    ));
+}
+
+#[test]
+fn test_language_odd_buffer_size() {
+    let mut data = Vec::new();
+    data.extend_from_slice(b"\x04E\0\0\0E");
+    // Entry count, modulo, hash, offset
+    for value in [1u32, 0x1F, 0x10, 0] {
+        data.extend_from_slice(&value.to_le_bytes());
+    }
+    // "Hi" and its terminator, plus a stray byte
+    data.extend_from_slice(&7u32.to_le_bytes());
+    data.extend_from_slice(&[b'H', 0, b'i', 0, 0, 0, 0xCD]);
+    let language = raw_chunk(ChunkType::FrontendLanguage, &data, &[]);
+    let bytes = raw_chunk(
+        ChunkType::DataFile,
+        &[],
+        &raw_chunk(
+            ChunkType::FrontendTextBible,
+            b"\x04srr2\x01\0\0\0\x04E\0\0\0",
+            &language,
+        ),
+    );
+
+    let mut tree = parse_file(Bytes::from(bytes.clone())).unwrap();
+    let ChunkData::FrontendLanguage(_, english) = &tree[2].data else {
+        panic!("Expected a language, got {:?}", tree[2].data);
+    };
+    assert_eq!(english.get(0x10).as_deref(), Some("Hi"));
+    assert_eq!(english.trailing_byte, Some(0xCD));
+    assert!(tree[2].unparsed_data.is_empty());
+    assert_eq!(write_file(&tree).unwrap(), bytes);
+
+    // An unedited import changes nothing, an edit keeps the stray byte at the end
+    let mut tables = string_tables(&tree).unwrap();
+    apply_string_tables(&mut tree, &tables).unwrap();
+    assert_eq!(write_file(&tree).unwrap(), bytes);
+
+    tables[0].strings.insert("0x00000010".into(), "Hey".into());
+    apply_string_tables(&mut tree, &tables).unwrap();
+    let written = write_file(&tree).unwrap();
+    assert_eq!(written.len(), bytes.len() + 2);
+    assert_eq!(written.last(), Some(&0xCD));
+    let parsed = parse_file(Bytes::from(written)).unwrap();
+    assert_eq!(string_tables(&parsed).unwrap(), tables);
+}
+
+#[test]
+fn test_string_tables_duplicate_hashes() {
+    let mut tree = bible_tree();
+    let ChunkData::FrontendLanguage(_, english) = &mut tree[2].data else {
+        panic!("Expected a language, got {:?}", tree[2].data);
+    };
+
+    // The same string twice can be exported, and an import sets both
+    english.set_entries([(0x10, "Start"), (0x10, "Start")]);
+    let mut tables = string_tables(&tree).unwrap();
+    assert_eq!(tables[0].strings.len(), 1);
+    tables[0]
+        .strings
+        .insert("0x00000010".into(), "Begin".into());
+    apply_string_tables(&mut tree, &tables).unwrap();
+    let ChunkData::FrontendLanguage(_, english) = &mut tree[2].data else {
+        panic!("Expected a language, got {:?}", tree[2].data);
+    };
+    assert!(english.entries().all(|(_, string)| string == "Begin"));
+
+    // Different strings under one hash would lose one of them
+    english.set_entries([(0x10, "Start"), (0x10, "Begin")]);
+    let error = string_tables(&tree).unwrap_err().to_string();
+    assert!(error.contains("0x00000010"), "{}", error);
+}
+
+#[test]
+fn test_string_tables() {
+    let tree = bible_tree();
+    let tables = string_tables(&tree).unwrap();
+
+    assert_eq!(tables.len(), 2);
+    assert_eq!(tables[0].bible, "srr2");
+    assert_eq!(tables[0].language, 'E');
+    assert_eq!(tables[0].strings["0x00000010"], "Start");
+    assert_eq!(tables[1].strings["0x0000002A"], "Quitter ✓");
+
+    // Both formats read back what they write
+    assert_eq!(from_json(&to_json(&tables).unwrap()).unwrap(), tables);
+    let po = to_po(&tables);
+    assert!(
+        po.contains("msgctxt \"srr2:E\"\nmsgid \"0x0000002A\"\nmsgstr \"Quit \\\"now\\\"\\n\"\n")
+    );
+    assert_eq!(from_po(&po).unwrap(), tables);
+
+    // Re-importing unedited tables leaves the file as it was
+    let mut unchanged = tree.clone();
+    apply_string_tables(&mut unchanged, &tables).unwrap();
+    assert_eq!(write_file(&unchanged).unwrap(), write_file(&tree).unwrap());
+}
+
+#[test]
+fn test_apply_string_tables() {
+    let mut tree = bible_tree();
+    let mut tables = string_tables(&tree).unwrap();
+    tables[1]
+        .strings
+        .insert("0x00000010".into(), "Commencer la partie".into());
+    // Only the edited language needs to be there
+    tables.remove(0);
+
+    apply_string_tables(&mut tree, &tables).unwrap();
+    let parsed = parse_file(Bytes::from(write_file(&tree).unwrap())).unwrap();
+    let tables = string_tables(&parsed).unwrap();
+    assert_eq!(tables[0].strings["0x00000010"], "Start");
+    assert_eq!(tables[1].strings["0x00000010"], "Commencer la partie");
+    assert_eq!(tables[1].strings["0x0000002A"], "Quitter ✓");
+
+    // Keys the game has no hash for can't be added
+    let mut bad = tables.clone();
+    bad[0].strings.insert("0x00000099".into(), "New".into());
+    assert!(apply_string_tables(&mut tree, &bad).is_err());
+    let mut bad = tables;
+    bad[0].language = 'G';
+    assert!(apply_string_tables(&mut tree, &bad).is_err());
+}
+
+#[test]
+fn test_from_po_continuation_lines() {
+    let po = r#"# Translator comment
+msgid ""
+msgstr ""
+"Content-Type: text/plain; charset=UTF-8\n"
+
+msgctxt "srr2:E"
+msgid "0x00000010"
+msgstr ""
+"Start "
+"game"
+"#;
+    let tables = from_po(po).unwrap();
+    assert_eq!(tables.len(), 1);
+    assert_eq!(tables[0].strings["0x00000010"], "Start game");
+
+    assert!(from_po("msgid \"0x00000010\"\nmsgstr \"No context\"\n").is_err());
+}
@@ -0,0 +1,141 @@
+use super::write::test_tree;
+use crate::{
+    chunk::{
+        data::kinds::{
+            channel::Channel, mesh::UVList, name::Name, shader::Shader, version::Version,
+        },
+        visitor::{walk, walk_from, ChunkVisitor, VisitContext, Walk},
+    },
+    Result,
+};
+use eyre::eyre;
+
+#[derive(Default)]
+struct Recorder {
+    events: Vec<String>,
+    shaders: Vec<&'static str>,
+    uvs: usize,
+}
+
+impl<'a> ChunkVisitor<'a> for Recorder {
+    fn enter(&mut self, ctx: &VisitContext<'a, '_>) -> Result<Walk> {
+        self.events
+            .push(format!("enter {} {}", ctx.index, ctx.depth));
+        assert_eq!(
+            ctx.parent().map(|p| p.span.absolute_index),
+            ctx.chunk.parent
+        );
+        Ok(Walk::Continue)
+    }
+
+    fn leave(&mut self, ctx: &VisitContext<'a, '_>) -> Result<()> {
+        self.events.push(format!("leave {}", ctx.index));
+        Ok(())
+    }
+
+    fn visit_shader(
+        &mut self,
+        ctx: &VisitContext<'a, '_>,
+        name: &'a Name,
+        _: &'a Version,
+        _: &'a Shader,
+    ) -> Result<()> {
+        assert_eq!(name.0, "shader1");
+        assert_eq!(ctx.depth, ctx.ancestors.len());
+        self.shaders.push("shader1");
+        Ok(())
+    }
+
+    fn visit_uv_list(&mut self, ctx: &VisitContext<'a, '_>, data: &'a UVList) -> Result<()> {
+        assert_eq!(ctx.ancestors, &[0, 4, 5]);
+        assert!(ctx.lineage().contains("mesh1:Mesh"));
+        self.uvs += data.UVs.len();
+        Ok(())
+    }
+}
+
+#[test]
+fn test_walk_order() {
+    let tree = test_tree();
+    let mut recorder = Recorder::default();
+    walk(&tree, &mut recorder).unwrap();
+
+    assert_eq!(recorder.shaders, &["shader1"]);
+    assert_eq!(recorder.uvs, 3);
+    assert_eq!(
+        recorder.events,
+        [
+            "enter 0 0",
+            "enter 1 1",
+            "enter 2 2",
+            "leave 2",
+            "enter 3 2",
+            "leave 3",
+            "leave 1",
+            "enter 4 1",
+            "enter 5 2",
+            "enter 6 3",
+            "leave 6",
+            "enter 7 3",
+            "leave 7",
+            "enter 8 3",
+            "leave 8",
+            "leave 5",
+            "leave 4",
+            "enter 9 1",
+            "leave 9",
+            "leave 0",
+        ]
+    );
+
+    // Depths are relative to where the walk starts
+    let mut recorder = Recorder::default();
+    walk_from(&tree, 1, &mut recorder).unwrap();
+    assert_eq!(
+        recorder.events,
+        [
+            "enter 1 0",
+            "enter 2 1",
+            "leave 2",
+            "enter 3 1",
+            "leave 3",
+            "leave 1"
+        ]
+    );
+}
+
+struct SkipMeshes(usize);
+
+impl<'a> ChunkVisitor<'a> for SkipMeshes {
+    fn enter(&mut self, ctx: &VisitContext<'a, '_>) -> Result<Walk> {
+        self.0 += 1;
+        match ctx.chunk.data.get_name() {
+            Some(name) if name.0 == "mesh1" => Ok(Walk::SkipChildren),
+            _ => Ok(Walk::Continue),
+        }
+    }
+
+    fn visit_uv_list(&mut self, _: &VisitContext<'a, '_>, _: &'a UVList) -> Result<()> {
+        Err(eyre!("UV lists are under the skipped mesh"))
+    }
+
+    fn visit_channel(
+        &mut self,
+        ctx: &VisitContext<'a, '_>,
+        _: &'a Version,
+        _: &'a Channel,
+    ) -> Result<()> {
+        Err(eyre!("Stopped at {}", ctx.index))
+    }
+}
+
+#[test]
+fn test_walk_skip_and_error() {
+    let tree = test_tree();
+    let mut visitor = SkipMeshes(0);
+
+    let error = walk(&tree, &mut visitor).unwrap_err();
+    assert_eq!(error.to_string(), "Stopped at 9");
+    // Nothing under the mesh was entered
+    assert_eq!(visitor.0, 6);
+}
@@ -0,0 +1,379 @@
+use crate::{
+    chunk::{
+        data::{
+            data_enum::ChunkData,
+            kinds::{
+                channel::{Channel, ChannelValues},
+                mesh::{IndexList, Mesh, OldPrimGroup, PositionList, PrimitiveType, UVList},
+                name::Name,
+                shader::Shader,
+                shader_param::{ShaderParam, ShaderParamValue},
+                version::Version,
+            },
+        },
+        edit::TreeEdit,
+        type_identifiers::ChunkType,
+        Chunk, FileSpan, Span, VecChunkExtension,
+    },
+    error::ParseError,
+    parse_file, parse_file_with_options, parse_path, write_file, ParseOptions,
+};
+use bytes::Bytes;
+
+/// Pushes a chunk onto the tree, chunks must be pushed in the same depth-first order the parser produces.
+pub(super) fn push(
+    tree: &mut Vec<Chunk>,
+    parent: Option<usize>,
+    typ: ChunkType,
+    data: ChunkData,
+) -> usize {
+    let index = tree.len();
+    let relative_index = match parent {
+        Some(parent) => {
+            let parent = &mut tree[parent];
+            parent.children.push(index);
+            parent.children.len() - 1
+        }
+        None => 0,
+    };
+
+    tree.push(Chunk {
+        typ,
+        data,
+        span: Span {
+            absolute_index: index,
+            relative_index,
+        },
+        parent,
+        children: Vec::new(),
+        unparsed_data: Bytes::new(),
+        file_span: FileSpan::default(),
+    });
+
+    index
+}
+
+/// Forgets where a parsed tree came from, so it can be compared against one built by hand.
+pub(super) fn clear_file_spans(mut tree: Vec<Chunk>) -> Vec<Chunk> {
+    for chunk in &mut tree {
+        chunk.file_span = FileSpan::default();
+    }
+    tree
+}
+
+pub(super) fn test_tree() -> Vec<Chunk> {
+    let mut tree = Vec::new();
+    let root = push(&mut tree, None, ChunkType::DataFile, ChunkData::None);
+
+    let shader = push(
+        &mut tree,
+        Some(root),
+        ChunkType::Shader,
+        ChunkData::Shader(
+            Name("shader1".into()),
+            Version(0),
+            Shader {
+                pddi_shader_name: "simple".into(),
+                has_translucency: 0,
+                vertex_needs: 0.into(),
+                vertex_mask: 0xFFFFFFFF.into(),
+                num_params: 2,
+            },
+        ),
+    );
+    push(
+        &mut tree,
+        Some(shader),
+        ChunkType::ShaderTextureParam,
+        ChunkData::ShaderParam(ShaderParam {
+            param: "TEX".into(),
+            value: ShaderParamValue::Texture("swatchX.bmp".into()),
+        }),
+    );
+    push(
+        &mut tree,
+        Some(shader),
+        ChunkType::ShaderColourParam,
+        ChunkData::ShaderParam(ShaderParam {
+            param: "DIFF".into(),
+            value: ShaderParamValue::Colour([0xff, 0x10, 0x20, 0x30].into()),
+        }),
+    );
+
+    let mesh = push(
+        &mut tree,
+        Some(root),
+        ChunkType::Mesh,
+        ChunkData::Mesh(
+            Name("mesh1".into()),
+            Version(0),
+            Mesh { num_prim_groups: 1 },
+        ),
+    );
+    let group = push(
+        &mut tree,
+        Some(mesh),
+        ChunkType::OldPrimGroup,
+        ChunkData::PrimGroup(
+            Version(0),
+            OldPrimGroup {
+                shader_name: "shader1".into(),
+                primitive_type: PrimitiveType::TriangleList,
+                vertex_types: 0x2011.into(),
+                num_vertices: 3,
+                num_indices: 3,
+                num_matrices: 0,
+            },
+        ),
+    );
+    push(
+        &mut tree,
+        Some(group),
+        ChunkType::PositionList,
+        ChunkData::PositionList(PositionList {
+            positions: vec![
+                [0., 0., 0.].into(),
+                [1., 0., 0.].into(),
+                [0., 1., -0.5].into(),
+            ],
+        }),
+    );
+    push(
+        &mut tree,
+        Some(group),
+        ChunkType::UVList,
+        ChunkData::UVList(UVList {
+            channel: 0,
+            UVs: vec![[0., 0.].into(), [1., 0.].into(), [0., 1.].into()],
+        }),
+    );
+    push(
+        &mut tree,
+        Some(group),
+        ChunkType::IndexList,
+        ChunkData::IndexList(IndexList {
+            indices: vec![0, 1, 2],
+        }),
+    );
+
+    push(
+        &mut tree,
+        Some(root),
+        ChunkType::CompressedQuaternionChannel,
+        ChunkData::Channel(
+            Version(0),
+            Channel {
+                param: "ROT".into(),
+                frames: vec![0, 10],
+                values: ChannelValues::Quaternion(vec![
+                    [0., 0., 0., 1.].into(),
+                    [16384. / 32767., 0., 0., 28377. / 32767.].into(),
+                ]),
+            },
+        ),
+    );
+
+    tree
+}
+
+#[test]
+fn test_write_round_trip() {
+    let tree = test_tree();
+
+    let bytes = write_file(&tree).unwrap();
+    let parsed = parse_file(Bytes::from(bytes.clone())).expect("Failed to parse written file");
+
+    assert_eq!(write_file(&parsed).unwrap(), bytes);
+    assert_eq!(clear_file_spans(parsed), tree);
+}
+
+#[test]
+fn test_write_header_sizes() {
+    let tree = test_tree();
+    let bytes = write_file(&tree).unwrap();
+
+    let header = |offset: usize, field: usize| {
+        let start = offset + field * 4;
+        u32::from_le_bytes(bytes[start..start + 4].try_into().unwrap())
+    };
+
+    // Root chunk has no data, and its total size covers the whole file
+    assert_eq!(header(0, 0), u32::from(ChunkType::DataFile));
+    assert_eq!(header(0, 1), 12);
+    assert_eq!(header(0, 2), bytes.len() as u32);
+
+    // The shader is the first child: name (1 + 8) + version (4) + pddi name (1 + 8) + 4 u32s (16)
+    assert_eq!(header(12, 0), u32::from(ChunkType::Shader));
+    assert_eq!(header(12, 1), 12 + 38);
+    // TEX param: fourcc (4) + texture name (1 + 12), DIFF param: fourcc (4) + colour (4)
+    assert_eq!(header(12, 2), 12 + 38 + (12 + 17) + (12 + 8));
+
+    // Parsing gives back the same offsets and sizes
+    let parsed = parse_file(Bytes::from(bytes.clone())).expect("Failed to parse written file");
+    assert_eq!(
+        parsed[1].file_span,
+        FileSpan {
+            offset: 12,
+            data_size: 12 + 38,
+            total_size: 12 + 38 + (12 + 17) + (12 + 8),
+            big_endian: false,
+        }
+    );
+    // The TEX param comes straight after the shader's data
+    assert_eq!(parsed[2].file_span.offset, 12 + 12 + 38);
+    assert!(parsed[2]
+        .get_lineage(&parsed)
+        .ends_with(&format!("@{}", parsed[0].file_span)));
+}
+
+/// Builds a chunk by hand, header included.
+pub(super) fn raw_chunk(typ: ChunkType, data: &[u8], children: &[u8]) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    bytes.extend_from_slice(&u32::from(typ).to_le_bytes());
+    bytes.extend_from_slice(&(12 + data.len() as u32).to_le_bytes());
+    bytes.extend_from_slice(&(12 + (data.len() + children.len()) as u32).to_le_bytes());
+    bytes.extend_from_slice(data);
+    bytes.extend_from_slice(children);
+    bytes
+}
+
+#[test]
+fn test_unparsed_data_round_trip() {
+    // Nobody knows what's in a photon map
+    let unknown = raw_chunk(ChunkType::LightPhotonMap, &[1, 2, 3, 4, 5, 6], &[]);
+    // Render status only has a single u32 in it, so the second one is left over
+    let leftover = raw_chunk(
+        ChunkType::RenderStatus,
+        &[1, 0, 0, 0, 0xAA, 0xBB, 0xCC, 0xDD],
+        &[],
+    );
+    let children = [unknown, leftover].concat();
+    let bytes = raw_chunk(ChunkType::DataFile, &[], &children);
+
+    let file = parse_file(Bytes::from(bytes.clone())).expect("Failed to parse file");
+
+    assert_eq!(file[1].data, ChunkData::Unknown);
+    assert_eq!(
+        file[1].unparsed_data,
+        Bytes::from_static(&[1, 2, 3, 4, 5, 6])
+    );
+    assert_eq!(
+        file[2].unparsed_data,
+        Bytes::from_static(&[0xAA, 0xBB, 0xCC, 0xDD])
+    );
+    assert!(file[2].children.is_empty());
+
+    assert_eq!(write_file(&file).unwrap(), bytes);
+}
+
+#[test]
+fn test_misaligned_leftover_round_trip() {
+    // Render status only reads a single u32, then comes a child that belongs after the data,
+    // then bytes that aren't a chunk at all
+    let child = raw_chunk(ChunkType::RenderStatus, &[2, 0, 0, 0], &[]);
+    let data = [[1, 0, 0, 0].as_slice(), &child, &[0xAA, 0xBB, 0xCC]].concat();
+    let misaligned = raw_chunk(ChunkType::RenderStatus, &data, &[]);
+    let bytes = raw_chunk(ChunkType::DataFile, &[], &misaligned);
+
+    let file = parse_file(Bytes::from(bytes.clone())).expect("Failed to parse file");
+
+    assert_eq!(file[1].children, vec![2]);
+    assert_eq!(
+        file[1].unparsed_data,
+        Bytes::from_static(&[0xAA, 0xBB, 0xCC])
+    );
+
+    // The leftovers move in front of the child, but nothing is lost
+    let written = write_file(&file).unwrap();
+    assert_eq!(written.len(), bytes.len());
+    let reparsed = parse_file(Bytes::from(written)).expect("Failed to parse written file");
+    assert_eq!(reparsed.len(), file.len());
+    assert_eq!(reparsed[1].unparsed_data, file[1].unparsed_data);
+    assert_eq!(reparsed[2].data, file[2].data);
+}
+
+#[test]
+fn test_odd_strings_round_trip() {
+    // Latin-1, no padding, too much padding, and a texture name that isn't padded either
+    let bytes = include_bytes!("fixtures/odd_strings.p3d");
+
+    let (mut file, diagnostics) =
+        parse_file_with_options(Bytes::from_static(bytes), &ParseOptions::strict())
+            .expect("Failed to parse file");
+    assert!(diagnostics.is_empty());
+
+    let names: Vec<_> = file[1..4]
+        .iter()
+        .map(|chunk| chunk.data.get_name().unwrap().0)
+        .collect();
+    assert_eq!(names, ["Caf\u{e9}s", "abcde", "ab"]);
+    match &file[4].data {
+        ChunkData::ShaderParam(param) => {
+            assert_eq!(param.value, ShaderParamValue::Texture("road.bmp".into()))
+        }
+        data => panic!("Expected a shader param, got {:?}", data),
+    }
+
+    assert_eq!(write_file(&file).unwrap(), bytes);
+
+    // Changed strings are padded the usual way
+    file.rename(2, "xyz").unwrap();
+    let written = write_file(&file).unwrap();
+    assert_eq!(written.len(), bytes.len() - 1);
+    assert_eq!(&written[0x2D..0x32], b"\x04xyz\0");
+}
+
+#[test]
+fn test_unrecognised_chunk_round_trip() {
+    // An ID nobody has heard of, wrapping a chunk we do know about
+    let known = raw_chunk(ChunkType::RenderStatus, &[1, 0, 0, 0], &[]);
+    let custom = raw_chunk(ChunkType::Other(0x7FFF0001), &[9, 8, 7], &known);
+    let bytes = raw_chunk(ChunkType::DataFile, &[], &custom);
+
+    let file = parse_file(Bytes::from(bytes.clone())).expect("Failed to parse file");
+
+    assert_eq!(file[1].typ, ChunkType::Other(0x7FFF0001));
+    assert_eq!(file[1].data, ChunkData::Unknown);
+    assert_eq!(file[1].unparsed_data, Bytes::from_static(&[9, 8, 7]));
+    assert_eq!(file[1].children, vec![2]);
+    assert_eq!(file[2].typ, ChunkType::RenderStatus);
+
+    let unrecognised = file.get_unrecognised();
+    assert_eq!(unrecognised.len(), 1);
+    assert_eq!(unrecognised[0].0.span.absolute_index, 1);
+
+    assert_eq!(write_file(&file).unwrap(), bytes);
+}
+
+#[test]
+fn test_navigation() {
+    let tree = test_tree();
+    let shader = tree[0].get_child(&tree, 0).unwrap();
+    assert_eq!(shader.typ, ChunkType::Shader);
+    assert!(tree[0].get_child(&tree, 5).is_err());
+
+    let params: Vec<_> = shader
+        .get_children_of_type(&tree, ChunkType::ShaderColourParam)
+        .map(|c| c.span.absolute_index)
+        .collect();
+    assert_eq!(params, vec![3]);
+
+    assert_eq!(tree[3].get_parent(&tree), Some(shader));
+    assert_eq!(tree[0].get_parent(&tree), None);
+}
+
+#[test]
+fn test_parse_path() {
+    let tree = test_tree();
+    let path = std::env::temp_dir().join(format!("p3dparse_test_{}.p3d", std::process::id()));
+    std::fs::write(&path, write_file(&tree).unwrap()).unwrap();
+
+    // Parsed chunks can slice into the mapping, so they have to be dropped before the file is removed
+    assert_eq!(clear_file_spans(parse_path(&path).unwrap()), tree);
+    std::fs::remove_file(&path).unwrap();
+
+    assert!(matches!(
+        parse_path(&path),
+        Err(ParseError::Io { path: p, .. }) if p == path
+    ));
+}
@@ -0,0 +1,311 @@
+//! Localized strings, as stored in text bibles.
+//!
+//! [`string_tables`] pulls every language out of a tree as a key to string table, and
+//! [`apply_string_tables`] puts edited tables back. Language chunks only store a hash of each key,
+//! so keys are the hash written as `0x` and 8 hex digits. Tables can be saved as JSON with
+//! [`to_json`] and [`from_json`], or as gettext PO with [`to_po`] and [`from_po`].
+//!
+//! ```ignore
+//! let mut tree = p3dparse::parse_file(file)?;
+//! let mut tables = text::string_tables(&tree)?;
+//! tables[0].strings.insert("0x0000BEEF".into(), "Hello".into());
+//! text::apply_string_tables(&mut tree, &tables)?;
+//! let file = p3dparse::write_file(&tree)?;
+//! ```
+
+use std::collections::BTreeMap;
+
+use eyre::eyre;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    chunk::{data::data_enum::ChunkData, Chunk},
+    Result,
+};
+
+/// Every string of one language in one text bible.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StringTable {
+    /// Name of the text bible the language is in
+    pub bible: String,
+    pub language: char,
+    pub strings: BTreeMap<String, String>,
+}
+
+fn key(hash: u32) -> String {
+    format!("{:#010X}", hash).replace("0X", "0x")
+}
+
+fn parse_key(key: &str) -> Result<u32> {
+    key.strip_prefix("0x")
+        .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+        .ok_or_else(|| eyre!("{:?} isn't a string key, expected 0x and a hex hash", key))
+}
+
+/// The name of the text bible a language chunk is in, or an empty string if it's on its own.
+fn bible_name(chunk: &Chunk, tree: &[Chunk]) -> String {
+    chunk
+        .get_parent(tree)
+        .and_then(|parent| parent.data.get_name())
+        .map(|name| name.0.into_string())
+        .unwrap_or_default()
+}
+
+/// A table for every language chunk in the tree, in tree order.
+///
+/// A language can have the same hash more than once. That's fine as long as the strings match,
+/// since importing sets them all, but different strings under one hash can't be told apart by key
+/// and are an error.
+pub fn string_tables(tree: &[Chunk]) -> Result<Vec<StringTable>> {
+    let mut tables = Vec::new();
+
+    for chunk in tree {
+        let ChunkData::FrontendLanguage(_, language) = &chunk.data else {
+            continue;
+        };
+        let mut table = StringTable {
+            bible: bible_name(chunk, tree),
+            language: language.language as char,
+            strings: BTreeMap::new(),
+        };
+        for (hash, string) in language.entries() {
+            if let Some(existing) = table.strings.get(&key(hash)) {
+                if *existing != string {
+                    return Err(eyre!(
+                        "Language {:?} of text bible {:?} has different strings for {}: {:?} and {:?}",
+                        table.language,
+                        table.bible,
+                        key(hash),
+                        existing,
+                        string
+                    ));
+                }
+            }
+            table.strings.insert(key(hash), string);
+        }
+        tables.push(table);
+    }
+
+    Ok(tables)
+}
+
+/// Replaces the strings of every language that has a table. Languages without one are left
+/// alone, as are strings a table doesn't mention.
+///
+/// Only existing strings can be changed, since the game looks keys up by a hash it computes
+/// itself. Keys a language doesn't have, or tables for languages the tree doesn't have, are an
+/// error, and nothing is changed.
+pub fn apply_string_tables(tree: &mut [Chunk], tables: &[StringTable]) -> Result<()> {
+    let mut updates = Vec::new();
+
+    for table in tables {
+        let found = tree.iter().position(|chunk| match &chunk.data {
+            ChunkData::FrontendLanguage(_, language) => {
+                language.language as char == table.language
+                    && bible_name(chunk, tree) == table.bible
+            }
+            _ => false,
+        });
+        let Some(index) = found else {
+            return Err(eyre!(
+                "Text bible {:?} has no language {:?}",
+                table.bible,
+                table.language
+            ));
+        };
+        let ChunkData::FrontendLanguage(_, language) = &tree[index].data else {
+            unreachable!("Found chunk is a language");
+        };
+
+        let mut strings = BTreeMap::new();
+        for (key, string) in &table.strings {
+            let hash = parse_key(key)?;
+            if !language.hashes.contains(&hash) {
+                return Err(eyre!(
+                    "Language {:?} of text bible {:?} has no string {}",
+                    table.language,
+                    table.bible,
+                    key
+                ));
+            }
+            strings.insert(hash, string.as_str());
+        }
+
+        let entries: Vec<_> = language.entries().collect();
+        // Untouched languages keep their original bytes
+        if entries
+            .iter()
+            .any(|(hash, old)| strings.get(hash).is_some_and(|new| new != old))
+        {
+            updates.push((index, entries, strings));
+        }
+    }
+
+    for (index, entries, strings) in updates {
+        if let ChunkData::FrontendLanguage(_, language) = &mut tree[index].data {
+            language.set_entries(
+                entries
+                    .iter()
+                    .map(|(hash, old)| (*hash, strings.get(hash).copied().unwrap_or(old.as_str()))),
+            );
+        }
+    }
+
+    Ok(())
+}
+
+pub fn to_json(tables: &[StringTable]) -> Result<String> {
+    Ok(serde_json::to_string_pretty(tables)?)
+}
+
+pub fn from_json(json: &str) -> Result<Vec<StringTable>> {
+    Ok(serde_json::from_str(json)?)
+}
+
+fn po_escape(string: &str) -> String {
+    let mut escaped = String::with_capacity(string.len() + 2);
+    escaped.push('"');
+    for c in string.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '"' => escaped.push_str("\\\""),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+fn po_unescape(quoted: &str, line: usize) -> Result<String> {
+    let inner = quoted
+        .strip_prefix('"')
+        .and_then(|s| s.strip_suffix('"'))
+        .ok_or_else(|| eyre!("Line {}: expected a quoted string", line))?;
+
+    let mut string = String::with_capacity(inner.len());
+    let mut chars = inner.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            string.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('\\') => string.push('\\'),
+            Some('"') => string.push('"'),
+            Some('n') => string.push('\n'),
+            Some('r') => string.push('\r'),
+            Some('t') => string.push('\t'),
+            other => return Err(eyre!("Line {}: unknown escape \\{:?}", line, other)),
+        }
+    }
+    Ok(string)
+}
+
+/// One entry per string, with `bible:language` as the context and the key as the id.
+pub fn to_po(tables: &[StringTable]) -> String {
+    let mut po =
+        String::from("msgid \"\"\nmsgstr \"\"\n\"Content-Type: text/plain; charset=UTF-8\\n\"\n");
+    for table in tables {
+        let context = po_escape(&format!("{}:{}", table.bible, table.language));
+        for (key, string) in &table.strings {
+            po.push_str(&format!(
+                "\nmsgctxt {}\nmsgid {}\nmsgstr {}\n",
+                context,
+                po_escape(key),
+                po_escape(string)
+            ));
+        }
+    }
+    po
+}
+
+#[derive(Default)]
+struct PoEntry {
+    context: Option<String>,
+    id: Option<String>,
+    string: Option<String>,
+}
+
+/// Reads back what [`to_po`] writes. Comments are ignored, and so is the header.
+pub fn from_po(po: &str) -> Result<Vec<StringTable>> {
+    let mut tables: Vec<StringTable> = Vec::new();
+    let mut entry = PoEntry::default();
+    // Which field a continuation line adds to
+    let mut current: Option<fn(&mut PoEntry) -> &mut Option<String>> = None;
+
+    let mut finish = |entry: PoEntry, line: usize| -> Result<()> {
+        let (Some(id), Some(string)) = (entry.id, entry.string) else {
+            return Err(eyre!(
+                "Line {}: entry needs both a msgid and a msgstr",
+                line
+            ));
+        };
+        if id.is_empty() {
+            return Ok(());
+        }
+        let context = entry
+            .context
+            .ok_or_else(|| eyre!("Line {}: {:?} has no msgctxt", line, id))?;
+        let (bible, language) = context
+            .rsplit_once(':')
+            .filter(|(_, language)| language.chars().count() == 1)
+            .ok_or_else(|| eyre!("Line {}: expected bible:language, got {:?}", line, context))?;
+        let language = language.chars().next().unwrap();
+
+        let position = tables
+            .iter()
+            .position(|t| t.bible == bible && t.language == language);
+        let table = match position {
+            Some(position) => &mut tables[position],
+            None => {
+                tables.push(StringTable {
+                    bible: bible.to_owned(),
+                    language,
+                    strings: BTreeMap::new(),
+                });
+                tables.last_mut().unwrap()
+            }
+        };
+        table.strings.insert(id, string);
+        Ok(())
+    };
+
+    for (number, line) in po.lines().enumerate() {
+        let number = number + 1;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        if line.starts_with('"') {
+            let field =
+                current.ok_or_else(|| eyre!("Line {}: string outside of an entry", number))?;
+            field(&mut entry)
+                .get_or_insert_with(String::new)
+                .push_str(&po_unescape(line, number)?);
+            continue;
+        }
+
+        let (keyword, rest) = line.split_once(' ').unwrap_or((line, ""));
+        let field: fn(&mut PoEntry) -> &mut Option<String> = match keyword {
+            "msgctxt" => |e| &mut e.context,
+            "msgid" => |e| &mut e.id,
+            "msgstr" => |e| &mut e.string,
+            _ => return Err(eyre!("Line {}: unknown keyword {:?}", number, keyword)),
+        };
+        // A context or id after a msgstr starts the next entry
+        if keyword != "msgstr" && entry.string.is_some() {
+            finish(std::mem::take(&mut entry), number)?;
+        }
+        *field(&mut entry) = Some(po_unescape(rest.trim(), number)?);
+        current = Some(field);
+    }
+    if entry.id.is_some() || entry.string.is_some() {
+        finish(entry, po.lines().count())?;
+    }
+
+    Ok(tables)
+}
@@ -0,0 +1,255 @@
+//! Custom payloads, using only what's public so they're written the way a downstream crate would.
+
+use bytes::{BufMut, Bytes};
+use p3dparse::{
+    bytes_ext::BufResult,
+    chunk::{
+        data::{
+            custom::{CustomData, CustomPayload},
+            data_enum::ChunkData,
+            kinds::{name::Name, shared::Colour},
+            parse_trait::Parse,
+            schema::chunk_struct,
+            write_trait::Write,
+        },
+        registry::{parser, register_custom, register_parser},
+        type_identifiers::ChunkType,
+        Chunk,
+    },
+    parse_file, write_file, Result,
+};
+use serde::{Deserialize, Serialize};
+
+// Raw IDs nothing in p3dparse uses, every test gets its own since the registry is global
+const ROAD_SEGMENT: ChunkType = ChunkType::Other(0x0300_1234);
+const ROAD_SEGMENT_OLD: ChunkType = ChunkType::Other(0x0300_1235);
+const MARKER: ChunkType = ChunkType::Other(0x0300_1236);
+const ROAD_PAINT: ChunkType = ChunkType::Other(0x0300_1237);
+
+/// A chunk header in either byte order, followed by its data and children.
+fn raw_chunk(big_endian: bool, typ: ChunkType, data: &[u8], children: &[u8]) -> Vec<u8> {
+    let u32_bytes = |value: u32| {
+        if big_endian {
+            value.to_be_bytes()
+        } else {
+            value.to_le_bytes()
+        }
+    };
+
+    let mut bytes = Vec::new();
+    bytes.extend_from_slice(&u32_bytes(typ.into()));
+    bytes.extend_from_slice(&u32_bytes(12 + data.len() as u32));
+    bytes.extend_from_slice(&u32_bytes(12 + (data.len() + children.len()) as u32));
+    bytes.extend_from_slice(data);
+    bytes.extend_from_slice(children);
+    bytes
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+struct RoadSegment {
+    name: Name,
+    lanes: u32,
+    width: f32,
+}
+
+impl Parse for RoadSegment {
+    fn parse(bytes: &mut Bytes, typ: ChunkType) -> Result<Self> {
+        Ok(RoadSegment {
+            name: Name::parse(bytes, typ)?,
+            lanes: bytes.safe_get_u32()?,
+            width: bytes.safe_get_f32()?,
+        })
+    }
+}
+
+impl Write for RoadSegment {
+    fn write(&self, bytes: &mut Vec<u8>, typ: ChunkType) {
+        self.name.write(bytes, typ);
+        bytes.put_u32_le(self.lanes);
+        bytes.put_f32_le(self.width);
+    }
+}
+
+impl CustomPayload for RoadSegment {
+    const NAME: &'static str = "RoadSegment";
+
+    fn get_name(&self) -> Option<Name> {
+        Some(self.name.clone())
+    }
+
+    fn get_name_mut(&mut self) -> Option<&mut Name> {
+        Some(&mut self.name)
+    }
+}
+
+fn road_file(typ: ChunkType) -> Vec<u8> {
+    let mut data = Vec::new();
+    RoadSegment {
+        name: Name("road1".into()),
+        lanes: 2,
+        width: 7.5,
+    }
+    .write(&mut data, typ);
+    let child = raw_chunk(false, typ, &data, &[]);
+    raw_chunk(false, ChunkType::DataFile, &[], &child)
+}
+
+#[test]
+fn test_custom_payload() {
+    register_custom::<RoadSegment>(&[ROAD_SEGMENT, ROAD_SEGMENT_OLD]);
+
+    for typ in [ROAD_SEGMENT, ROAD_SEGMENT_OLD] {
+        let file = road_file(typ);
+        let tree = parse_file(Bytes::from(file.clone())).unwrap();
+        let ChunkData::Custom(data) = &tree[1].data else {
+            panic!("Expected custom data, got {:?}", tree[1].data);
+        };
+        assert_eq!(data.payload_name(), "RoadSegment");
+        assert_eq!(data.downcast_ref::<RoadSegment>().unwrap().lanes, 2);
+        assert_eq!(tree[1].data.get_name().unwrap().0, "road1");
+        assert!(tree[1].unparsed_data.is_empty());
+        assert_eq!(write_file(&tree).unwrap(), file);
+
+        // Serde goes through the registered name
+        let json = serde_json::to_string(&tree).unwrap();
+        assert!(json.contains(r#""Custom":{"name":"RoadSegment","data":{"name":"road1""#));
+        let back: Vec<Chunk> = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, tree);
+    }
+}
+
+#[test]
+fn test_custom_payload_unregistered() {
+    #[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+    struct Unregistered(u32);
+
+    impl Parse for Unregistered {
+        fn parse(bytes: &mut Bytes, _: ChunkType) -> Result<Self> {
+            Ok(Unregistered(bytes.safe_get_u32()?))
+        }
+    }
+
+    impl Write for Unregistered {
+        fn write(&self, bytes: &mut Vec<u8>, _: ChunkType) {
+            bytes.put_u32_le(self.0);
+        }
+    }
+
+    impl CustomPayload for Unregistered {
+        const NAME: &'static str = "Unregistered";
+    }
+
+    let data = CustomData::new(Unregistered(5));
+    assert_eq!(data.clone(), data);
+    assert_ne!(data, CustomData::new(Unregistered(6)));
+    assert!(data.downcast_ref::<RoadSegment>().is_none());
+    assert_eq!(data.get_name(), None);
+
+    // It can be built and serialized by hand, but nothing knows how to read it back
+    let json = serde_json::to_string(&ChunkData::Custom(data)).unwrap();
+    let err = serde_json::from_str::<ChunkData>(&json).unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("\"Unregistered\" is not registered"));
+}
+
+#[test]
+fn test_register_parser() {
+    assert!(parser(ChunkType::Mesh).is_some());
+    assert!(parser(MARKER).is_none());
+
+    let file = raw_chunk(
+        false,
+        ChunkType::DataFile,
+        &[],
+        &raw_chunk(false, MARKER, &[1, 2, 3, 4], &[]),
+    );
+    let tree = parse_file(Bytes::from(file.clone())).unwrap();
+    assert_eq!(tree[1].data, ChunkData::Unknown);
+
+    // Plain functions work as well as custom payloads, here one that ignores the data entirely
+    assert!(register_parser(MARKER, |_, _| Ok(ChunkData::None)).is_none());
+    assert!(register_parser(MARKER, |_, _| Ok(ChunkData::None)).is_some());
+    let tree = parse_file(Bytes::from(file)).unwrap();
+    assert_eq!(tree[1].data, ChunkData::None);
+    assert_eq!(tree[1].unparsed_data.as_ref(), &[1, 2, 3, 4]);
+}
+
+#[test]
+fn test_custom_payload_big_endian() {
+    register_custom::<RoadSegment>(&[ROAD_SEGMENT]);
+
+    let data = [
+        [4, b'r', b'o', b'a', b'd'].as_slice(),
+        &3u32.to_be_bytes(),
+        &10.25f32.to_be_bytes(),
+    ]
+    .concat();
+    let child = raw_chunk(true, ROAD_SEGMENT, &data, &[]);
+    let file = raw_chunk(true, ChunkType::DataFile, &[], &child);
+
+    let tree = parse_file(Bytes::from(file)).unwrap();
+    let ChunkData::Custom(data) = &tree[1].data else {
+        panic!("Expected custom data, got {:?}", tree[1].data);
+    };
+    assert_eq!(
+        data.downcast_ref::<RoadSegment>().unwrap(),
+        &RoadSegment {
+            name: Name("road".into()),
+            lanes: 3,
+            width: 10.25,
+        }
+    );
+}
+
+chunk_struct! {
+    pub struct RoadPaint {
+        pub name: String,
+        pub colour: Colour,
+        pub dashes: Vec<f32>,
+    }
+}
+
+impl CustomPayload for RoadPaint {
+    const NAME: &'static str = "RoadPaint";
+}
+
+#[test]
+fn test_custom_chunk_struct() {
+    register_custom::<RoadPaint>(&[ROAD_PAINT]);
+
+    for big_endian in [false, true] {
+        let u32_bytes = |value: u32| match big_endian {
+            true => value.to_be_bytes(),
+            false => value.to_le_bytes(),
+        };
+        let data = [
+            [4, b'l', b'i', b'n', b'e'].as_slice(),
+            // 0xAARRGGBB
+            &u32_bytes(0xFF10_2030),
+            &u32_bytes(2),
+            &u32_bytes(1.5f32.to_bits()),
+            &u32_bytes(0.5f32.to_bits()),
+        ]
+        .concat();
+        let child = raw_chunk(big_endian, ROAD_PAINT, &data, &[]);
+        let file = raw_chunk(big_endian, ChunkType::DataFile, &[], &child);
+
+        let tree = parse_file(Bytes::from(file.clone())).unwrap();
+        let ChunkData::Custom(data) = &tree[1].data else {
+            panic!("Expected custom data, got {:?}", tree[1].data);
+        };
+        assert_eq!(
+            data.downcast_ref::<RoadPaint>().unwrap(),
+            &RoadPaint {
+                name: "line".to_owned(),
+                colour: [0xFF, 0x10, 0x20, 0x30].into(),
+                dashes: vec![1.5, 0.5],
+            }
+        );
+        assert!(tree[1].unparsed_data.is_empty());
+        if !big_endian {
+            assert_eq!(write_file(&tree).unwrap(), file);
+        }
+    }
+}